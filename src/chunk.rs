@@ -1,11 +1,13 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cmp::max;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::mem;
+use std::sync::Arc;
 
 use crate::array::*;
-use crate::shader::Vertex;
-use gfx;
+use crate::minecraft::nbt::{Compound, Nbt};
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct BlockState {
     pub value: u16,
 }
@@ -17,7 +19,7 @@ pub struct BiomeId {
     pub value: u8,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct LightLevel {
     pub value: u8,
 }
@@ -33,87 +35,755 @@ impl LightLevel {
 
 pub const SIZE: usize = 16;
 
-/// A chunk of SIZE x SIZE x SIZE blocks, in YZX order.
-#[derive(Copy, Clone)]
+const VOLUME: usize = SIZE * SIZE * SIZE;
+
+/// SIZE x SIZE x SIZE values, stored as bit-packed indices into
+/// a palette of the distinct values, in YZX order.
+#[derive(Clone)]
+pub enum PalettedArray<T> {
+    // Fast path for sections made of a single value, e.g. all air.
+    Single(T),
+    Packed {
+        palette: Vec<T>,
+        bits: usize,
+        // Indices don't straddle words, leaving some bits unused.
+        data: Vec<u64>,
+    },
+}
+
+fn index(x: usize, y: usize, z: usize) -> usize {
+    (y * SIZE + z) * SIZE + x
+}
+
+fn pack<T: Copy + Eq + Hash>(values: &[T], palette: &[T], bits: usize) -> Vec<u64> {
+    let indices: HashMap<T, u64> = palette
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (v, i as u64))
+        .collect();
+    let per_word = 64 / bits;
+    let mut data = vec![0; VOLUME.div_ceil(per_word)];
+    for (i, v) in values.iter().enumerate() {
+        data[i / per_word] |= indices[v] << (i % per_word * bits);
+    }
+    data
+}
+
+fn read(data: &[u64], bits: usize, i: usize) -> usize {
+    let per_word = 64 / bits;
+    ((data[i / per_word] >> (i % per_word * bits)) & ((1 << bits) - 1)) as usize
+}
+
+fn bits_for(palette_len: usize) -> usize {
+    max(
+        1,
+        (usize::BITS - (palette_len - 1).leading_zeros()) as usize,
+    )
+}
+
+impl<T: Copy + Eq + Hash> PalettedArray<T> {
+    pub fn from_fn<F>(mut f: F) -> PalettedArray<T>
+    where
+        F: FnMut(usize, usize, usize) -> T,
+    {
+        let mut values = Vec::with_capacity(VOLUME);
+        let mut palette = vec![];
+        let mut seen = HashSet::new();
+        for y in 0..SIZE {
+            for z in 0..SIZE {
+                for x in 0..SIZE {
+                    let v = f(x, y, z);
+                    if seen.insert(v) {
+                        palette.push(v);
+                    }
+                    values.push(v);
+                }
+            }
+        }
+        if palette.len() == 1 {
+            return PalettedArray::Single(palette[0]);
+        }
+        let bits = bits_for(palette.len());
+        PalettedArray::Packed {
+            data: pack(&values, &palette, bits),
+            palette,
+            bits,
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> T {
+        match *self {
+            PalettedArray::Single(v) => v,
+            PalettedArray::Packed {
+                ref palette,
+                bits,
+                ref data,
+            } => palette[read(data, bits, index(x, y, z))],
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, z: usize, v: T) {
+        if let PalettedArray::Single(old) = *self {
+            if old == v {
+                return;
+            }
+            *self = PalettedArray::Packed {
+                palette: vec![old],
+                bits: 1,
+                data: vec![0; VOLUME / 64],
+            };
+        }
+        if let PalettedArray::Packed {
+            ref mut palette,
+            ref mut bits,
+            ref mut data,
+        } = *self
+        {
+            let p = match palette.iter().position(|&p| p == v) {
+                Some(p) => p,
+                None => {
+                    if palette.len() == 1 << *bits {
+                        // Out of indices, repack with wider ones.
+                        let values: Vec<T> =
+                            (0..VOLUME).map(|i| palette[read(data, *bits, i)]).collect();
+                        *bits += 1;
+                        *data = pack(&values, palette, *bits);
+                    }
+                    palette.push(v);
+                    palette.len() - 1
+                }
+            };
+            let i = index(x, y, z);
+            let per_word = 64 / *bits;
+            let shift = i % per_word * *bits;
+            let word = &mut data[i / per_word];
+            *word = (*word & !(((1 << *bits) - 1) << shift)) | ((p as u64) << shift);
+        }
+    }
+
+    /// Bytes allocated outside of the array itself.
+    pub fn heap_size(&self) -> usize {
+        match *self {
+            PalettedArray::Single(_) => 0,
+            PalettedArray::Packed {
+                ref palette,
+                ref data,
+                ..
+            } => palette.capacity() * mem::size_of::<T>() + data.capacity() * 8,
+        }
+    }
+}
+
+/// A chunk of SIZE x SIZE x SIZE blocks.
+#[derive(Clone)]
 pub struct Chunk {
-    pub blocks: [[[BlockState; SIZE]; SIZE]; SIZE],
-    pub light_levels: [[[LightLevel; SIZE]; SIZE]; SIZE],
+    blocks: PalettedArray<BlockState>,
+    light_levels: PalettedArray<LightLevel>,
 }
 
+impl Chunk {
+    pub fn from_fn<F>(mut f: F) -> Chunk
+    where
+        F: FnMut(usize, usize, usize) -> (BlockState, LightLevel),
+    {
+        // Decode each block once, for both arrays.
+        let mut cells = Vec::with_capacity(VOLUME);
+        for y in 0..SIZE {
+            for z in 0..SIZE {
+                for x in 0..SIZE {
+                    cells.push(f(x, y, z));
+                }
+            }
+        }
+        Chunk {
+            blocks: PalettedArray::from_fn(|x, y, z| cells[index(x, y, z)].0),
+            light_levels: PalettedArray::from_fn(|x, y, z| cells[index(x, y, z)].1),
+        }
+    }
+
+    pub fn block(&self, x: usize, y: usize, z: usize) -> BlockState {
+        self.blocks.get(x, y, z)
+    }
+
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, block: BlockState) {
+        self.blocks.set(x, y, z, block)
+    }
+
+    pub fn light_level(&self, x: usize, y: usize, z: usize) -> LightLevel {
+        self.light_levels.get(x, y, z)
+    }
+
+    pub fn set_light_level(&mut self, x: usize, y: usize, z: usize, light: LightLevel) {
+        self.light_levels.set(x, y, z, light)
+    }
+
+    /// Total memory used by the chunk, in bytes.
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Chunk>() + self.blocks.heap_size() + self.light_levels.heap_size()
+    }
+}
+
+// Memory used by a chunk before palette compression.
+pub const UNPACKED_CHUNK_SIZE: usize =
+    VOLUME * (mem::size_of::<BlockState>() + mem::size_of::<LightLevel>());
+
 // TODO: Change to const pointer.
 pub const EMPTY_CHUNK: &Chunk = &Chunk {
-    blocks: [[[EMPTY_BLOCK; SIZE]; SIZE]; SIZE],
-    light_levels: [[[LightLevel { value: 0xf0 }; SIZE]; SIZE]; SIZE],
+    blocks: PalettedArray::Single(EMPTY_BLOCK),
+    light_levels: PalettedArray::Single(LightLevel { value: 0xf0 }),
 };
 
-pub struct ChunkColumn<R: gfx::Resources> {
-    pub chunks: Vec<Chunk>,
-    pub buffers: [RefCell<Option<gfx::handle::Buffer<R, Vertex>>>; SIZE],
+pub struct ChunkColumn {
+    // Shared with the meshing threads, copied on write.
+    pub chunks: Vec<Arc<Chunk>>,
     pub biomes: [[BiomeId; SIZE]; SIZE],
+    // Whether the saved light levels can be used as-is.
+    pub light_populated: bool,
+    // Extra data of blocks like chests and signs, by world position.
+    pub tile_entities: HashMap<[i32; 3], Compound>,
+}
+
+impl ChunkColumn {
+    /// A lit column without any sections, for tests.
+    #[cfg(test)]
+    pub fn empty() -> ChunkColumn {
+        ChunkColumn {
+            chunks: vec![],
+            biomes: [[BiomeId { value: 0 }; SIZE]; SIZE],
+            light_populated: true,
+            tile_entities: HashMap::new(),
+        }
+    }
+}
+
+/// Everything stored about a single block.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BlockSnapshot {
+    pub block: BlockState,
+    pub light: LightLevel,
+    pub tile_entity: Option<Compound>,
+}
+
+/// A section and its neighbors, as needed for building its vertices.
+pub struct ChunkNeighborhood {
+    pub coords: [i32; 3],
+    chunks: [[[Option<Arc<Chunk>>; 3]; 3]; 3],
+    biomes: [[Option<[[BiomeId; SIZE]; SIZE]>; 3]; 3],
+}
+
+impl ChunkNeighborhood {
+    pub fn chunks(&self) -> [[[&Chunk; 3]; 3]; 3] {
+        self.chunks.each_ref().map(|cy| {
+            cy.each_ref()
+                .map(|cz| cz.each_ref().map(|c| c.as_deref().unwrap_or(EMPTY_CHUNK)))
+        })
+    }
+
+    pub fn biomes(&self) -> [[Option<&[[BiomeId; SIZE]; SIZE]>; 3]; 3] {
+        self.biomes
+            .each_ref()
+            .map(|cz| cz.each_ref().map(|c| c.as_ref()))
+    }
+}
+
+pub struct ChunkManager {
+    chunk_columns: HashMap<(i32, i32), ChunkColumn>,
+    // Sections whose vertex buffers need to be (re)built.
+    dirty: HashSet<[i32; 3]>,
+    // Columns whose light levels need to be computed.
+    unlit: HashSet<(i32, i32)>,
+    // Columns with changes which weren't saved yet.
+    modified: HashSet<(i32, i32)>,
+    // The original state of blocks changed while recording.
+    recording: Option<HashMap<[i32; 3], BlockSnapshot>>,
+}
+
+/// Splits a world coordinate into chunk and in-chunk coordinates.
+fn split(x: i32) -> (i32, usize) {
+    (x >> 4, (x & 0xf) as usize)
 }
 
-pub struct ChunkManager<R: gfx::Resources> {
-    chunk_columns: HashMap<(i32, i32), ChunkColumn<R>>,
+impl Default for ChunkManager {
+    fn default() -> ChunkManager {
+        ChunkManager::new()
+    }
 }
 
-impl<R: gfx::Resources> ChunkManager<R> {
-    pub fn new() -> ChunkManager<R> {
+impl ChunkManager {
+    pub fn new() -> ChunkManager {
         ChunkManager {
             chunk_columns: HashMap::new(),
+            dirty: HashSet::new(),
+            unlit: HashSet::new(),
+            modified: HashSet::new(),
+            recording: None,
         }
     }
 
-    pub fn add_chunk_column(&mut self, x: i32, z: i32, c: ChunkColumn<R>) {
+    pub fn add_chunk_column(&mut self, x: i32, z: i32, c: ChunkColumn) {
+        if !c.light_populated {
+            self.unlit.insert((x, z));
+        }
         self.chunk_columns.insert((x, z), c);
+
+        // The new column affects the meshes of its neighbors' edges.
+        self.mark_column_and_neighbors_dirty(x, z);
+    }
+
+    fn mark_column_and_neighbors_dirty(&mut self, x: i32, z: i32) {
+        for dz in -1..2 {
+            for dx in -1..2 {
+                if let Some(column) = self.chunk_columns.get(&(x + dx, z + dz)) {
+                    for y in 0..column.chunks.len() as i32 {
+                        self.dirty.insert([x + dx, y, z + dz]);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes a column, e.g. when it gets too far from the camera.
+    pub fn remove_chunk_column(&mut self, x: i32, z: i32) -> Option<ChunkColumn> {
+        let column = self.chunk_columns.remove(&(x, z))?;
+        self.unlit.remove(&(x, z));
+        self.modified.remove(&(x, z));
+
+        // Neighbors now have an open edge, which needs to be meshed.
+        self.mark_column_and_neighbors_dirty(x, z);
+        Some(column)
+    }
+
+    pub fn has_chunk_column(&self, x: i32, z: i32) -> bool {
+        self.chunk_columns.contains_key(&(x, z))
+    }
+
+    pub fn chunk_column_coords(&self) -> Vec<(i32, i32)> {
+        self.chunk_columns.keys().cloned().collect()
+    }
+
+    pub fn chunk_column(&self, x: i32, z: i32) -> Option<&ChunkColumn> {
+        self.chunk_columns.get(&(x, z))
+    }
+
+    pub fn is_modified(&self, x: i32, z: i32) -> bool {
+        self.modified.contains(&(x, z))
+    }
+
+    /// Returns the columns with unsaved changes.
+    pub fn modified_columns(&self) -> Vec<(i32, i32)> {
+        self.modified.iter().cloned().collect()
+    }
+
+    pub fn mark_saved(&mut self, x: i32, z: i32) {
+        self.modified.remove(&(x, z));
+    }
+
+    fn chunk(&self, x: i32, y: i32, z: i32) -> Option<(&Chunk, [usize; 3])> {
+        if y < 0 || y >= (SIZE * SIZE) as i32 {
+            return None;
+        }
+        let ((cx, x), (cy, y), (cz, z)) = (split(x), split(y), split(z));
+        let column = self.chunk_columns.get(&(cx, cz))?;
+        let chunk = column.chunks.get(cy as usize).map_or(EMPTY_CHUNK, |c| &**c);
+        Some((chunk, [x, y, z]))
+    }
+
+    pub fn get_block(&self, x: i32, y: i32, z: i32) -> Option<BlockState> {
+        self.chunk(x, y, z)
+            .map(|(chunk, [x, y, z])| chunk.block(x, y, z))
+    }
+
+    pub fn get_light(&self, x: i32, y: i32, z: i32) -> Option<LightLevel> {
+        self.chunk(x, y, z)
+            .map(|(chunk, [x, y, z])| chunk.light_level(x, y, z))
+    }
+
+    pub fn get_biome(&self, x: i32, z: i32) -> Option<BiomeId> {
+        let ((cx, x), (cz, z)) = (split(x), split(z));
+        self.chunk_columns
+            .get(&(cx, cz))
+            .map(|column| column.biomes[z][x])
+    }
+
+    fn chunk_mut(&mut self, x: i32, y: i32, z: i32) -> Option<(&mut Chunk, [usize; 3])> {
+        if y < 0 || y >= (SIZE * SIZE) as i32 {
+            return None;
+        }
+        let ((cx, x), (cy, y), (cz, z)) = (split(x), split(y), split(z));
+        let column = self.chunk_columns.get_mut(&(cx, cz))?;
+        while column.chunks.len() <= cy as usize {
+            column.chunks.push(Arc::new(EMPTY_CHUNK.clone()));
+        }
+        Some((Arc::make_mut(&mut column.chunks[cy as usize]), [x, y, z]))
+    }
+
+    fn mark_block_dirty(&mut self, x: i32, y: i32, z: i32) {
+        // Meshing looks at all 26 neighbors of each block, so blocks
+        // on a section border also affect the adjacent sections.
+        let range = |v: i32| match v & 15 {
+            0 => -1..=0,
+            15 => 0..=1,
+            _ => 0..=0,
+        };
+        for dy in range(y) {
+            for dz in range(z) {
+                for dx in range(x) {
+                    let (cx, cy, cz) = ((x >> 4) + dx, (y >> 4) + dy, (z >> 4) + dz);
+                    if cy >= 0 && cy < SIZE as i32 && self.chunk_columns.contains_key(&(cx, cz)) {
+                        self.dirty.insert([cx, cy, cz]);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn tile_entity(&self, x: i32, y: i32, z: i32) -> Option<&Compound> {
+        self.chunk_columns
+            .get(&(x >> 4, z >> 4))?
+            .tile_entities
+            .get(&[x, y, z])
+    }
+
+    /// Everything about a block, or `None` if it isn't loaded.
+    pub fn snapshot(&self, x: i32, y: i32, z: i32) -> Option<BlockSnapshot> {
+        Some(BlockSnapshot {
+            block: self.get_block(x, y, z)?,
+            light: self.get_light(x, y, z)?,
+            tile_entity: self.tile_entity(x, y, z).cloned(),
+        })
+    }
+
+    /// Starts remembering the original state of changed blocks.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(HashMap::new());
+    }
+
+    /// Returns the original state of the blocks changed since
+    /// `start_recording`, and stops recording.
+    pub fn stop_recording(&mut self) -> Vec<([i32; 3], BlockSnapshot)> {
+        self.recording
+            .take()
+            .map_or(vec![], |changes| changes.into_iter().collect())
+    }
+
+    // Remembers a block before its first change while recording.
+    fn record(&mut self, x: i32, y: i32, z: i32) {
+        if self
+            .recording
+            .as_ref()
+            .is_some_and(|r| !r.contains_key(&[x, y, z]))
+        {
+            if let Some(snapshot) = self.snapshot(x, y, z) {
+                self.recording.as_mut().unwrap().insert([x, y, z], snapshot);
+            }
+        }
+    }
+
+    /// Puts a block back in a previous state, returning whether it was loaded.
+    /// The light isn't updated for neighbors, as they're expected to be restored too.
+    pub fn restore(&mut self, x: i32, y: i32, z: i32, snapshot: &BlockSnapshot) -> bool {
+        if self.set_block(x, y, z, snapshot.block).is_none() {
+            return false;
+        }
+        self.set_tile_entity(x, y, z, snapshot.tile_entity.clone());
+        self.set_light(x, y, z, snapshot.light)
+    }
+
+    /// Replaces the tile entity of a block, returning the previous one.
+    pub fn set_tile_entity(
+        &mut self,
+        x: i32,
+        y: i32,
+        z: i32,
+        mut tile_entity: Option<Compound>,
+    ) -> Option<Compound> {
+        // The position is part of the tile entity, as saved.
+        if let Some(tile_entity) = &mut tile_entity {
+            for (k, &v) in ["x", "y", "z"].iter().zip(&[x, y, z]) {
+                tile_entity.insert(k.to_string(), Nbt::Int(v));
+            }
+        }
+        if !self.has_chunk_column(x >> 4, z >> 4) {
+            return None;
+        }
+        if self.tile_entity(x, y, z) == tile_entity.as_ref() {
+            return tile_entity;
+        }
+        self.record(x, y, z);
+        self.modified.insert((x >> 4, z >> 4));
+        let tile_entities = &mut self.chunk_columns.get_mut(&(x >> 4, z >> 4))?.tile_entities;
+        match tile_entity {
+            Some(tile_entity) => tile_entities.insert([x, y, z], tile_entity),
+            None => tile_entities.remove(&[x, y, z]),
+        }
+    }
+
+    /// Changes a block, returning the previous one, or `None`
+    /// if the position is outside of the loaded world.
+    /// The tile entity goes away if the block type changes.
+    pub fn set_block(&mut self, x: i32, y: i32, z: i32, block: BlockState) -> Option<BlockState> {
+        self.record(x, y, z);
+        let old = {
+            let (chunk, [bx, by, bz]) = self.chunk_mut(x, y, z)?;
+            let old = chunk.block(bx, by, bz);
+            chunk.set_block(bx, by, bz, block);
+            old
+        };
+        self.modified.insert((x >> 4, z >> 4));
+        self.mark_block_dirty(x, y, z);
+        if old.value >> 4 != block.value >> 4 {
+            self.set_tile_entity(x, y, z, None);
+        }
+        Some(old)
+    }
+
+    /// Changes the light level of a block after an edit,
+    /// returning whether it was loaded.
+    pub fn set_light(&mut self, x: i32, y: i32, z: i32, light: LightLevel) -> bool {
+        self.change_light(x, y, z, light, true)
+    }
+
+    /// Like `set_light`, for light computed after loading a column,
+    /// which doesn't need saving.
+    pub fn set_computed_light(&mut self, x: i32, y: i32, z: i32, light: LightLevel) -> bool {
+        self.change_light(x, y, z, light, false)
+    }
+
+    fn change_light(&mut self, x: i32, y: i32, z: i32, light: LightLevel, edit: bool) -> bool {
+        match self.get_light(x, y, z) {
+            Some(old) if old == light => return true,
+            Some(_) => {}
+            None => return false,
+        }
+        self.record(x, y, z);
+        let (chunk, [bx, by, bz]) = self.chunk_mut(x, y, z).unwrap();
+        chunk.set_light_level(bx, by, bz, light);
+        if edit {
+            self.modified.insert((x >> 4, z >> 4));
+        }
+        self.mark_block_dirty(x, y, z);
+        true
+    }
+
+    /// Returns the number of loaded sections and the memory they use.
+    pub fn memory_usage(&self) -> (usize, usize) {
+        self.chunk_columns
+            .values()
+            .flat_map(|column| column.chunks.iter())
+            .fold((0, 0), |(n, size), c| (n + 1, size + c.memory_usage()))
+    }
+
+    /// Returns the columns added without light since the last call.
+    pub fn take_unlit_columns(&mut self) -> Vec<(i32, i32)> {
+        self.unlit.drain().collect()
+    }
+
+    /// Returns the sections which were modified since the last call.
+    pub fn take_dirty(&mut self) -> Vec<[i32; 3]> {
+        self.dirty.drain().collect()
+    }
+
+    /// Returns the blocks and biomes around a section,
+    /// as needed for building its vertices.
+    #[allow(clippy::type_complexity)]
+    pub fn chunk_and_neighbors(
+        &self,
+        [x, y, z]: [i32; 3],
+    ) -> Option<(
+        [[[&Chunk; 3]; 3]; 3],
+        [[Option<&[[BiomeId; SIZE]; SIZE]>; 3]; 3],
+    )> {
+        let columns =
+            [-1, 0, 1].map(|dz| [-1, 0, 1].map(|dx| self.chunk_columns.get(&(x + dx, z + dz))));
+        let central = columns[1][1]?;
+        if y < 0 || y as usize >= central.chunks.len() {
+            return None;
+        }
+        let chunks = [-1, 0, 1].map(|dy| {
+            let y = y + dy;
+            columns.map(|cz| {
+                cz.map(|cx| {
+                    cx.and_then(|c| c.chunks[..].get(y as usize))
+                        .map_or(EMPTY_CHUNK, |c| &**c)
+                })
+            })
+        });
+        Some((chunks, columns.map(|cz| cz.map(|cx| cx.map(|c| &c.biomes)))))
+    }
+
+    /// Like `chunk_and_neighbors`, but sharing the chunks
+    /// so that they can be sent to another thread.
+    pub fn neighborhood(&self, [x, y, z]: [i32; 3]) -> Option<ChunkNeighborhood> {
+        let columns =
+            [-1, 0, 1].map(|dz| [-1, 0, 1].map(|dx| self.chunk_columns.get(&(x + dx, z + dz))));
+        let central = columns[1][1]?;
+        if y < 0 || y as usize >= central.chunks.len() {
+            return None;
+        }
+        let chunks = [-1, 0, 1].map(|dy| {
+            let y = y + dy;
+            columns.map(|cz| cz.map(|cx| cx.and_then(|c| c.chunks[..].get(y as usize)).cloned()))
+        });
+        Some(ChunkNeighborhood {
+            coords: [x, y, z],
+            chunks,
+            biomes: columns.map(|cz| cz.map(|cx| cx.map(|c| c.biomes))),
+        })
     }
 
     pub fn each_chunk_and_neighbors<'a, F>(&'a self, mut f: F)
     where
         F: FnMut(
             /*coords:*/ [i32; 3],
-            /*buffer:*/ &'a RefCell<Option<gfx::handle::Buffer<R, Vertex>>>,
             /*chunks:*/ [[[&'a Chunk; 3]; 3]; 3],
             /*biomes:*/ [[Option<&'a [[BiomeId; SIZE]; SIZE]>; 3]; 3],
         ),
     {
-        for &(x, z) in self.chunk_columns.keys() {
-            let columns =
-                [-1, 0, 1].map(|dz| [-1, 0, 1].map(|dx| self.chunk_columns.get(&(x + dx, z + dz))));
-            let central = columns[1][1].unwrap();
-            for y in 0..central.chunks.len() {
-                let chunks = [-1, 0, 1].map(|dy| {
-                    let y = y as i32 + dy;
-                    columns.map(|cz| {
-                        cz.map(|cx| {
-                            cx.and_then(|c| c.chunks[..].get(y as usize))
-                                .unwrap_or(EMPTY_CHUNK)
-                        })
-                    })
-                });
-                f(
-                    [x, y as i32, z],
-                    &central.buffers[y],
-                    chunks,
-                    columns.map(|cz| cz.map(|cx| cx.map(|c| &c.biomes))),
-                )
+        for (&(x, z), column) in self.chunk_columns.iter() {
+            for y in 0..column.chunks.len() as i32 {
+                let (chunks, biomes) = self.chunk_and_neighbors([x, y, z]).unwrap();
+                f([x, y, z], chunks, biomes)
             }
         }
     }
 
     pub fn each_chunk<F>(&self, mut f: F)
     where
-        F: FnMut(
-            /*x:*/ i32,
-            /*y:*/ i32,
-            /*z:*/ i32,
-            /*c:*/ &Chunk,
-            /*b:*/ &RefCell<Option<gfx::handle::Buffer<R, Vertex>>>,
-        ),
+        F: FnMut(/*x:*/ i32, /*y:*/ i32, /*z:*/ i32, /*c:*/ &Chunk),
     {
         for (&(x, z), c) in self.chunk_columns.iter() {
-            for (y, (c, b)) in c.chunks.iter().zip(c.buffers.iter()).enumerate() {
-                f(x, y as i32, z, c, b)
+            for (y, c) in c.chunks.iter().enumerate() {
+                f(x, y as i32, z, c)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits<T>(array: &PalettedArray<T>) -> Option<usize> {
+        match *array {
+            PalettedArray::Single(_) => None,
+            PalettedArray::Packed { bits, .. } => Some(bits),
+        }
+    }
+
+    #[test]
+    fn single() {
+        let mut array = PalettedArray::from_fn(|_, _, _| 7u16);
+        assert_eq!(bits(&array), None);
+        array.set(3, 4, 5, 7);
+        assert_eq!(bits(&array), None);
+        assert_eq!(array.heap_size(), 0);
+    }
+
+    #[test]
+    fn single_to_packed() {
+        let mut array = PalettedArray::Single(0u16);
+        array.set(1, 2, 3, 9);
+        assert_eq!(bits(&array), Some(1));
+        assert_eq!(array.get(1, 2, 3), 9);
+        assert_eq!(array.get(3, 2, 1), 0);
+        assert_eq!(array.get(15, 15, 15), 0);
+    }
+
+    #[test]
+    fn set_and_repack() {
+        let mut array = PalettedArray::from_fn(|x, _, _| (x % 2) as u16);
+        assert_eq!(bits(&array), Some(1));
+        // 17 values need 5 bits, repacking at 3, 5, 9 and 17.
+        for v in 2..17 {
+            array.set(v as usize - 2, 1, 0, v);
+        }
+        assert_eq!(bits(&array), Some(5));
+        for y in 0..SIZE {
+            for z in 0..SIZE {
+                for x in 0..SIZE {
+                    let expected = match (x, y, z) {
+                        (x, 1, 0) if x < 15 => x as u16 + 2,
+                        _ => (x % 2) as u16,
+                    };
+                    assert_eq!(array.get(x, y, z), expected, "at {} {} {}", x, y, z);
+                }
             }
         }
+        // Overwriting keeps the palette.
+        array.set(0, 0, 0, 1);
+        assert_eq!(array.get(0, 0, 0), 1);
+        assert_eq!(array.get(1, 0, 0), 1);
+    }
+
+    #[test]
+    fn chunk_from_fn() {
+        let chunk = Chunk::from_fn(|x, y, z| {
+            (
+                BlockState {
+                    value: (x + z) as u16,
+                },
+                LightLevel { value: y as u8 },
+            )
+        });
+        assert_eq!(chunk.block(3, 9, 4).value, 7);
+        assert_eq!(chunk.light_level(3, 9, 4).value, 9);
+    }
+
+    const STONE: BlockState = BlockState { value: 1 << 4 };
+
+    // Two columns side by side, with nothing dirty.
+    fn manager() -> ChunkManager {
+        let mut manager = ChunkManager::new();
+        manager.add_chunk_column(0, 0, ChunkColumn::empty());
+        manager.add_chunk_column(1, 0, ChunkColumn::empty());
+        manager.take_dirty();
+        manager
+    }
+
+    fn dirty(manager: &mut ChunkManager) -> Vec<[i32; 3]> {
+        let mut dirty = manager.take_dirty();
+        dirty.sort();
+        dirty
+    }
+
+    #[test]
+    fn interior_edits_dirty_their_section() {
+        let mut manager = manager();
+        manager.set_block(5, 20, 5, STONE);
+        assert_eq!(dirty(&mut manager), vec![[0, 1, 0]]);
+        assert_eq!(manager.get_block(5, 20, 5), Some(STONE));
+    }
+
+    #[test]
+    fn edge_edits_dirty_neighbors() {
+        let mut manager = manager();
+        manager.set_block(15, 16, 8, STONE);
+        assert_eq!(
+            dirty(&mut manager),
+            vec![[0, 0, 0], [0, 1, 0], [1, 0, 0], [1, 1, 0]]
+        );
+        // Not unloaded columns or sections below the world.
+        manager.set_block(0, 0, 0, STONE);
+        assert_eq!(dirty(&mut manager), vec![[0, 0, 0]]);
+    }
+
+    #[test]
+    fn unloaded_columns() {
+        let manager = manager();
+        assert_eq!(manager.get_block(40, 5, 0), None);
+        assert_eq!(manager.get_light(-1, 5, 0), None);
+        assert!(manager.get_biome(8, 16).is_none());
+        assert_eq!(manager.get_light(8, -1, 8), None);
+        assert_eq!(manager.get_light(8, 256, 8), None);
+    }
+
+    #[test]
+    fn missing_sections() {
+        // Loaded columns are open to the sky above their sections.
+        let manager = manager();
+        assert_eq!(manager.get_block(8, 100, 8), Some(EMPTY_BLOCK));
+        let light = manager.get_light(8, 100, 8).unwrap();
+        assert_eq!((light.sky_light(), light.block_light()), (15, 0));
+        assert_eq!(manager.get_biome(31, 15).map(|b| b.value), Some(0));
     }
 }
@@ -0,0 +1,119 @@
+use vecmath::{col_mat4_mul, Matrix4};
+
+/// The volume seen by the camera, as six planes facing inwards.
+#[derive(Copy, Clone, Debug)]
+pub struct Frustum {
+    // Normal and distance to the origin of each plane, with the
+    // inside of the frustum where `n · p + d >= 0`.
+    planes: [[f32; 4]; 6],
+}
+
+impl Frustum {
+    pub fn new(projection: Matrix4<f32>, view: Matrix4<f32>) -> Frustum {
+        Frustum::from_matrix(col_mat4_mul(projection, view))
+    }
+
+    /// Extracts the planes from a column-major view-projection
+    /// matrix, with OpenGL's -1 to 1 depth range.
+    pub fn from_matrix(m: Matrix4<f32>) -> Frustum {
+        let row = |i: usize| [m[0][i], m[1][i], m[2][i], m[3][i]];
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        let add = |a: [f32; 4], b: [f32; 4], s: f32| [0, 1, 2, 3].map(|i| a[i] + s * b[i]);
+        let planes = [
+            add(w, x, 1.0),
+            add(w, x, -1.0),
+            add(w, y, 1.0),
+            add(w, y, -1.0),
+            add(w, z, 1.0),
+            add(w, z, -1.0),
+        ]
+        .map(|p| {
+            let len = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+            p.map(|x| x / len)
+        });
+        Frustum { planes }
+    }
+
+    pub fn contains_point(&self, p: [f32; 3]) -> bool {
+        self.planes
+            .iter()
+            .all(|n| n[0] * p[0] + n[1] * p[1] + n[2] * p[2] + n[3] >= 0.0)
+    }
+
+    /// Whether any part of a box might be visible. Boxes near the
+    /// edges of the frustum can pass while being just outside of it,
+    /// which is fine for culling.
+    pub fn intersects_aabb(&self, min: [f32; 3], max: [f32; 3]) -> bool {
+        self.planes.iter().all(|n| {
+            // The corner furthest along the normal.
+            let p = [0, 1, 2].map(|i| if n[i] >= 0.0 { max[i] } else { min[i] });
+            n[0] * p[0] + n[1] * p[1] + n[2] * p[2] + n[3] >= 0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Frustum;
+
+    // Looking down -z from the origin, 90° wide and high, seeing 0.1 to 100.
+    fn frustum() -> Frustum {
+        let projection = camera_controllers::CameraPerspective {
+            fov: 90.0,
+            near_clip: 0.1,
+            far_clip: 100.0,
+            aspect_ratio: 1.0,
+        }
+        .projection();
+        let identity = vecmath::mat4_id();
+        Frustum::new(projection, identity)
+    }
+
+    #[test]
+    fn box_in_front_is_visible() {
+        assert!(frustum().intersects_aabb([-1.0, -1.0, -10.0], [1.0, 1.0, -8.0]));
+    }
+
+    #[test]
+    fn box_behind_is_culled() {
+        assert!(!frustum().intersects_aabb([-1.0, -1.0, 5.0], [1.0, 1.0, 8.0]));
+    }
+
+    #[test]
+    fn box_around_camera_is_visible() {
+        // Crosses the near plane, with corners behind the camera.
+        assert!(frustum().intersects_aabb([-8.0, -8.0, -8.0], [8.0, 8.0, 8.0]));
+        assert!(frustum().intersects_aabb([-16.0, 0.0, -0.5], [16.0, 16.0, 16.0]));
+    }
+
+    #[test]
+    fn box_beside_is_culled() {
+        assert!(!frustum().intersects_aabb([20.0, -1.0, -10.0], [22.0, 1.0, -8.0]));
+        assert!(!frustum().intersects_aabb([-1.0, -22.0, -10.0], [1.0, -20.0, -8.0]));
+    }
+
+    #[test]
+    fn box_crossing_a_side_is_visible() {
+        assert!(frustum().intersects_aabb([9.0, -1.0, -10.0], [12.0, 1.0, -8.0]));
+    }
+
+    #[test]
+    fn box_past_far_plane_is_culled() {
+        assert!(!frustum().intersects_aabb([-1.0, -1.0, -120.0], [1.0, 1.0, -110.0]));
+        assert!(frustum().intersects_aabb([-1.0, -1.0, -120.0], [1.0, 1.0, -90.0]));
+    }
+
+    #[test]
+    fn box_enclosing_frustum_is_visible() {
+        assert!(frustum().intersects_aabb([-1000.0; 3], [1000.0; 3]));
+    }
+
+    #[test]
+    fn points() {
+        let f = frustum();
+        assert!(f.contains_point([0.0, 0.0, -1.0]));
+        assert!(!f.contains_point([0.0, 0.0, 1.0]));
+        assert!(!f.contains_point([0.0, 0.0, -0.05]));
+        assert!(!f.contains_point([2.0, 0.0, -1.0]));
+    }
+}
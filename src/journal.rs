@@ -0,0 +1,384 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::chunk::{BlockSnapshot, BlockState, ChunkColumn, ChunkManager, LightLevel};
+use crate::minecraft::nbt::{Compound, List, Nbt};
+
+// Oldest transactions are forgotten past this.
+const MAX_TRANSACTIONS: usize = 1000;
+
+/// A block changed by a transaction.
+#[derive(Clone, Debug)]
+pub struct Change {
+    pub pos: [i32; 3],
+    pub before: BlockSnapshot,
+    pub after: BlockSnapshot,
+}
+
+/// Changes made, undone and redone together.
+#[derive(Clone, Debug)]
+pub struct Transaction {
+    pub name: String,
+    pub changes: Vec<Change>,
+}
+
+/// The history of edits to the world, for undoing and redoing them.
+pub struct Journal {
+    done: Vec<Transaction>,
+    undone: Vec<Transaction>,
+    // Where the journal is kept between runs, if anywhere.
+    path: Option<PathBuf>,
+}
+
+impl Default for Journal {
+    fn default() -> Journal {
+        Journal::new()
+    }
+}
+
+impl Journal {
+    /// Creates a journal only kept in memory.
+    pub fn new() -> Journal {
+        Journal {
+            done: vec![],
+            undone: vec![],
+            path: None,
+        }
+    }
+
+    /// Opens the journal saved at `path`, or starts a new one there.
+    pub fn open(path: &Path) -> io::Result<Journal> {
+        let mut journal = Journal::new();
+        if path.exists() {
+            let nbt = Nbt::from_gzip(&fs::read(path)?)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", e)))?;
+            let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid journal");
+            let transactions = |key| -> io::Result<Vec<Transaction>> {
+                nbt.as_compound()
+                    .and_then(|c| c.get(key))
+                    .and_then(|x| x.as_compound_list())
+                    .ok_or_else(invalid)?
+                    .iter()
+                    .map(|t| transaction_from_nbt(t).ok_or_else(invalid))
+                    .collect()
+            };
+            journal.done = transactions("Done")?;
+            journal.undone = transactions("Undone")?;
+        }
+        journal.path = Some(path.to_path_buf());
+        Ok(journal)
+    }
+
+    /// Runs `f` on the world, recording everything it changes as one transaction.
+    pub fn transaction<F, R>(&mut self, world: &mut ChunkManager, name: &str, f: F) -> R
+    where
+        F: FnOnce(&mut ChunkManager) -> R,
+    {
+        world.start_recording();
+        let result = f(world);
+        let mut changes: Vec<_> = world
+            .stop_recording()
+            .into_iter()
+            .filter_map(|(pos, before)| {
+                let [x, y, z] = pos;
+                let after = world.snapshot(x, y, z)?;
+                if after == before {
+                    return None;
+                }
+                Some(Change { pos, before, after })
+            })
+            .collect();
+        if !changes.is_empty() {
+            changes.sort_by_key(|c| c.pos);
+            self.done.push(Transaction {
+                name: name.to_string(),
+                changes,
+            });
+            if self.done.len() > MAX_TRANSACTIONS {
+                self.done.remove(0);
+            }
+            self.undone.clear();
+            self.save();
+        }
+        result
+    }
+
+    /// Reverts the last transaction, returning its name, or `None` if
+    /// there's nothing to undo. Columns which aren't loaded are loaded
+    /// with `load`, as the changes might have been saved since.
+    pub fn undo<L>(&mut self, world: &mut ChunkManager, load: L) -> Option<&str>
+    where
+        L: FnMut(i32, i32) -> Option<ChunkColumn>,
+    {
+        let transaction = self.done.pop()?;
+        apply(
+            world,
+            load,
+            transaction.changes.iter().map(|c| (c.pos, &c.before)),
+        );
+        self.undone.push(transaction);
+        self.save();
+        self.undone.last().map(|t| &t.name[..])
+    }
+
+    /// Applies the last undone transaction again, like `undo`.
+    pub fn redo<L>(&mut self, world: &mut ChunkManager, load: L) -> Option<&str>
+    where
+        L: FnMut(i32, i32) -> Option<ChunkColumn>,
+    {
+        let transaction = self.undone.pop()?;
+        apply(
+            world,
+            load,
+            transaction.changes.iter().map(|c| (c.pos, &c.after)),
+        );
+        self.done.push(transaction);
+        self.save();
+        self.done.last().map(|t| &t.name[..])
+    }
+
+    // Rewrites the whole journal, as it's small enough.
+    fn save(&self) {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return,
+        };
+        let list = |transactions: &[Transaction]| {
+            Nbt::List(List::Compound(
+                transactions.iter().map(transaction_to_nbt).collect(),
+            ))
+        };
+        let mut root = Compound::new();
+        root.insert("Done".to_string(), list(&self.done));
+        root.insert("Undone".to_string(), list(&self.undone));
+        let result = Nbt::Compound(root).to_gzip("").and_then(|data| {
+            // Keep the old journal intact if writing the new one fails midway.
+            let tmp = path.with_extension("dat.tmp");
+            {
+                let mut file = File::create(&tmp)?;
+                file.write_all(&data)?;
+                file.sync_all()?;
+            }
+            fs::rename(&tmp, path)
+        });
+        if let Err(e) = result {
+            println!(
+                "Warning: couldn't write the journal to {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+fn apply<'a, L, I>(world: &mut ChunkManager, mut load: L, snapshots: I)
+where
+    L: FnMut(i32, i32) -> Option<ChunkColumn>,
+    I: Iterator<Item = ([i32; 3], &'a BlockSnapshot)>,
+{
+    for ([x, y, z], snapshot) in snapshots {
+        let (cx, cz) = (x >> 4, z >> 4);
+        if !world.has_chunk_column(cx, cz) {
+            if let Some(column) = load(cx, cz) {
+                world.add_chunk_column(cx, cz, column);
+            }
+        }
+        if !world.restore(x, y, z, snapshot) {
+            println!("Warning: couldn't restore block at {} {} {}", x, y, z);
+        }
+    }
+}
+
+fn snapshot_to_nbt(snapshot: &BlockSnapshot) -> Nbt {
+    let mut c = Compound::new();
+    c.insert("Block".to_string(), Nbt::Short(snapshot.block.value as i16));
+    c.insert("Light".to_string(), Nbt::Byte(snapshot.light.value as i8));
+    if let Some(ref tile_entity) = snapshot.tile_entity {
+        c.insert("TileEntity".to_string(), Nbt::Compound(tile_entity.clone()));
+    }
+    Nbt::Compound(c)
+}
+
+fn snapshot_from_nbt(nbt: &Nbt) -> Option<BlockSnapshot> {
+    let c = nbt.as_compound()?;
+    Some(BlockSnapshot {
+        block: BlockState {
+            value: c.get("Block")?.as_short()? as u16,
+        },
+        light: LightLevel {
+            value: c.get("Light")?.as_byte()? as u8,
+        },
+        tile_entity: c.get("TileEntity").and_then(|x| x.as_compound()).cloned(),
+    })
+}
+
+fn transaction_to_nbt(transaction: &Transaction) -> Compound {
+    let changes = transaction
+        .changes
+        .iter()
+        .map(|change| {
+            let mut c = Compound::new();
+            c.insert("Pos".to_string(), Nbt::IntArray(change.pos.to_vec()));
+            c.insert("Before".to_string(), snapshot_to_nbt(&change.before));
+            c.insert("After".to_string(), snapshot_to_nbt(&change.after));
+            c
+        })
+        .collect();
+    let mut c = Compound::new();
+    c.insert("Name".to_string(), Nbt::String(transaction.name.clone()));
+    c.insert("Changes".to_string(), Nbt::List(List::Compound(changes)));
+    c
+}
+
+fn transaction_from_nbt(c: &Compound) -> Option<Transaction> {
+    let changes = c
+        .get("Changes")?
+        .as_compound_list()?
+        .iter()
+        .map(|change| {
+            let pos = match *change.get("Pos")?.as_intarray()? {
+                [x, y, z] => [x, y, z],
+                _ => return None,
+            };
+            Some(Change {
+                pos,
+                before: snapshot_from_nbt(change.get("Before")?)?,
+                after: snapshot_from_nbt(change.get("After")?)?,
+            })
+        })
+        .collect::<Option<_>>()?;
+    Some(Transaction {
+        name: c.get("Name")?.as_string()?.to_string(),
+        changes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    const STONE: BlockState = BlockState { value: 1 << 4 };
+    const CHEST: BlockState = BlockState { value: 54 << 4 | 2 };
+
+    fn world() -> ChunkManager {
+        let mut world = ChunkManager::new();
+        world.add_chunk_column(0, 0, ChunkColumn::empty());
+        world.set_block(1, 2, 3, STONE);
+        world
+    }
+
+    fn chest() -> Compound {
+        let mut chest = Compound::new();
+        chest.insert("id".to_string(), Nbt::String("Chest".to_string()));
+        chest
+    }
+
+    // Replaces the stone with a chest.
+    fn place_chest(journal: &mut Journal, world: &mut ChunkManager) {
+        journal.transaction(world, "place chest", |world| {
+            world.set_block(1, 2, 3, CHEST);
+            world.set_tile_entity(1, 2, 3, Some(chest()));
+            world.set_light(1, 2, 3, LightLevel { value: 0x30 });
+        });
+    }
+
+    fn no_load(_: i32, _: i32) -> Option<ChunkColumn> {
+        panic!("nothing to load")
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut world = world();
+        let before = world.snapshot(1, 2, 3).unwrap();
+        let mut journal = Journal::new();
+        assert_eq!(journal.undo(&mut world, no_load), None);
+
+        place_chest(&mut journal, &mut world);
+        let after = world.snapshot(1, 2, 3).unwrap();
+        assert_eq!(after.block, CHEST);
+        assert!(after.tile_entity.is_some());
+
+        assert_eq!(journal.undo(&mut world, no_load), Some("place chest"));
+        assert_eq!(world.snapshot(1, 2, 3), Some(before));
+        assert_eq!(journal.undo(&mut world, no_load), None);
+
+        assert_eq!(journal.redo(&mut world, no_load), Some("place chest"));
+        assert_eq!(world.snapshot(1, 2, 3), Some(after));
+        assert_eq!(journal.redo(&mut world, no_load), None);
+    }
+
+    #[test]
+    fn new_transactions_clear_redo() {
+        let mut world = world();
+        let mut journal = Journal::new();
+        place_chest(&mut journal, &mut world);
+        journal.undo(&mut world, no_load);
+        journal.transaction(&mut world, "break stone", |world| {
+            world.set_block(1, 2, 3, BlockState { value: 0 })
+        });
+        assert_eq!(journal.redo(&mut world, no_load), None);
+
+        // Nothing changed, nothing recorded.
+        journal.transaction(&mut world, "nothing", |_| {});
+        assert_eq!(journal.undo(&mut world, no_load), Some("break stone"));
+    }
+
+    #[test]
+    fn undo_loads_columns() {
+        let mut world = world();
+        let mut journal = Journal::new();
+        place_chest(&mut journal, &mut world);
+        world.remove_chunk_column(0, 0);
+
+        // As if saved with the chest.
+        let mut loaded = vec![];
+        journal.undo(&mut world, |x, z| {
+            loaded.push((x, z));
+            let mut column = ChunkColumn::empty();
+            column.tile_entities.insert([1, 2, 3], chest());
+            Some(column)
+        });
+        assert_eq!(loaded, vec![(0, 0)]);
+        assert_eq!(world.get_block(1, 2, 3), Some(STONE));
+        assert_eq!(world.tile_entity(1, 2, 3), None);
+    }
+
+    #[test]
+    fn save_and_open() {
+        let path = env::temp_dir().join(format!("hematite_journal_{}.dat", process::id()));
+        let _ = fs::remove_file(&path);
+        let mut world = world();
+        let before = world.snapshot(1, 2, 3).unwrap();
+        {
+            let mut journal = Journal::open(&path).unwrap();
+            place_chest(&mut journal, &mut world);
+            journal.transaction(&mut world, "place stone", |world| {
+                world.set_block(4, 5, 6, STONE)
+            });
+            journal.undo(&mut world, no_load);
+        }
+        assert!(path.exists());
+        assert!(!path.with_extension("dat.tmp").exists());
+
+        let mut journal = Journal::open(&path).unwrap();
+        assert_eq!(journal.done.len(), 1);
+        assert_eq!(journal.undone.len(), 1);
+        let change = &journal.done[0].changes[0];
+        assert_eq!(change.pos, [1, 2, 3]);
+        assert_eq!(change.before, before);
+        assert_eq!(
+            change.after.tile_entity.as_ref().unwrap().get("x"),
+            Some(&Nbt::Int(1))
+        );
+
+        assert_eq!(journal.redo(&mut world, no_load), Some("place stone"));
+        assert_eq!(world.get_block(4, 5, 6), Some(STONE));
+        assert_eq!(journal.undo(&mut world, no_load), Some("place stone"));
+        assert_eq!(journal.undo(&mut world, no_load), Some("place chest"));
+        assert_eq!(world.snapshot(1, 2, 3), Some(before));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,560 @@
+use std::cmp::max;
+use std::collections::VecDeque;
+
+use crate::chunk::{BlockState, ChunkManager, LightLevel, SIZE};
+
+/// How blocks interact with light.
+pub trait LightProperties {
+    /// Light level emitted by a block, from 0 to 15.
+    fn light_emission(&self, block: BlockState) -> u8;
+
+    /// Light levels absorbed by a block, from 0 to 15.
+    fn light_opacity(&self, block: BlockState) -> u8;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Channel {
+    Block,
+    Sky,
+}
+
+const CHANNELS: [Channel; 2] = [Channel::Block, Channel::Sky];
+
+impl Channel {
+    fn get(self, light: LightLevel) -> u8 {
+        match self {
+            Channel::Block => light.block_light(),
+            Channel::Sky => light.sky_light(),
+        }
+    }
+
+    fn with(self, light: LightLevel, level: u8) -> LightLevel {
+        let value = match self {
+            Channel::Block => (light.value & 0xf0) | level,
+            Channel::Sky => (light.value & 0x0f) | (level << 4),
+        };
+        LightLevel { value }
+    }
+}
+
+const DOWN: [i32; 3] = [0, -1, 0];
+const DIRECTIONS: [[i32; 3]; 6] = [
+    DOWN,
+    [0, 1, 0],
+    [0, 0, -1],
+    [0, 0, 1],
+    [-1, 0, 0],
+    [1, 0, 0],
+];
+const HEIGHT: i32 = (SIZE * SIZE) as i32;
+
+fn step([x, y, z]: [i32; 3], [dx, dy, dz]: [i32; 3]) -> [i32; 3] {
+    [x + dx, y + dy, z + dz]
+}
+
+/// Light reaching a block with the given opacity,
+/// from a neighbor towards `dir`.
+fn attenuate(channel: Channel, level: u8, opacity: u8, dir: [i32; 3]) -> u8 {
+    // Direct sunlight goes down through transparent blocks unchanged.
+    if channel == Channel::Sky && level == 15 && opacity == 0 && dir == DOWN {
+        15
+    } else {
+        level.saturating_sub(max(1, opacity))
+    }
+}
+
+fn get(world: &ChunkManager, channel: Channel, [x, y, z]: [i32; 3]) -> Option<u8> {
+    world.get_light(x, y, z).map(|light| channel.get(light))
+}
+
+// Changes the light of a block, which only needs saving after an `edit`.
+fn set(world: &mut ChunkManager, channel: Channel, [x, y, z]: [i32; 3], level: u8, edit: bool) {
+    if let Some(light) = world.get_light(x, y, z) {
+        let light = channel.with(light, level);
+        if edit {
+            world.set_light(x, y, z, light);
+        } else {
+            world.set_computed_light(x, y, z, light);
+        }
+    }
+}
+
+// Light a block has regardless of its neighbors.
+fn intrinsic<P: LightProperties>(
+    props: &P,
+    channel: Channel,
+    [_, y, _]: [i32; 3],
+    block: BlockState,
+) -> u8 {
+    match channel {
+        Channel::Block => props.light_emission(block),
+        Channel::Sky if y == HEIGHT - 1 => attenuate(channel, 15, props.light_opacity(block), DOWN),
+        Channel::Sky => 0,
+    }
+}
+
+/// Spreads the light of the queued blocks to their neighbors,
+/// wherever it's brighter than what they already have.
+fn propagate<P: LightProperties>(
+    world: &mut ChunkManager,
+    props: &P,
+    channel: Channel,
+    queue: &mut VecDeque<[i32; 3]>,
+    edit: bool,
+) {
+    while let Some(p) = queue.pop_front() {
+        let level = match get(world, channel, p) {
+            Some(level) if level > 1 => level,
+            _ => continue,
+        };
+        for &dir in DIRECTIONS.iter() {
+            let n = step(p, dir);
+            let block = match world.get_block(n[0], n[1], n[2]) {
+                Some(block) => block,
+                None => continue,
+            };
+            let level = attenuate(channel, level, props.light_opacity(block), dir);
+            if level > get(world, channel, n).unwrap() {
+                set(world, channel, n, level, edit);
+                queue.push_back(n);
+            }
+        }
+    }
+}
+
+/// Darkens the blocks which were lit through `p`, which had `level`,
+/// queueing the blocks light has to spread back from.
+fn remove<P: LightProperties>(
+    world: &mut ChunkManager,
+    props: &P,
+    channel: Channel,
+    p: [i32; 3],
+    level: u8,
+    relight: &mut VecDeque<[i32; 3]>,
+) {
+    let mut queue = VecDeque::new();
+    queue.push_back((p, level));
+    while let Some((p, level)) = queue.pop_front() {
+        for &dir in DIRECTIONS.iter() {
+            let n = step(p, dir);
+            let current = match get(world, channel, n) {
+                Some(current) => current,
+                None => continue,
+            };
+            let sunlight = channel == Channel::Sky && dir == DOWN && level == 15;
+            if current != 0 && (current < level || (sunlight && current == 15)) {
+                let block = world.get_block(n[0], n[1], n[2]).unwrap();
+                let own = intrinsic(props, channel, n, block);
+                set(world, channel, n, own, true);
+                if own > 0 {
+                    relight.push_back(n);
+                }
+                queue.push_back((n, current));
+            } else if current >= level {
+                relight.push_back(n);
+            }
+        }
+    }
+}
+
+/// Changes a block and updates the light around it,
+/// returning the previous block if it was loaded.
+pub fn set_block<P: LightProperties>(
+    world: &mut ChunkManager,
+    props: &P,
+    x: i32,
+    y: i32,
+    z: i32,
+    block: BlockState,
+) -> Option<BlockState> {
+    let old = world.set_block(x, y, z, block)?;
+    update_light(world, props, x, y, z);
+    Some(old)
+}
+
+/// Updates the light around a block, after it was changed
+/// with `ChunkManager::set_block`.
+pub fn update_light<P: LightProperties>(
+    world: &mut ChunkManager,
+    props: &P,
+    x: i32,
+    y: i32,
+    z: i32,
+) {
+    let p = [x, y, z];
+    let block = match world.get_block(x, y, z) {
+        Some(block) => block,
+        None => return,
+    };
+    let opacity = props.light_opacity(block);
+    for &channel in CHANNELS.iter() {
+        let old = get(world, channel, p).unwrap();
+        let own = intrinsic(props, channel, p, block);
+        let new = DIRECTIONS.iter().fold(own, |new, &dir| {
+            // Light coming from the neighbor travels opposite to `dir`.
+            let level = get(world, channel, step(p, dir)).unwrap_or(0);
+            max(new, attenuate(channel, level, opacity, dir.map(|d| -d)))
+        });
+
+        let mut queue = VecDeque::new();
+        if new < old {
+            set(world, channel, p, own, true);
+            if own > 0 {
+                queue.push_back(p);
+            }
+            remove(world, props, channel, p, old, &mut queue);
+        } else if new > old {
+            set(world, channel, p, new, true);
+            queue.push_back(p);
+        }
+        propagate(world, props, channel, &mut queue, true);
+    }
+}
+
+/// Computes the light of a whole column from scratch, e.g. when
+/// it was saved without it, also spreading it into loaded neighbors.
+pub fn relight_column<P: LightProperties>(world: &mut ChunkManager, props: &P, cx: i32, cz: i32) {
+    if !world.has_chunk_column(cx, cz) {
+        return;
+    }
+    let (x0, z0) = (cx * SIZE as i32, cz * SIZE as i32);
+    let mut queues = [VecDeque::new(), VecDeque::new()];
+    for z in z0..z0 + SIZE as i32 {
+        for x in x0..x0 + SIZE as i32 {
+            let mut sky = 15;
+            for y in (0..HEIGHT).rev() {
+                let block = world.get_block(x, y, z).unwrap();
+                sky = attenuate(Channel::Sky, sky, props.light_opacity(block), DOWN);
+                let emission = props.light_emission(block);
+                world.set_computed_light(
+                    x,
+                    y,
+                    z,
+                    LightLevel {
+                        value: (sky << 4) | emission,
+                    },
+                );
+                if emission > 1 {
+                    queues[0].push_back([x, y, z]);
+                }
+                if sky > 1 {
+                    queues[1].push_back([x, y, z]);
+                }
+            }
+        }
+    }
+
+    // Let the light of loaded neighbors flow in.
+    for i in 0..SIZE as i32 {
+        let border = [
+            [x0 - 1, z0 + i],
+            [x0 + SIZE as i32, z0 + i],
+            [x0 + i, z0 - 1],
+            [x0 + i, z0 + SIZE as i32],
+        ];
+        for &[x, z] in border.iter() {
+            if !world.has_chunk_column(x >> 4, z >> 4) {
+                continue;
+            }
+            for y in 0..HEIGHT {
+                for queue in queues.iter_mut() {
+                    queue.push_back([x, y, z]);
+                }
+            }
+        }
+    }
+
+    for (&channel, queue) in CHANNELS.iter().zip(queues.iter_mut()) {
+        propagate(world, props, channel, queue, false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::chunk::{Chunk, ChunkColumn, EMPTY_BLOCK};
+    use crate::minecraft::nbt::{Compound, List, Nbt};
+    use crate::minecraft::region::column_from_level;
+
+    const STONE: BlockState = BlockState { value: 1 << 4 };
+    const TORCH: BlockState = BlockState { value: 50 << 4 | 5 };
+
+    struct Props;
+
+    impl LightProperties for Props {
+        fn light_emission(&self, block: BlockState) -> u8 {
+            if block == TORCH {
+                14
+            } else {
+                0
+            }
+        }
+
+        fn light_opacity(&self, block: BlockState) -> u8 {
+            if block == STONE {
+                15
+            } else {
+                0
+            }
+        }
+    }
+
+    // A lit column at 0, 0 with the given blocks in its lowest 32 layers.
+    fn world<F: Fn(i32, i32, i32) -> BlockState>(f: F) -> ChunkManager {
+        let mut world = ChunkManager::new();
+        let chunks = (0..2)
+            .map(|cy| {
+                Arc::new(Chunk::from_fn(|x, y, z| {
+                    let block = f(x as i32, (cy * SIZE + y) as i32, z as i32);
+                    (block, LightLevel { value: 0 })
+                }))
+            })
+            .collect();
+        world.add_chunk_column(
+            0,
+            0,
+            ChunkColumn {
+                chunks,
+                light_populated: false,
+                ..ChunkColumn::empty()
+            },
+        );
+        relight_column(&mut world, &Props, 0, 0);
+        world
+    }
+
+    fn block_light(world: &ChunkManager, x: i32, y: i32, z: i32) -> u8 {
+        world.get_light(x, y, z).unwrap().block_light()
+    }
+
+    fn sky_light(world: &ChunkManager, x: i32, y: i32, z: i32) -> u8 {
+        world.get_light(x, y, z).unwrap().sky_light()
+    }
+
+    // Light levels of the lowest 32 layers.
+    fn levels(world: &ChunkManager) -> Vec<LightLevel> {
+        let mut levels = vec![];
+        for y in 0..32 {
+            for z in 0..SIZE as i32 {
+                for x in 0..SIZE as i32 {
+                    levels.push(world.get_light(x, y, z).unwrap());
+                }
+            }
+        }
+        levels
+    }
+
+    #[test]
+    fn torch_falloff() {
+        let world = world(|x, y, z| {
+            if [x, y, z] == [8, 10, 8] {
+                TORCH
+            } else {
+                EMPTY_BLOCK
+            }
+        });
+        for d in 0..8 {
+            assert_eq!(block_light(&world, 8 + d, 10, 8), 14 - d as u8);
+            assert_eq!(block_light(&world, 8, 10 - d, 8), 14 - d as u8);
+        }
+        // Manhattan distance, like vanilla.
+        assert_eq!(block_light(&world, 11, 12, 5), 14 - 8);
+        assert_eq!(block_light(&world, 0, 0, 0), 0);
+        assert_eq!(sky_light(&world, 8, 10, 8), 15);
+    }
+
+    #[test]
+    fn torch_walled_off() {
+        // The torch is in a stone box with one opening to the east.
+        let world = world(|x, y, z| match [x, y, z] {
+            [8, 10, 8] => TORCH,
+            [9, 10, 8] => EMPTY_BLOCK,
+            [7..=9, 9..=11, 7..=9] => STONE,
+            _ => EMPTY_BLOCK,
+        });
+        assert_eq!(block_light(&world, 9, 10, 8), 13);
+        assert_eq!(block_light(&world, 10, 10, 8), 12);
+        assert_eq!(block_light(&world, 9, 9, 8), 0);
+        // Around the box, 8 blocks from the opening.
+        assert_eq!(block_light(&world, 6, 10, 8), 12 - 8);
+    }
+
+    #[test]
+    fn sky_under_overhang() {
+        let world = world(|x, y, _| if y == 20 && x < 8 { STONE } else { EMPTY_BLOCK });
+        assert_eq!(sky_light(&world, 8, 10, 8), 15);
+        assert_eq!(sky_light(&world, 7, 10, 8), 14);
+        assert_eq!(sky_light(&world, 2, 10, 8), 9);
+        assert_eq!(sky_light(&world, 2, 19, 8), 9);
+        assert_eq!(sky_light(&world, 2, 20, 8), 0);
+        assert_eq!(sky_light(&world, 2, 21, 8), 15);
+    }
+
+    // A column written by hand in the format 1.8 saves sections in, with
+    // a torch in a hollow stone box, and the light the game gives it.
+    fn saved_level() -> Compound {
+        let (mut blocks, mut data) = (vec![0; 4096], vec![0; 2048]);
+        let (mut block_light, mut sky_light) = (vec![0; 2048], vec![0; 2048]);
+        let set_nibble = |array: &mut Vec<u8>, i: usize, v: u8| {
+            array[i >> 1] |= v << ((i & 1) * 4);
+        };
+        for y in 0..SIZE {
+            for z in 0..SIZE {
+                for x in 0..SIZE {
+                    let i = (y * SIZE + z) * SIZE + x;
+                    let inside = |v: usize| v > 4 && v < 10;
+                    let d = |a: usize, b: usize| (a as i32 - b as i32).unsigned_abs() as u8;
+                    if [x, y, z] == [7, 3, 7] {
+                        blocks[i] = 50;
+                        set_nibble(&mut data, i, 5);
+                        set_nibble(&mut block_light, i, 14);
+                    } else if inside(x) && y > 0 && y < 6 && inside(z) {
+                        set_nibble(&mut block_light, i, 14 - d(x, 7) - d(y, 3) - d(z, 7));
+                    } else if x > 3 && x < 11 && y < 7 && z > 3 && z < 11 {
+                        blocks[i] = 1;
+                    } else {
+                        set_nibble(&mut sky_light, i, 15);
+                    }
+                }
+            }
+        }
+        let mut section = Compound::new();
+        section.insert("Y".to_string(), Nbt::Byte(0));
+        section.insert("Blocks".to_string(), Nbt::ByteArray(blocks));
+        section.insert("Data".to_string(), Nbt::ByteArray(data));
+        section.insert("BlockLight".to_string(), Nbt::ByteArray(block_light));
+        section.insert("SkyLight".to_string(), Nbt::ByteArray(sky_light));
+        let mut level = Compound::new();
+        level.insert(
+            "Sections".to_string(),
+            Nbt::List(List::Compound(vec![section])),
+        );
+        level.insert("Biomes".to_string(), Nbt::ByteArray(vec![1; 256]));
+        level
+    }
+
+    #[test]
+    fn relight_saved_column() {
+        let column = column_from_level(&saved_level());
+        let saved = column.chunks[0].clone();
+        let mut world = ChunkManager::new();
+        world.add_chunk_column(0, 0, column);
+        relight_column(&mut world, &Props, 0, 0);
+        for y in 0..SIZE {
+            for z in 0..SIZE {
+                for x in 0..SIZE {
+                    let light = world.get_light(x as i32, y as i32, z as i32);
+                    assert_eq!(
+                        light,
+                        Some(saved.light_level(x, y, z)),
+                        "at {} {} {}",
+                        x,
+                        y,
+                        z
+                    );
+                }
+            }
+        }
+        assert_eq!(block_light(&world, 9, 5, 9), 14 - 6);
+        assert_eq!(sky_light(&world, 11, 6, 11), 15);
+    }
+
+    #[test]
+    fn place_torch() {
+        let expected = world(|x, y, z| {
+            if [x, y, z] == [8, 10, 8] {
+                TORCH
+            } else {
+                EMPTY_BLOCK
+            }
+        });
+        let mut world = world(|_, _, _| EMPTY_BLOCK);
+        set_block(&mut world, &Props, 8, 10, 8, TORCH);
+        assert!(levels(&world) == levels(&expected));
+    }
+
+    #[test]
+    fn break_torch() {
+        let mut world = world(|x, y, z| {
+            if [x, y, z] == [8, 10, 8] {
+                TORCH
+            } else {
+                EMPTY_BLOCK
+            }
+        });
+        set_block(&mut world, &Props, 8, 10, 8, EMPTY_BLOCK);
+        let expected = self::world(|_, _, _| EMPTY_BLOCK);
+        assert!(levels(&world) == levels(&expected));
+        assert_eq!(block_light(&world, 9, 10, 8), 0);
+    }
+
+    #[test]
+    fn break_one_of_two_torches() {
+        let torches = |x, y, z| match [x, y, z] {
+            [4, 10, 8] | [12, 10, 8] => TORCH,
+            _ => EMPTY_BLOCK,
+        };
+        let mut world = world(torches);
+        set_block(&mut world, &Props, 4, 10, 8, EMPTY_BLOCK);
+        let expected = self::world(|x, y, z| {
+            if [x, y, z] == [12, 10, 8] {
+                TORCH
+            } else {
+                EMPTY_BLOCK
+            }
+        });
+        assert!(levels(&world) == levels(&expected));
+        assert_eq!(block_light(&world, 4, 10, 8), 14 - 8);
+    }
+
+    #[test]
+    fn light_changes_are_saved() {
+        let mut world = world(|_, _, _| EMPTY_BLOCK);
+        assert!(world.modified_columns().is_empty());
+        // Lighting a column doesn't need saving, even into loaded neighbors.
+        world.add_chunk_column(
+            1,
+            0,
+            ChunkColumn {
+                light_populated: false,
+                ..ChunkColumn::empty()
+            },
+        );
+        relight_column(&mut world, &Props, 1, 0);
+        assert!(world.modified_columns().is_empty());
+
+        // Light spilling over from an edit does.
+        set_block(&mut world, &Props, 15, 10, 8, TORCH);
+        let mut modified = world.modified_columns();
+        modified.sort();
+        assert_eq!(modified, vec![(0, 0), (1, 0)]);
+        assert_eq!(block_light(&world, 16, 10, 8), 13);
+    }
+
+    #[test]
+    fn cover_and_uncover_sky() {
+        let overhang = |x, y, _| {
+            if y == 20 && x < 8 {
+                STONE
+            } else {
+                EMPTY_BLOCK
+            }
+        };
+        let mut world = world(|_, _, _| EMPTY_BLOCK);
+        for x in 0..8 {
+            for z in 0..SIZE as i32 {
+                set_block(&mut world, &Props, x, 20, z, STONE);
+            }
+        }
+        assert!(levels(&world) == levels(&self::world(overhang)));
+
+        for x in 0..8 {
+            for z in 0..SIZE as i32 {
+                set_block(&mut world, &Props, x, 20, z, EMPTY_BLOCK);
+            }
+        }
+        assert!(levels(&world) == levels(&self::world(|_, _, _| EMPTY_BLOCK)));
+    }
+}
@@ -8,15 +8,16 @@ extern crate gfx;
 pub use gfx_voxel::{array, cube};
 
 use std::cmp::max;
+use std::collections::HashMap;
 use std::f32::consts::PI;
-use std::fs::File;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::Instant;
 
 use crate::array::*;
 use crate::shader::Renderer;
 use docopt::Docopt;
-use flate2::read::GzDecoder;
 use gfx::traits::Device;
 use glutin_window::*;
 use piston::event_loop::{EventLoop, EventSettings, Events};
@@ -25,11 +26,28 @@ use piston::window::{AdvancedWindow, OpenGLWindow, Size, Window, WindowSettings}
 use vecmath::{vec3_add, vec3_normalized, vec3_scale};
 
 pub mod chunk;
+pub mod frustum;
+pub mod journal;
+pub mod lighting;
 pub mod minecraft;
+pub mod physics;
+pub mod raycast;
 pub mod shader;
+pub mod streaming;
+pub mod visibility;
+pub mod workers;
 
+use crate::chunk::ChunkNeighborhood;
+use crate::journal::Journal;
 use crate::minecraft::biome::Biomes;
 use crate::minecraft::block_state::BlockStates;
+use crate::minecraft::level::LevelData;
+use crate::minecraft::player::{self, PlayerData};
+use crate::minecraft::region::RegionStore;
+use crate::minecraft::registry::Registry;
+use crate::streaming::ChunkStreamer;
+use crate::visibility::VisibilityGraph;
+use crate::workers::WorkerPool;
 
 static USAGE: &str = "
 hematite, Minecraft made in Rust!
@@ -40,13 +58,26 @@ Usage:
 Options:
     -p, --path               Fully qualified path for world folder.
     --mcversion=<version>    Minecraft version [default: 1.8.8].
+    --dimension=<dim>        Dimension to load: overworld, nether, end or
+                             <namespace>:<name> (defaults to the player's).
+    --player=<player>        UUID or name of the player to start at, for
+                             worlds saved by a server.
+    --render-distance=<n>    Radius of loaded chunks [default: 8].
+    --journal                Keep the undo history next to the world.
 ";
 
+// How far away blocks can be targeted, like in creative mode.
+const REACH: f32 = 5.0;
+
 #[derive(RustcDecodable)]
 struct Args {
     arg_world: String,
     flag_path: bool,
     flag_mcversion: String,
+    flag_dimension: Option<String>,
+    flag_player: Option<String>,
+    flag_render_distance: i32,
+    flag_journal: bool,
 }
 
 fn create_main_targets(
@@ -86,25 +117,52 @@ fn main() {
         mc_path
     };
 
-    let file_name = world.join("level.dat");
-    let level_reader = GzDecoder::new(File::open(file_name).unwrap());
-    let level = minecraft::nbt::Nbt::from_reader(level_reader).unwrap();
-    println!("{:?}", level);
-    let player_pos: [f32; 3] = Array::from_iter(
-        level["Data"]["Player"]["Pos"]
-            .as_double_list()
-            .unwrap()
-            .iter()
-            .map(|&x| x as f32),
+    let level = LevelData::open(&world).unwrap_or_else(|e| panic!("{}", e));
+    println!(
+        "Opened level '{}' ({})",
+        level.level_name,
+        level.version.as_ref().map_or("pre-1.9", |v| &v.name[..])
     );
-    let player_chunk = [player_pos.x(), player_pos.z()].map(|x| (x / 16.0).floor() as i32);
-    let player_rot = level["Data"]["Player"]["Rotation"].as_float_list().unwrap();
-    let player_yaw = player_rot[0];
-    let player_pitch = player_rot[1];
 
-    let regions = player_chunk.map(|x| x >> 5);
-    let region_file = world.join(format!("region/r.{}.{}.mca", regions[0], regions[1]));
-    let region = minecraft::region::Region::open(&region_file).unwrap();
+    let players = player::load_players(&world);
+    for player in players.iter() {
+        println!("Found player {}", player);
+    }
+    let player = match args.flag_player {
+        Some(ref query) => players.into_iter().find(|p| p.matches(query)).or_else(|| {
+            println!("Warning: no player data found for {}", query);
+            None
+        }),
+        None => level.player.as_ref().and_then(PlayerData::from_compound),
+    }
+    .unwrap_or_else(|| {
+        println!("Starting at the world spawn");
+        PlayerData::at_spawn(&level)
+    });
+
+    let player_pos = player.pos.map(|x| x as f32);
+    let player_yaw = player.rotation[0];
+    let player_pitch = player.rotation[1];
+
+    let dimension = match args.flag_dimension {
+        Some(ref dimension) => dimension
+            .parse()
+            .unwrap_or_else(|e: String| panic!("{}", e)),
+        None => player.dimension.clone(),
+    };
+    println!("Loading dimension {}", dimension);
+
+    let region_path = dimension.region_path(&world);
+    let mut journal = if args.flag_journal {
+        let path = region_path.with_file_name("hematite_journal.dat");
+        Journal::open(&path).unwrap_or_else(|e| {
+            println!("Warning: couldn't open {}: {}", path.display(), e);
+            Journal::new()
+        })
+    } else {
+        Journal::new()
+    };
+    let region_store = Arc::new(Mutex::new(RegionStore::new(region_path)));
 
     let loading_title = format!(
         "Hematite loading... - {}",
@@ -132,11 +190,16 @@ fn main() {
 
     let assets = Path::new("./assets");
 
+    // Load block state ids and biomes of the version, from
+    // ./registries/<version> if there, or bundled ones.
+    let registry = Registry::load(&args.flag_mcversion, Path::new("./registries"))
+        .unwrap_or_else(|e| panic!("{}", e));
+
     // Load biomes.
-    let biomes = Biomes::load(assets);
+    let biomes = Arc::new(Biomes::load(assets, &registry));
 
     // Load block state definitions and models.
-    let block_states = BlockStates::load(assets, &mut factory);
+    let block_states = Arc::new(BlockStates::load(assets, &registry, &mut factory));
 
     let encoder = factory.create_command_buffer().into();
     let mut renderer = Renderer::new(
@@ -146,20 +209,43 @@ fn main() {
         depth_view,
         block_states.texture.surface.clone(),
     );
+    renderer.set_clear_color(dimension.sky_color());
 
     let mut chunk_manager = chunk::ChunkManager::new();
-
-    println!("Started loading chunks...");
-    let c_bases = player_chunk.map(|x| max(0, (x & 0x1f) - 8) as u8);
-    for cz in c_bases[1]..c_bases[1] + 16 {
-        for cx in c_bases[0]..c_bases[0] + 16 {
-            if let Some(column) = region.get_chunk_column(cx, cz) {
-                let (cx, cz) = (cx as i32 + regions[0] * 32, cz as i32 + regions[1] * 32);
-                chunk_manager.add_chunk_column(cx, cz, column)
-            }
-        }
-    }
-    println!("Finished loading chunks.");
+    let mut chunk_buffers = shader::ChunkBuffers::new();
+
+    let num_threads = thread::available_parallelism().map_or(2, |n| n.get());
+    let render_distance = args.flag_render_distance;
+    let mut streamer = ChunkStreamer::new(render_distance, render_distance + 2);
+    let mut region_loader = streaming::region_loader(region_store.clone(), 2);
+
+    // Vertex buffers are filled in the background, and only uploaded
+    // if the section wasn't modified again in the meantime.
+    let mesher = {
+        let block_states = block_states.clone();
+        let biomes = biomes.clone();
+        WorkerPool::new(
+            "chunk mesher",
+            max(1, num_threads - 1),
+            move |(neighborhood, generation): (ChunkNeighborhood, u64)| {
+                let mut buffers = [vec![], vec![], vec![]];
+                let graph = minecraft::block_state::fill_buffer(
+                    &block_states,
+                    &biomes,
+                    &mut buffers,
+                    neighborhood.coords,
+                    neighborhood.chunks(),
+                    neighborhood.biomes(),
+                );
+                (neighborhood.coords, generation, buffers, graph)
+            },
+        )
+    };
+    let mut mesh_generations = HashMap::new();
+    // Which faces of each meshed section see each other.
+    let mut visibility = HashMap::new();
+    let mut next_generation = 0_u64;
+    let mut camera_chunk = None;
 
     let projection_mat = camera_controllers::CameraPerspective {
         fov: 70.0,
@@ -187,24 +273,29 @@ fn main() {
 
     let mut fps_counter = fps_counter::FPSCounter::new();
 
-    let mut pending_chunks = vec![];
-    chunk_manager.each_chunk_and_neighbors(|coords, buffer, chunks, column_biomes| {
-        pending_chunks.push((coords, buffer, chunks, column_biomes));
-    });
-
     let mut capture_cursor = false;
     println!("Press C to capture mouse");
 
-    let mut staging_buffer = vec![];
+    // Walking is off until toggled, as the area around
+    // the player might take a while to load.
+    let mut walking: Option<physics::Player> = None;
+    println!("Press F to toggle walking");
+
+    // The block the camera is looking at, and the one placed by clicking.
+    let mut target = None;
+    let mut held_block = chunk::BlockState { value: 0x0010 };
+    println!("Click to break (left), place (right) or pick (middle) blocks, press P to save");
+    println!("Press Z to undo and Y to redo changes");
+
     let mut events = Events::new(EventSettings::new().ups(120).max_fps(10_000));
     while let Some(e) = events.next(&mut window) {
-        use piston::input::Button::Keyboard;
-        use piston::input::Key;
+        use piston::input::Button::{Keyboard, Mouse};
+        use piston::input::{Key, MouseButton};
 
         if e.render_args().is_some() {
             // Apply the same y/z camera offset vanilla minecraft has.
             let mut camera = first_person.camera(0.0);
-            camera.position[1] += 1.62;
+            camera.position[1] += physics::EYE_HEIGHT;
             let mut xz_forward = camera.forward;
             xz_forward[1] = 0.0;
             xz_forward = vec3_normalized(xz_forward);
@@ -213,57 +304,73 @@ fn main() {
             let view_mat = camera.orthogonal();
             renderer.set_view(view_mat);
             renderer.clear();
+            let frustum = frustum::Frustum::new(projection_mat, view_mat);
+            let in_view = |[cx, cy, cz]: [i32; 3]| {
+                let min = [cx, cy, cz].map(|x| x as f32 * 16.0);
+                frustum.intersects_aabb(min, min.map(|x| x + 16.0))
+            };
+            // Sections which aren't meshed yet are assumed to see through.
+            let visible = visibility::visible_sections(
+                camera.position.map(|x| (x.floor() as i32) >> 4),
+                |[cx, cy, cz]: [i32; 3]| {
+                    if !chunk_manager.has_chunk_column(cx, cz) {
+                        return None;
+                    }
+                    Some(
+                        *visibility
+                            .get(&[cx, cy, cz])
+                            .unwrap_or(&VisibilityGraph::ALL),
+                    )
+                },
+                in_view,
+            );
             let mut num_chunks: usize = 0;
-            let mut num_sorted_chunks: usize = 0;
             let mut num_total_chunks: usize = 0;
             let start_time = Instant::now();
-            chunk_manager.each_chunk(|cx, cy, cz, _, buffer| {
-                if let Some(buffer) = buffer.borrow_mut().as_mut() {
-                    num_total_chunks += 1;
-
-                    let inf = f32::INFINITY;
-                    let mut bb_min = [inf, inf, inf];
-                    let mut bb_max = [-inf, -inf, -inf];
-                    let xyz = [cx, cy, cz].map(|x| x as f32 * 16.0);
-                    for &dx in [0.0, 16.0].iter() {
-                        for &dy in [0.0, 16.0].iter() {
-                            for &dz in [0.0, 16.0].iter() {
-                                use vecmath::col_mat4_transform;
-
-                                let v = vec3_add(xyz, [dx, dy, dz]);
-                                let xyzw = col_mat4_transform(view_mat, [v[0], v[1], v[2], 1.0]);
-                                let v = col_mat4_transform(projection_mat, xyzw);
-                                let xyz = vec3_scale([v[0], v[1], v[2]], 1.0 / v[3]);
-                                bb_min = Array::from_fn(|i| bb_min[i].min(xyz[i]));
-                                bb_max = Array::from_fn(|i| bb_max[i].max(xyz[i]));
-                            }
-                        }
-                    }
-
-                    let cull_bits: [bool; 3] = Array::from_fn(|i| {
-                        let (min, max) = (bb_min[i], bb_max[i]);
-                        min.signum() == max.signum() && min.abs().min(max.abs()) >= 1.0
-                    });
-
-                    if !cull_bits.iter().any(|&cull| cull) {
-                        renderer.render(buffer);
-                        num_chunks += 1;
-
-                        if bb_min[0] < 0.0 && bb_max[0] > 0.0 || bb_min[1] < 0.0 && bb_max[1] > 0.0
-                        {
-                            num_sorted_chunks += 1;
-                        }
+            let mut translucent = vec![];
+            chunk_buffers.each_buffer(|coords, buffers| {
+                num_total_chunks += 1;
+
+                if visible.contains(&coords) {
+                    renderer.render_section(buffers);
+                    num_chunks += 1;
+                    if buffers.has_translucent() {
+                        translucent.push(coords);
                     }
                 }
             });
+
+            // Translucent sections go last, furthest first.
+            shader::sort_back_to_front(&mut translucent, camera.position);
+            for coords in translucent {
+                let buffers = chunk_buffers.get_mut(coords).unwrap();
+                renderer.render_translucent(buffers, camera.position);
+            }
+
+            target = raycast::raycast(
+                &chunk_manager,
+                &*block_states,
+                camera.position,
+                camera.forward,
+                REACH,
+            );
+            if let Some(hit) = target {
+                use raycast::BlockBounds;
+
+                let (min, max) = block_states.block_bounds(hit.block).unwrap();
+                let xyz = hit.pos.map(|x| x as f32);
+                renderer.render_outline(
+                    Array::from_fn(|i| xyz[i] + min[i] - 0.002),
+                    Array::from_fn(|i| xyz[i] + max[i] + 0.002),
+                );
+            }
             let end_duration = start_time.elapsed();
             renderer.flush(&mut device);
             let frame_end_duration = start_time.elapsed();
 
             let fps = fps_counter.tick();
             let title = format!(
-                "Hematite sort={} render={} total={} in {:.2}ms+{:.2}ms @ {}FPS - {}",
-                num_sorted_chunks,
+                "Hematite render={} total={} in {:.2}ms+{:.2}ms @ {}FPS - {}",
                 num_chunks,
                 num_total_chunks,
                 end_duration.as_secs() as f64
@@ -280,48 +387,69 @@ fn main() {
             device.cleanup();
         }
 
-        if e.update_args().is_some() {
-            use std::i32;
-            // HACK(eddyb) find the closest chunk to the player.
-            // The pending vector should be sorted instead.
+        if let Some(args) = e.update_args() {
+            if let Some(ref mut player) = walking {
+                // Same as the movement of `FirstPerson`, along the ground.
+                let [dx, dy, dz] = first_person.direction;
+                let (s, c) = (first_person.yaw.sin(), first_person.yaw.cos());
+                let input = physics::Input {
+                    direction: [s * dx - c * dz, s * dz + c * dx],
+                    jump: dy > 0.0,
+                    sprint: first_person.velocity > 1.0,
+                };
+                player.step(&chunk_manager, &*block_states, input, args.dt as f32);
+                first_person.position = player.position;
+            }
+
             let pp = first_person.position.map(|x| (x / 16.0).floor() as i32);
-            let closest = pending_chunks
-                .iter()
-                .enumerate()
-                .fold(
-                    (None, i32::max_value()),
-                    |(best_i, best_dist), (i, &(cc, _, _, _))| {
-                        let xyz = [cc[0] - pp[0], cc[1] - pp[1], cc[2] - pp[2]].map(|x| x * x);
-                        let dist = xyz[0] + xyz[1] + xyz[2];
-                        if dist < best_dist {
-                            (Some(i), dist)
-                        } else {
-                            (best_i, best_dist)
-                        }
-                    },
-                )
-                .0;
-            let pending = closest.and_then(|i| {
-                // Vec swap_remove doesn't return Option anymore
-                match pending_chunks.len() {
-                    0 => None,
-                    _ => Some(pending_chunks.swap_remove(i)),
+            let distance = |cc: [i32; 3]| {
+                let xyz = [cc[0] - pp[0], cc[1] - pp[1], cc[2] - pp[2]].map(|x| x * x);
+                xyz[0] + xyz[1] + xyz[2]
+            };
+            if camera_chunk != Some(pp) {
+                camera_chunk = Some(pp);
+                mesher.reprioritize(|(neighborhood, _)| distance(neighborhood.coords));
+            }
+
+            let unloaded = streamer.update(
+                first_person.position,
+                &mut chunk_manager,
+                &mut region_loader,
+            );
+            for (x, z) in unloaded {
+                chunk_buffers.remove_column(x, z);
+                mesh_generations.retain(|c: &[i32; 3], _| c[0] != x || c[2] != z);
+                visibility.retain(|c: &[i32; 3], _| c[0] != x || c[2] != z);
+            }
+            for (x, z) in chunk_manager.take_unlit_columns() {
+                lighting::relight_column(&mut chunk_manager, &*block_states, x, z);
+            }
+
+            for coords in chunk_manager.take_dirty() {
+                if let Some(neighborhood) = chunk_manager.neighborhood(coords) {
+                    next_generation += 1;
+                    mesh_generations.insert(coords, next_generation);
+                    mesher.push(distance(coords), (neighborhood, next_generation));
                 }
-            });
-            if let Some((coords, buffer, chunks, column_biomes)) = pending {
-                minecraft::block_state::fill_buffer(
-                    &block_states,
-                    &biomes,
-                    &mut staging_buffer,
-                    coords,
-                    chunks,
-                    column_biomes,
-                );
-                *buffer.borrow_mut() = Some(renderer.create_buffer(&staging_buffer[..]));
-                staging_buffer.clear();
+            }
+
+            while let Some((coords, generation, layers, graph)) = mesher.try_recv() {
+                if mesh_generations.get(&coords) != Some(&generation) {
+                    continue;
+                }
+                mesh_generations.remove(&coords);
+                visibility.insert(coords, graph);
+                chunk_buffers.insert(coords, renderer.create_section_buffers(layers));
 
-                if pending_chunks.is_empty() {
+                if mesh_generations.is_empty() && streamer.is_idle() {
                     println!("Finished filling chunk vertex buffers.");
+                    let (num_sections, memory_usage) = chunk_manager.memory_usage();
+                    println!(
+                        "{} sections use {} KiB ({} KiB unpacked).",
+                        num_sections,
+                        memory_usage / 1024,
+                        num_sections * chunk::UNPACKED_CHUNK_SIZE / 1024
+                    );
                 }
             }
         }
@@ -336,6 +464,98 @@ fn main() {
             window.set_capture_cursor(capture_cursor);
         }
 
+        match (e.press_args(), target) {
+            (Some(Mouse(MouseButton::Left)), Some(hit)) => {
+                let [x, y, z] = hit.pos;
+                let name = format!("break block at {} {} {}", x, y, z);
+                journal.transaction(&mut chunk_manager, &name, |world| {
+                    lighting::set_block(world, &*block_states, x, y, z, chunk::EMPTY_BLOCK)
+                });
+            }
+            (Some(Mouse(MouseButton::Right)), Some(hit)) => {
+                use physics::CollisionShapes;
+
+                let d = hit.face.direction();
+                let [x, y, z] = [0, 1, 2].map(|i| hit.pos[i] + d[i]);
+                let replaceable = chunk_manager
+                    .get_block(x, y, z)
+                    .is_some_and(|b| block_states.collision_box(b).is_none());
+                // Don't place blocks inside the player.
+                let blocked = walking.is_some_and(|player| {
+                    block_states
+                        .collision_box(held_block)
+                        .is_some_and(|(min, max)| {
+                            let xyz = [x as f32, y as f32, z as f32];
+                            physics::Aabb {
+                                min: [0, 1, 2].map(|i| xyz[i] + min[i]),
+                                max: [0, 1, 2].map(|i| xyz[i] + max[i]),
+                            }
+                            .intersects(&player.bounding_box())
+                        })
+                });
+                if replaceable && !blocked {
+                    let name = format!("place block at {} {} {}", x, y, z);
+                    journal.transaction(&mut chunk_manager, &name, |world| {
+                        lighting::set_block(world, &*block_states, x, y, z, held_block)
+                    });
+                }
+            }
+            (Some(Mouse(MouseButton::Middle)), Some(hit)) => held_block = hit.block,
+            (Some(Keyboard(Key::Z)), _) => {
+                let load = |x, z| region_store.lock().unwrap().get_chunk_column(x, z);
+                match journal.undo(&mut chunk_manager, load) {
+                    Some(name) => println!("Undid {}", name),
+                    None => println!("Nothing to undo"),
+                }
+            }
+            (Some(Keyboard(Key::Y)), _) => {
+                let load = |x, z| region_store.lock().unwrap().get_chunk_column(x, z);
+                match journal.redo(&mut chunk_manager, load) {
+                    Some(name) => println!("Redid {}", name),
+                    None => println!("Nothing to redo"),
+                }
+            }
+            (Some(Keyboard(Key::P)), _) => {
+                let modified = chunk_manager.modified_columns();
+                let result = {
+                    let columns: Vec<_> = modified
+                        .iter()
+                        .filter_map(|&(x, z)| chunk_manager.chunk_column(x, z).map(|c| (x, z, c)))
+                        .collect();
+                    region_store
+                        .lock()
+                        .unwrap()
+                        .save_columns(&columns, &*block_states)
+                };
+                match result {
+                    Ok(saved) => {
+                        for &(x, z) in saved.iter() {
+                            chunk_manager.mark_saved(x, z);
+                        }
+                        println!("Saved {} chunk columns", saved.len());
+                    }
+                    Err(e) => println!("Warning: couldn't save the world: {}", e),
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(Keyboard(Key::F)) = e.press_args() {
+            walking = match walking {
+                Some(_) => None,
+                None => Some(physics::Player::new(first_person.position)),
+            };
+            println!(
+                "Turned walking {}",
+                if walking.is_some() { "on" } else { "off" }
+            );
+        }
+
+        if e.update_args().is_some() && walking.is_some() {
+            // Don't let the FPS controller fly around.
+            continue;
+        }
+
         if e.mouse_relative_args().is_some() && !capture_cursor {
             // Don't send the mouse event to the FPS controller.
             continue;
@@ -343,4 +563,12 @@ fn main() {
 
         first_person.event(&e);
     }
+
+    let num_modified = chunk_manager.modified_columns().len();
+    if num_modified > 0 {
+        println!(
+            "Warning: changes to {} chunk columns weren't saved",
+            num_modified
+        );
+    }
 }
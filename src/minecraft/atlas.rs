@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::mem;
+use std::path::PathBuf;
+
+use gfx_voxel::texture::{Texture, TextureSettings};
+use image::{Rgba, RgbaImage};
+
+/// Builds an atlas of square block textures, like the one of `gfx_voxel`,
+/// but also taking textures with larger frames, e.g. vanilla's flowing
+/// water, which are scaled down to the size of a tile.
+pub struct AtlasBuilder {
+    image: RgbaImage,
+    // Directory the textures are loaded from.
+    path: PathBuf,
+    // Width and height of a tile, in pixels.
+    unit: u32,
+    // Size of the fully used square in the corner, in tiles.
+    completed_tiles_size: u32,
+    // Position of the next tile along the edges of that square.
+    position: u32,
+    // Pixel positions of the loaded textures, by name.
+    tile_positions: HashMap<String, (u32, u32)>,
+    // Lowest alpha of rectangles in the atlas.
+    min_alpha_cache: HashMap<[u32; 4], u8>,
+}
+
+// The first frame of a texture scaled down to `unit`, averaging
+// the pixels each one covers, or `None` if it's too small.
+fn first_frame(image: &RgbaImage, unit: u32) -> Option<RgbaImage> {
+    let (width, height) = image.dimensions();
+    let scale = width / unit;
+    if scale == 0 || width % unit != 0 || height < width {
+        return None;
+    }
+    Some(RgbaImage::from_fn(unit, unit, |x, y| {
+        let mut sum = [0; 4];
+        for dy in 0..scale {
+            for dx in 0..scale {
+                let pixel = image.get_pixel(x * scale + dx, y * scale + dy);
+                for (s, &p) in sum.iter_mut().zip(pixel.data.iter()) {
+                    *s += p as u32;
+                }
+            }
+        }
+        Rgba {
+            data: sum.map(|s| (s / (scale * scale)) as u8),
+        }
+    }))
+}
+
+impl AtlasBuilder {
+    pub fn new<P: Into<PathBuf>>(path: P, unit: u32) -> AtlasBuilder {
+        AtlasBuilder {
+            image: RgbaImage::new(unit * 4, unit * 4),
+            path: path.into(),
+            unit,
+            completed_tiles_size: 0,
+            position: 0,
+            tile_positions: HashMap::new(),
+            min_alpha_cache: HashMap::new(),
+        }
+    }
+
+    /// Loads `<name>.png` into the atlas, if it isn't already,
+    /// returning its position in pixels.
+    pub fn load(&mut self, name: &str) -> (u32, u32) {
+        if let Some(&pos) = self.tile_positions.get(name) {
+            return pos;
+        }
+        let path = self.path.join(format!("{}.png", name));
+        let image = match image::open(&path) {
+            Ok(image) => image.to_rgba(),
+            Err(e) => panic!("couldn't load {}: {}", path.display(), e),
+        };
+        self.insert(name, &image)
+    }
+
+    /// Adds the first frame of a texture to the atlas,
+    /// returning its position in pixels.
+    pub fn insert(&mut self, name: &str, image: &RgbaImage) -> (u32, u32) {
+        let tile = match first_frame(image, self.unit) {
+            Some(tile) => tile,
+            None => panic!(
+                "{} is {}x{}, not frames of a multiple of {} pixels",
+                name,
+                image.width(),
+                image.height(),
+                self.unit
+            ),
+        };
+
+        let unit = self.unit;
+        let size = self.completed_tiles_size;
+        let (w, h) = self.image.dimensions();
+        // Make room for the next square, twice as large.
+        if self.position == 0 && (unit * size >= w || unit * size >= h) {
+            let old = mem::replace(&mut self.image, RgbaImage::new(w * 2, h * 2));
+            for (x, y, pixel) in old.enumerate_pixels() {
+                self.image.put_pixel(x, y, *pixel);
+            }
+        }
+
+        // Tiles go down the right edge of the square, then along its bottom.
+        let (x, y) = if self.position < size {
+            (size, self.position)
+        } else {
+            (self.position - size, size)
+        };
+        self.position += 1;
+        if self.position > size * 2 {
+            self.position = 0;
+            self.completed_tiles_size += 1;
+        }
+
+        let (x, y) = (x * unit, y * unit);
+        for (dx, dy, pixel) in tile.enumerate_pixels() {
+            self.image.put_pixel(x + dx, y + dy, *pixel);
+        }
+        self.tile_positions.insert(name.to_string(), (x, y));
+        (x, y)
+    }
+
+    /// Finds the lowest alpha value in a rectangle of the atlas.
+    pub fn min_alpha(&mut self, rect: [u32; 4]) -> u8 {
+        if let Some(&alpha) = self.min_alpha_cache.get(&rect) {
+            return alpha;
+        }
+        let [x, y, w, h] = rect;
+        let mut min_alpha = None;
+        for py in y..y + h {
+            for px in x..x + w {
+                let alpha = self.image.get_pixel(px, py).data[3];
+                min_alpha = Some(min_alpha.map_or(alpha, |a: u8| a.min(alpha)));
+            }
+        }
+        let min_alpha = min_alpha.unwrap_or(0);
+        self.min_alpha_cache.insert(rect, min_alpha);
+        min_alpha
+    }
+
+    pub fn complete<R, F>(self, factory: &mut F) -> Texture<R>
+    where
+        R: gfx::Resources,
+        F: gfx::Factory<R>,
+    {
+        let settings = TextureSettings::new().generate_mipmap(true);
+        Texture::from_image(factory, &self.image, &settings).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(width: u32, height: u32, rgba: [u8; 4]) -> RgbaImage {
+        RgbaImage::from_pixel(width, height, Rgba { data: rgba })
+    }
+
+    #[test]
+    fn layout() {
+        let mut atlas = AtlasBuilder::new("textures", 16);
+        let positions: Vec<_> = (0..17)
+            .map(|i| atlas.insert(&i.to_string(), &filled(16, 16, [i, 0, 0, 255])))
+            .collect();
+        assert_eq!(
+            positions[..10],
+            [
+                (0, 0),
+                (16, 0),
+                (0, 16),
+                (16, 16),
+                (32, 0),
+                (32, 16),
+                (0, 32),
+                (16, 32),
+                (32, 32),
+                (48, 0)
+            ]
+        );
+        // Grown past the first 4x4 tiles, keeping the loaded ones.
+        assert_eq!(positions[16], (64, 0));
+        assert_eq!(atlas.image.dimensions(), (128, 128));
+        assert_eq!(atlas.image.get_pixel(40, 20).data, [5, 0, 0, 255]);
+        // Already loaded textures aren't added again.
+        assert_eq!(atlas.load("3"), (16, 16));
+    }
+
+    #[test]
+    fn larger_frames() {
+        // Two 32x32 frames, striped on the first one.
+        let flow = RgbaImage::from_fn(32, 64, |x, y| Rgba {
+            data: match (x % 2, y < 32) {
+                (_, false) => [0, 0, 0, 0],
+                (0, true) => [200, 0, 0, 255],
+                (_, true) => [100, 50, 0, 255],
+            },
+        });
+        let mut atlas = AtlasBuilder::new("textures", 16);
+        assert_eq!(
+            atlas.insert("still", &filled(16, 32, [0, 0, 255, 128])),
+            (0, 0)
+        );
+        assert_eq!(atlas.insert("flow", &flow), (16, 0));
+        assert_eq!(atlas.image.get_pixel(21, 7).data, [150, 25, 0, 255]);
+        assert_eq!(atlas.min_alpha([16, 0, 16, 16]), 255);
+        assert_eq!(atlas.min_alpha([0, 0, 32, 16]), 128);
+        assert!(first_frame(&filled(24, 24, [0; 4]), 16).is_none());
+        assert!(first_frame(&filled(32, 16, [0; 4]), 16).is_none());
+    }
+}
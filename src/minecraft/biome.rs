@@ -2,38 +2,42 @@ use std::ops::Index;
 use std::path::Path;
 
 use crate::chunk::BiomeId;
-use crate::minecraft::data;
+use crate::minecraft::registry::Registry;
 use gfx_voxel::texture::ColorMap;
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct Biome {
-    pub name: &'static str,
+    pub name: String,
     pub temperature: f32,
     pub humidity: f32,
     pub grass_color: [u8; 3],
     pub foliage_color: [u8; 3],
+    pub water_color: [u8; 3],
 }
 
 pub struct Biomes {
-    biomes: Box<[Option<Biome>; 256]>,
+    // By id.
+    biomes: Vec<Option<Biome>>,
 }
 
 impl Biomes {
-    pub fn load(assets: &Path) -> Biomes {
-        let mut biomes = Box::new([None; 256]);
+    pub fn load(assets: &Path, registry: &Registry) -> Biomes {
+        let mut biomes = vec![None; 256];
 
         let grass_colors = Path::new("minecraft/textures/colormap/grass.png");
         let grass_colors = ColorMap::from_path(&assets.join(&grass_colors)).unwrap();
         let foliage_colors = Path::new("minecraft/textures/colormap/foliage.png");
         let foliage_colors = ColorMap::from_path(&assets.join(foliage_colors)).unwrap();
 
-        for (i, &biome) in data::BIOMES.iter().enumerate() {
-            biomes[i] = biome.map(|(name, t, h)| Biome {
-                name,
+        for biome in registry.biomes.iter() {
+            let (t, h) = (biome.temperature, biome.downfall);
+            biomes[biome.id as usize] = Some(Biome {
+                name: biome.name.clone(),
                 temperature: t,
                 humidity: h,
                 grass_color: grass_colors.get(t, h),
                 foliage_color: foliage_colors.get(t, h),
+                water_color: biome.water_color,
             });
         }
 
@@ -1,4 +1,3 @@
-use std::borrow::Cow;
 use std::cmp::max;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
@@ -7,15 +6,23 @@ use std::num::Wrapping;
 use std::path::Path;
 
 use crate::array::*;
-use crate::chunk::{BiomeId, BlockState, Chunk};
+use crate::chunk::{BiomeId, BlockState, Chunk, LightLevel};
 use crate::cube;
+use crate::lighting::LightProperties;
+use crate::minecraft::atlas::AtlasBuilder;
 use crate::minecraft::biome::Biomes;
-use crate::minecraft::data::BLOCK_STATES;
+use crate::minecraft::fluid::{self, Fluid, FluidBlocks, FluidTextures};
 use crate::minecraft::model::OrthoRotation::*;
 use crate::minecraft::model::{self, Model, OrthoRotation};
+use crate::minecraft::multipart::{self, Condition, Properties};
+use crate::minecraft::neighbor::{self, BlockGroups, Group, NeighborRule};
+use crate::minecraft::registry::{BlockStateInfo, Registry};
+use crate::physics::CollisionShapes;
+use crate::raycast::BlockBounds;
 use crate::shader::Vertex;
+use crate::visibility::VisibilityGraph;
 use gfx;
-use gfx_voxel::texture::{AtlasBuilder, ImageSize, Texture};
+use gfx_voxel::texture::{ImageSize, Texture};
 use rustc_serialize::json;
 use vecmath::vec3_add;
 
@@ -23,6 +30,8 @@ use self::PolymorphDecision::*;
 
 pub struct BlockStates<R: gfx::Resources> {
     pub models: Vec<ModelAndBehavior>,
+    // Water and lava, which don't have models.
+    pub fluids: [FluidTextures; 2],
     pub texture: Texture<R>,
 }
 
@@ -78,80 +87,424 @@ pub enum PolymorphDecision {
     // The 'OrSolid' variants also check for any solid blocks.
     IfBlock(Dir, i8, u8),
     IfBlockOrSolid(Dir, i8, u8),
-    //IfGroup(Dir, Group, u8),
-    //IfGroupOrSolid(Dir, Group, u8)
 }
 
 struct Description {
     id: u16,
-    name: &'static str,
-    variant: Cow<'static, str>,
+    info: BlockStateInfo,
+    // The key of the model in the blockstate file.
+    variant: String,
     random_offset: RandomOffset,
     polymorph_oracle: Vec<PolymorphDecision>,
+    light_emission: u8,
+    // Guessed from the model if unknown.
+    light_opacity: Option<u8>,
+    collides: bool,
+}
+
+/// How a block state interacts with light, from 0 to 15.
+#[derive(Copy, Clone, Default)]
+pub struct BlockLight {
+    pub emission: u8,
+    pub opacity: u8,
 }
 
 #[derive(Clone)]
 pub struct ModelAndBehavior {
     pub model: Model,
+    // Weighted models picked from by position, if there are several.
+    pub alternatives: Vec<(u32, Model)>,
     pub random_offset: RandomOffset,
     pub polymorph_oracle: Vec<PolymorphDecision>,
+    pub light: BlockLight,
+    // Whether entities are stopped by the model's bounds.
+    pub collides: bool,
+    pub info: BlockStateInfo,
+    // Parts of a multipart model, drawn on top of each other if they
+    // match the properties of `info`, some of which come from neighbors.
+    pub parts: Vec<Part>,
+    pub neighbor_properties: Vec<(String, NeighborRule)>,
+    // Weighted models by variant, for the variants which
+    // only differ in properties coming from neighbors.
+    pub neighbor_variants: HashMap<String, Vec<(u32, Model)>>,
+    pub group: Group,
+    // Water and lava are drawn from their textures instead of a model.
+    pub fluid: Option<Fluid>,
+}
+
+/// Part of a multipart model, drawn when its condition holds.
+#[derive(Clone)]
+pub struct Part {
+    pub when: Option<Condition>,
+    pub alternatives: Vec<(u32, Model)>,
+}
+
+impl Part {
+    pub fn matches(&self, properties: &Properties) -> bool {
+        self.when.as_ref().is_none_or(|c| c.matches(properties))
+    }
+}
+
+// Picks one of the weighted models for a block position, like vanilla.
+fn pick_weighted(alternatives: &[(u32, Model)], [x, y, z]: [i32; 3]) -> Option<&Model> {
+    let total: u32 = alternatives.iter().map(|&(weight, _)| weight).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut n = ((position_random(x, y, z) as i32) >> 16).unsigned_abs() % total;
+    for &(weight, ref model) in alternatives.iter() {
+        if n < weight {
+            return Some(model);
+        }
+        n -= weight;
+    }
+    None
 }
 
 impl ModelAndBehavior {
     pub fn empty() -> ModelAndBehavior {
         ModelAndBehavior {
             model: Model::empty(),
+            alternatives: vec![],
             random_offset: RandomOffset::None,
             polymorph_oracle: vec![],
+            light: BlockLight::default(),
+            collides: false,
+            parts: vec![],
+            info: BlockStateInfo::default(),
+            neighbor_properties: vec![],
+            neighbor_variants: HashMap::new(),
+            group: Group::None,
+            fluid: None,
         }
     }
 
     pub fn is_empty(&self) -> bool {
         self.model.is_empty()
     }
+
+    /// Picks one of the weighted models for a block position, like vanilla.
+    pub fn model_at(&self, pos: [i32; 3]) -> &Model {
+        pick_weighted(&self.alternatives, pos).unwrap_or(&self.model)
+    }
+
+    /// Picks the models of the parts matching `properties`.
+    pub fn parts_at<'a>(
+        &'a self,
+        properties: &'a Properties,
+        pos: [i32; 3],
+    ) -> impl Iterator<Item = &'a Model> + 'a {
+        self.parts
+            .iter()
+            .filter(move |part| part.matches(properties))
+            .filter_map(move |part| pick_weighted(&part.alternatives, pos))
+    }
+}
+
+// The models drawn for a block: the matching parts of a multipart
+// model, or a single one.
+enum Models<'a, P> {
+    Parts(P),
+    Single(Option<&'a Model>),
+}
+
+impl<'a, P: Iterator<Item = &'a Model>> Iterator for Models<'a, P> {
+    type Item = &'a Model;
+
+    fn next(&mut self) -> Option<&'a Model> {
+        match self {
+            Models::Parts(parts) => parts.next(),
+            Models::Single(model) => model.take(),
+        }
+    }
+}
+
+// One of the models a block state can be drawn with.
+struct Variant {
+    model: String,
+    rotate_x: OrthoRotation,
+    rotate_y: OrthoRotation,
+    rotate_z: OrthoRotation,
+    uvlock: bool,
+    // How often this model is picked, relative to the others.
+    weight: u32,
+}
+
+impl Variant {
+    fn from_json(name: &str, mut variant: json::Object) -> Variant {
+        let model = match variant.remove("model").unwrap() {
+            json::Json::String(s) => s,
+            json => panic!("'model' has invalid value {}", json),
+        };
+        let rotate_x =
+            variant
+                .remove("x")
+                .map_or(Rotate0, |r| match OrthoRotation::from_json(&r) {
+                    Some(r) => r,
+                    None => panic!("invalid rotation for x {}", r),
+                });
+        let rotate_y =
+            variant
+                .remove("y")
+                .map_or(Rotate0, |r| match OrthoRotation::from_json(&r) {
+                    Some(r) => r,
+                    None => panic!("invalid rotation for y {}", r),
+                });
+        let rotate_z =
+            variant
+                .remove("z")
+                .map_or(Rotate0, |r| match OrthoRotation::from_json(&r) {
+                    Some(r) => r,
+                    None => panic!("invalid rotation for z {} in {}", r, name),
+                });
+        let uvlock = variant
+            .remove("uvlock")
+            .map_or(false, |x| x.as_boolean().unwrap());
+        let weight = variant
+            .remove("weight")
+            .map_or(1, |x| x.as_u64().unwrap() as u32);
+        Variant {
+            model,
+            rotate_x,
+            rotate_y,
+            rotate_z,
+            uvlock,
+            weight,
+        }
+    }
+
+    fn load(
+        &self,
+        assets: &Path,
+        atlas: &mut AtlasBuilder,
+        cache: &mut HashMap<String, model::PartialModel>,
+    ) -> Model {
+        let mut model = Model::load(&self.model, assets, atlas, cache);
+        self.rotate(&mut model);
+        model
+    }
+
+    // Rotates around x, then y, then z, clockwise when looking down each axis.
+    fn rotate(&self, model: &mut Model) {
+        let rotate_faces = |m: &mut Model, ix: usize, iy: usize, rot_mat: [i32; 4]| {
+            let (a, b, c, d) = (
+                rot_mat[0] as f32,
+                rot_mat[1] as f32,
+                rot_mat[2] as f32,
+                rot_mat[3] as f32,
+            );
+            for face in m.faces.iter_mut() {
+                for vertex in face.vertices.iter_mut() {
+                    let xyz = &mut vertex.xyz;
+                    let (x, y) = (xyz[ix] - 0.5, xyz[iy] - 0.5);
+                    xyz[ix] = a * x + b * y + 0.5;
+                    xyz[iy] = c * x + d * y + 0.5;
+                }
+                let fixup_cube_face = |f: cube::Face| {
+                    let (a, b, c, d) = (rot_mat[0], rot_mat[1], rot_mat[2], rot_mat[3]);
+                    let mut dir = f.direction();
+                    let (x, y) = (dir[ix], dir[iy]);
+                    dir[ix] = a * x + b * y;
+                    dir[iy] = c * x + d * y;
+                    cube::Face::from_direction(dir).unwrap()
+                };
+                face.cull_face = face.cull_face.map(fixup_cube_face);
+                face.ao_face = face.ao_face.map(fixup_cube_face);
+                if self.uvlock {
+                    // Skip over faces that are constant in the ix or iy axis.
+                    let xs = face.vertices.map(|v| v.xyz[ix]);
+                    if xs.map(|x| (x - xs[0]).abs() < f32::EPSILON) == [true, true, true, true] {
+                        continue;
+                    }
+                    let ys = face.vertices.map(|v| v.xyz[iy]);
+                    if ys.map(|y| (y - ys[0]).abs() < f32::EPSILON) == [true, true, true, true] {
+                        continue;
+                    }
+
+                    let uvs = face.vertices.map(|x| x.uv);
+                    let uv_min =
+                        [0, 1].map(|i| (uvs[0][i]).min(uvs[1][i]).min(uvs[2][i]).min(uvs[3][i]));
+                    let temp = uv_min.map(|x| (x / 16.0).floor() * 16.0);
+                    let (u_base, v_base) = (temp[0], temp[1]);
+                    for vertex in face.vertices.iter_mut() {
+                        let uv = &mut vertex.uv;
+                        let (u, v) = (uv[0] - u_base - 8.0, uv[1] - v_base - 8.0);
+                        uv[0] = a * u - b * v + 8.0 + u_base;
+                        uv[1] = -c * u + d * v + 8.0 + v_base;
+                    }
+                }
+            }
+        };
+
+        let rotate_faces = |m: &mut Model, ix: usize, iy: usize, r: OrthoRotation| match r {
+            Rotate0 => {}
+            Rotate90 => rotate_faces(m, ix, iy, [0, -1, 1, 0]),
+            Rotate180 => rotate_faces(m, ix, iy, [-1, 0, 0, -1]),
+            Rotate270 => rotate_faces(m, ix, iy, [0, 1, -1, 0]),
+        };
+
+        rotate_faces(model, 2, 1, self.rotate_x);
+        rotate_faces(model, 0, 2, self.rotate_y);
+        rotate_faces(model, 1, 0, self.rotate_z);
+    }
+}
+
+// The contents of a blockstate file.
+enum Definition {
+    // Models by variant, e.g. `facing=north,half=top`.
+    Variants(HashMap<String, Vec<Variant>>),
+    // Parts drawn together when their conditions hold.
+    Multipart(Vec<(Option<Condition>, Vec<Variant>)>),
+}
+
+impl Definition {
+    /// Loads a blockstate file, or `None` if there isn't one.
+    fn load(name: &str, assets: &Path) -> Option<Definition> {
+        let path = assets.join(Path::new(&format!("minecraft/blockstates/{}.json", name)));
+        let mut json = match json::Json::from_reader(&mut File::open(&path).ok()?).unwrap() {
+            json::Json::Object(json) => json,
+            json => panic!("root object has invalid value {}", json),
+        };
+        // A variant is either one model or a list of weighted ones.
+        let variants = |k: &str, v: json::Json| -> Vec<Variant> {
+            let variants = match v {
+                json::Json::Object(o) => vec![o],
+                json::Json::Array(l) => l
+                    .into_iter()
+                    .map(|v| match v {
+                        json::Json::Object(o) => o,
+                        json => panic!("{}#{} has invalid value {}", name, k, json),
+                    })
+                    .collect(),
+                json => panic!("{}#{} has invalid value {}", name, k, json),
+            };
+            variants
+                .into_iter()
+                .map(|v| Variant::from_json(name, v))
+                .collect()
+        };
+        if let Some(multipart) = json.remove("multipart") {
+            let parts = match multipart {
+                json::Json::Array(parts) => parts,
+                json => panic!("'multipart' has invalid value {}", json),
+            };
+            return Some(Definition::Multipart(
+                parts
+                    .into_iter()
+                    .map(|part| {
+                        let mut part = match part {
+                            json::Json::Object(part) => part,
+                            json => panic!("{} has invalid part {}", name, json),
+                        };
+                        let when = part.remove("when").map(|c| Condition::from_json(&c));
+                        let apply = match part.remove("apply") {
+                            Some(apply) => apply,
+                            None => panic!("{} has a part without 'apply'", name),
+                        };
+                        let apply = variants("apply", apply);
+                        if apply.is_empty() {
+                            panic!("{} has a part with an empty 'apply'", name);
+                        }
+                        (when, apply)
+                    })
+                    .collect(),
+            ));
+        }
+        match json.remove("variants").unwrap() {
+            json::Json::Object(v) => Some(Definition::Variants(
+                v.into_iter()
+                    .map(|(k, v)| {
+                        let variants = variants(&k, v);
+                        (k, variants)
+                    })
+                    .collect(),
+            )),
+            json => panic!("'variants' has invalid value {}", json),
+        }
+    }
+}
+
+// Finds the models of a variant, either by name or, like in newer
+// blockstate files, by the first key whose properties all match.
+fn find_variant<'a>(
+    variants: &'a HashMap<String, Vec<Variant>>,
+    variant: &str,
+    properties: &Properties,
+) -> Option<&'a Vec<Variant>> {
+    variants.get(variant).or_else(|| {
+        let mut keys: Vec<_> = variants.keys().collect();
+        keys.sort();
+        let key = keys.into_iter().find(|key| {
+            multipart::parse_properties(key)
+                .iter()
+                .all(|(k, v)| properties.get(k) == Some(v))
+        })?;
+        variants.get(key)
+    })
+}
+
+// Vanilla's pseudo-random number for a block position, used for
+// random offsets and models, so that they look the same as there.
+fn position_random(x: i32, y: i32, z: i32) -> i64 {
+    let seed = Wrapping((Wrapping(x) * Wrapping(3129871)).0 as i64)
+        ^ (Wrapping(z as i64) * Wrapping(116129781))
+        ^ Wrapping(y as i64);
+    (seed * seed * Wrapping(42317861) + seed * Wrapping(11)).0
 }
 
 impl<R: gfx::Resources> BlockStates<R> {
-    pub fn load<F: gfx::Factory<R>>(assets: &Path, f: &mut F) -> BlockStates<R> {
-        let mut last_id = BLOCK_STATES.last().map_or(0, |state| state.0);
-        let mut states = Vec::<Description>::with_capacity(BLOCK_STATES.len().next_power_of_two());
+    pub fn load<F: gfx::Factory<R>>(
+        assets: &Path,
+        registry: &Registry,
+        f: &mut F,
+    ) -> BlockStates<R> {
+        let entries = &registry.block_states;
+        let mut last_id = entries.last().map_or(0, |state| state.id);
+        let mut states = Vec::<Description>::with_capacity(entries.len().next_power_of_two());
         let mut extras = vec![];
         let mut flower1 = None::<u16>;
         let mut flower2 = None::<u16>;
-        for (i, &(id, name, variant)) in BLOCK_STATES.iter().enumerate() {
+        for (i, entry) in entries.iter().enumerate() {
+            let (id, info, name) = (entry.id, &entry.info, &entry.info.name[..]);
             let mut polymorph_oracle = vec![];
             let mut random_offset = RandomOffset::None;
 
-            // Find double_plant.
-            if variant == "half=upper" {
-                if name != "paeonia" {
-                    println!("Warning: unknown upper double_plant {}", name);
-                }
-                let (_, lower_name, lower_variant) = BLOCK_STATES[i - 1];
-                assert!(lower_name == name && lower_variant == "half=lower");
-                let lower = BLOCK_STATES[..i - 1].iter().enumerate().rev();
-                let lower = lower.take_while(|&(i, &(id, _, variant))| {
-                    id + 1 == BLOCK_STATES[i + 1].0 && variant == "half=lower"
-                });
+            // Find double_plant, the upper half of which doesn't know
+            // its plant before 1.13.
+            let is_half = |info: &BlockStateInfo, half| {
+                info.properties.len() == 1 && info.get("half") == Some(half)
+            };
+            let is_lower = |i: usize| is_half(&entries[i].info, "lower");
+            let upper = is_half(info, "upper");
+            if upper && i > 0 && entries[i - 1].info.name == name && is_lower(i - 1) {
+                let lower = entries[..i - 1].iter().enumerate().rev();
+                let lower = lower
+                    .take_while(|&(i, state)| state.id + 1 == entries[i + 1].id && is_lower(i));
                 // Note: excluding paeonia itself, which works as-is.
                 let num_plants = lower.count();
+                if num_plants > 0 && name != "paeonia" {
+                    println!("Warning: unknown upper double_plant {}", name);
+                }
 
                 for j in i - 1 - num_plants..i - 1 {
                     last_id += 1;
-                    let (_, lower_name, _) = BLOCK_STATES[j];
+                    let info = entries[j].info.with("half", "upper");
                     extras.push(Description {
                         id: last_id,
-                        name: lower_name,
-                        variant: Cow::Borrowed("half=upper"),
+                        variant: info.variant(),
+                        info,
                         random_offset: RandomOffset::XZ,
                         polymorph_oracle: vec![],
+                        light_emission: states[j].light_emission,
+                        light_opacity: states[j].light_opacity,
+                        collides: states[j].collides,
                     });
                     states[j].random_offset = RandomOffset::XZ;
 
                     let next_index = polymorph_oracle.len() as u8;
                     polymorph_oracle.push(IfBlock(
                         Dir::Down,
-                        (BLOCK_STATES[j].0.wrapping_sub(id)) as i8,
+                        (entries[j].id.wrapping_sub(id)) as i8,
                         next_index.wrapping_add(2),
                     ));
                     polymorph_oracle.push(PickBlockState(last_id));
@@ -172,21 +525,25 @@ impl<R: gfx::Resources> BlockStates<R> {
                 random_offset = RandomOffset::XZ;
             }
 
-            let variant = if variant.ends_with(",shape=outer_right") {
-                Cow::Owned(format!("{}=straight", &variant[..variant.len() - 12]))
+            // The shape of stairs comes from their neighbors.
+            let variant = if Group::of(name) == Group::Stairs {
+                info.with("shape", "straight").variant()
             } else {
-                Cow::Borrowed(variant)
+                entry.variant()
             };
 
             states.push(Description {
                 id,
-                name,
+                info: info.clone(),
                 variant,
                 random_offset,
                 polymorph_oracle,
+                light_emission: entry.light_emission,
+                light_opacity: entry.light_opacity,
+                collides: !entry.passable,
             });
         }
-        states.extend(extras.into_iter());
+        states.extend(extras);
 
         BlockStates::load_with_states(assets, f, states)
     }
@@ -196,187 +553,191 @@ impl<R: gfx::Resources> BlockStates<R> {
         f: &mut F,
         states: Vec<Description>,
     ) -> BlockStates<R> {
-        struct Variant {
-            model: String,
-            rotate_x: OrthoRotation,
-            rotate_y: OrthoRotation,
-            uvlock: bool,
-        }
-
         let last_id = states.last().map_or(0, |state| state.id);
         let mut models = Vec::with_capacity(last_id as usize + 1);
-        let mut atlas = AtlasBuilder::new(assets.join(Path::new("minecraft/textures")), 16, 16);
+        let mut atlas = AtlasBuilder::new(assets.join(Path::new("minecraft/textures")), 16);
         let mut partial_model_cache = HashMap::new();
-        let mut block_state_cache: HashMap<String, HashMap<String, Variant>> = HashMap::new();
+        let mut block_state_cache: HashMap<String, Option<Definition>> = HashMap::new();
 
         for state in states.into_iter() {
-            let variants = match block_state_cache.entry(state.name.to_string()) {
+            let fluid = Fluid::from_name(&state.info.name);
+            let definition = match block_state_cache.entry(state.info.name.clone()) {
                 Occupied(entry) => entry.into_mut(),
-                Vacant(entry) => entry.insert({
-                    let name = state.name;
-                    let path =
-                        assets.join(Path::new(&format!("minecraft/blockstates/{}.json", name)));
-                    match json::Json::from_reader(&mut File::open(&path).unwrap()).unwrap() {
-                        json::Json::Object(mut json) => match json.remove("variants").unwrap() {
-                            json::Json::Object(variants) => variants
-                                .into_iter()
-                                .map(|(k, v)| {
-                                    let mut variant = match v {
-                                        json::Json::Object(o) => o,
-                                        json::Json::Array(l) => {
-                                            println!(
-                                                "ignoring {} extra variants for {}#{}",
-                                                l.len() - 1,
-                                                name,
-                                                k
-                                            );
-                                            match l.into_iter().next() {
-                                                Some(json::Json::Object(o)) => Some(o),
-                                                _ => None,
-                                            }
-                                            .unwrap()
-                                        }
-                                        json => panic!("{}#{} has invalid value {}", name, k, json),
-                                    };
-                                    let model = match variant.remove("model").unwrap() {
-                                        json::Json::String(s) => s,
-                                        json => panic!("'model' has invalid value {}", json),
-                                    };
-                                    let rotate_x = variant.remove("x").map_or(Rotate0, |r| {
-                                        match OrthoRotation::from_json(&r) {
-                                            Some(r) => r,
-                                            None => panic!("invalid rotation for x {}", r),
-                                        }
-                                    });
-                                    let rotate_y = variant.remove("y").map_or(Rotate0, |r| {
-                                        match OrthoRotation::from_json(&r) {
-                                            Some(r) => r,
-                                            None => panic!("invalid rotation for y {}", r),
-                                        }
-                                    });
-                                    if let Some(r) = variant.remove("z") {
-                                        println!("ignoring z rotation {} in {}", r, name)
-                                    }
-                                    let uvlock = variant
-                                        .remove("uvlock")
-                                        .map_or(false, |x| x.as_boolean().unwrap());
-                                    (
-                                        k,
-                                        Variant {
-                                            model,
-                                            rotate_x,
-                                            rotate_y,
-                                            uvlock,
-                                        },
-                                    )
-                                })
-                                .collect(),
-                            json => panic!("'variants' has invalid value {}", json),
-                        },
-                        json => panic!("root object has invalid value {}", json),
+                Vacant(entry) => {
+                    let definition = Definition::load(&state.info.name, assets);
+                    // Air and fluids don't have any.
+                    if definition.is_none() && fluid.is_none() && !state.info.name.ends_with("air")
+                    {
+                        println!("Warning: no blockstate file for {}", state.info.name);
                     }
-                }),
+                    entry.insert(definition)
+                }
             };
-
-            let variant = match state.variant {
-                Cow::Owned(ref variant) => variants.get(variant),
-                Cow::Borrowed(variant) => variants.get(variant),
-            }
-            .unwrap();
-            let mut model =
-                Model::load(&variant.model, assets, &mut atlas, &mut partial_model_cache);
-
-            let rotate_faces = |m: &mut Model, ix: usize, iy: usize, rot_mat: [i32; 4]| {
-                let (a, b, c, d) = (
-                    rot_mat[0] as f32,
-                    rot_mat[1] as f32,
-                    rot_mat[2] as f32,
-                    rot_mat[3] as f32,
-                );
-                for face in m.faces.iter_mut() {
-                    for vertex in face.vertices.iter_mut() {
-                        let xyz = &mut vertex.xyz;
-                        let (x, y) = (xyz[ix] - 0.5, xyz[iy] - 0.5);
-                        xyz[ix] = a * x + b * y + 0.5;
-                        xyz[iy] = c * x + d * y + 0.5;
+            let definition = match (definition.as_ref(), fluid) {
+                (Some(definition), _) => definition,
+                // Kept without a model, for their light and name.
+                (None, Some(fluid)) => {
+                    while models.len() <= state.id as usize {
+                        models.push(ModelAndBehavior::empty());
                     }
-                    let fixup_cube_face = |f: cube::Face| {
-                        let (a, b, c, d) = (rot_mat[0], rot_mat[1], rot_mat[2], rot_mat[3]);
-                        let mut dir = f.direction();
-                        let (x, y) = (dir[ix], dir[iy]);
-                        dir[ix] = a * x + b * y;
-                        dir[iy] = c * x + d * y;
-                        cube::Face::from_direction(dir).unwrap()
+                    models[state.id as usize] = ModelAndBehavior {
+                        light: BlockLight {
+                            emission: state.light_emission,
+                            opacity: state.light_opacity.unwrap_or(0),
+                        },
+                        collides: state.collides,
+                        info: state.info,
+                        fluid: Some(fluid),
+                        ..ModelAndBehavior::empty()
                     };
-                    face.cull_face = face.cull_face.map(fixup_cube_face);
-                    face.ao_face = face.ao_face.map(fixup_cube_face);
-                    if variant.uvlock {
-                        // Skip over faces that are constant in the ix or iy axis.
-                        let xs = face.vertices.map(|v| v.xyz[ix]);
-                        if xs.map(|x| (x - xs[0]).abs() < f32::EPSILON) == [true, true, true, true]
-                        {
-                            continue;
+                    continue;
+                }
+                (None, None) => continue,
+            };
+
+            let mut load = |variants: &[Variant]| -> Vec<_> {
+                variants
+                    .iter()
+                    .map(|variant| {
+                        let model = variant.load(assets, &mut atlas, &mut partial_model_cache);
+                        (variant.weight, model)
+                    })
+                    .collect()
+            };
+
+            let group = Group::of(&state.info.name);
+            let properties = &state.info.properties;
+            let neighbor_properties: Vec<_> = properties
+                .keys()
+                .filter_map(|name| {
+                    NeighborRule::for_property(group, name).map(|rule| (name.clone(), rule))
+                })
+                .collect();
+            let from_neighbors = |name: &str| neighbor_properties.iter().any(|(n, _)| n == name);
+
+            let mut neighbor_variants = HashMap::new();
+            let (model, alternatives, parts) = match definition {
+                Definition::Variants(variants) => {
+                    if !neighbor_properties.is_empty() {
+                        for (key, variants) in variants.iter() {
+                            let other = multipart::parse_properties(key);
+                            let same = other
+                                .iter()
+                                .all(|(k, v)| from_neighbors(k) || properties.get(k) == Some(v));
+                            if same {
+                                let key = multipart::format_properties(&other);
+                                neighbor_variants.insert(key, load(variants));
+                            }
                         }
-                        let ys = face.vertices.map(|v| v.xyz[iy]);
-                        if ys.map(|y| (y - ys[0]).abs() < f32::EPSILON) == [true, true, true, true]
-                        {
+                    }
+                    let variants = match find_variant(variants, &state.variant, properties) {
+                        Some(variants) => variants,
+                        None => {
+                            println!(
+                                "Warning: no model for {}#{}",
+                                state.info.name, state.variant
+                            );
                             continue;
                         }
-
-                        let uvs = face.vertices.map(|x| x.uv);
-                        let uv_min = [0, 1]
-                            .map(|i| (uvs[0][i]).min(uvs[1][i]).min(uvs[2][i]).min(uvs[3][i]));
-                        let temp = uv_min.map(|x| (x / 16.0).floor() * 16.0);
-                        let (u_base, v_base) = (temp[0], temp[1]);
-                        for vertex in face.vertices.iter_mut() {
-                            let uv = &mut vertex.uv;
-                            let (u, v) = (uv[0] - u_base - 8.0, uv[1] - v_base - 8.0);
-                            uv[0] = a * u - b * v + 8.0 + u_base;
-                            uv[1] = -c * u + d * v + 8.0 + v_base;
-                        }
+                    };
+                    let mut alternatives = load(variants);
+                    let model = alternatives[0].1.clone();
+                    if alternatives.len() == 1 {
+                        alternatives.clear();
                     }
+                    (model, alternatives, vec![])
+                }
+                Definition::Multipart(parts) => {
+                    let parts: Vec<_> = parts
+                        .iter()
+                        .map(|(when, variants)| Part {
+                            when: when.clone(),
+                            alternatives: load(variants),
+                        })
+                        .collect();
+                    // Used for bounds and opacity, without the
+                    // parts which depend on neighbors.
+                    let mut model = Model::empty();
+                    for part in parts.iter().filter(|part| part.matches(properties)) {
+                        model.merge(&part.alternatives[0].1);
+                    }
+                    (model, vec![], parts)
                 }
             };
 
-            let rotate_faces = |m: &mut Model, ix: usize, iy: usize, r: OrthoRotation| match r {
-                Rotate0 => {}
-                Rotate90 => rotate_faces(m, ix, iy, [0, -1, 1, 0]),
-                Rotate180 => rotate_faces(m, ix, iy, [-1, 0, 0, -1]),
-                Rotate270 => rotate_faces(m, ix, iy, [0, 1, -1, 0]),
-            };
-
-            rotate_faces(&mut model, 2, 1, variant.rotate_x);
-            rotate_faces(&mut model, 0, 2, variant.rotate_y);
-
             while models.len() <= state.id as usize {
                 models.push(ModelAndBehavior::empty());
             }
 
+            let light = BlockLight {
+                emission: state.light_emission,
+                opacity: state.light_opacity.unwrap_or(if model.opacity.is_opaque() {
+                    15
+                } else {
+                    0
+                }),
+            };
             models[state.id as usize] = ModelAndBehavior {
                 model,
+                alternatives,
                 random_offset: state.random_offset,
                 polymorph_oracle: state.polymorph_oracle,
+                light,
+                collides: state.collides,
+                parts,
+                info: state.info,
+                neighbor_properties,
+                neighbor_variants,
+                group,
+                fluid,
             };
         }
 
         drop(partial_model_cache);
         drop(block_state_cache);
 
+        let mut fluids =
+            [Fluid::Water, Fluid::Lava].map(|fluid| FluidTextures::load(fluid, &mut atlas));
+
         let texture = atlas.complete(f);
         let (width, height) = texture.get_size();
         let u_unit = 1.0 / (width as f32);
         let v_unit = 1.0 / (height as f32);
 
         for model in models.iter_mut() {
-            for face in model.model.faces.iter_mut() {
-                for vertex in face.vertices.iter_mut() {
-                    vertex.uv[0] *= u_unit;
-                    vertex.uv[1] *= v_unit;
+            let alternatives = model.alternatives.iter_mut().map(|(_, m)| m);
+            let parts = model
+                .parts
+                .iter_mut()
+                .flat_map(|part| part.alternatives.iter_mut().map(|(_, m)| m));
+            let neighbor_variants = model
+                .neighbor_variants
+                .values_mut()
+                .flat_map(|alternatives| alternatives.iter_mut().map(|(_, m)| m));
+            for m in Some(&mut model.model)
+                .into_iter()
+                .chain(alternatives)
+                .chain(parts)
+                .chain(neighbor_variants)
+            {
+                for face in m.faces.iter_mut() {
+                    for vertex in face.vertices.iter_mut() {
+                        vertex.uv[0] *= u_unit;
+                        vertex.uv[1] *= v_unit;
+                    }
                 }
             }
         }
 
-        BlockStates { models, texture }
+        for fluid in fluids.iter_mut() {
+            fluid.scale(u_unit, v_unit);
+        }
+
+        BlockStates {
+            models,
+            fluids,
+            texture,
+        }
     }
 
     pub fn get_model(&self, i: BlockState) -> Option<&ModelAndBehavior> {
@@ -388,6 +749,11 @@ impl<R: gfx::Resources> BlockStates<R> {
         }
     }
 
+    /// What a block state is, if it has a model.
+    pub fn info(&self, i: BlockState) -> Option<&BlockStateInfo> {
+        self.get_model(i).map(|m| &m.info)
+    }
+
     pub fn texture(&self) -> &Texture<R> {
         &self.texture
     }
@@ -400,16 +766,133 @@ impl<R: gfx::Resources> BlockStates<R> {
             self.models[i].model.opacity
         }
     }
+
+    pub fn get_light(&self, i: BlockState) -> BlockLight {
+        self.models
+            .get(i.value as usize)
+            .map_or(BlockLight::default(), |m| m.light)
+    }
+
+    pub fn light_emission(&self, i: BlockState) -> u8 {
+        self.get_light(i).emission
+    }
+
+    pub fn light_opacity(&self, i: BlockState) -> u8 {
+        self.get_light(i).opacity
+    }
+
+    /// Whether a block state is water or lava.
+    pub fn fluid(&self, i: BlockState) -> Option<Fluid> {
+        self.models.get(i.value as usize).and_then(|m| m.fluid)
+    }
+}
+
+impl<R: gfx::Resources> FluidBlocks for BlockStates<R> {
+    fn fluid(&self, block: BlockState) -> Option<Fluid> {
+        BlockStates::fluid(self, block)
+    }
+
+    fn opacity(&self, block: BlockState) -> model::Opacity {
+        self.get_opacity(block)
+    }
+}
+
+impl<R: gfx::Resources> LightProperties for BlockStates<R> {
+    fn light_emission(&self, block: BlockState) -> u8 {
+        BlockStates::light_emission(self, block)
+    }
+
+    fn light_opacity(&self, block: BlockState) -> u8 {
+        BlockStates::light_opacity(self, block)
+    }
+}
+
+impl<R: gfx::Resources> BlockGroups for BlockStates<R> {
+    fn group(&self, block: BlockState) -> Group {
+        self.models
+            .get(block.value as usize)
+            .map_or(Group::None, |m| m.group)
+    }
+
+    fn is_opaque(&self, block: BlockState) -> bool {
+        self.get_opacity(block).is_opaque()
+    }
+
+    fn properties(&self, block: BlockState) -> Option<&Properties> {
+        self.models
+            .get(block.value as usize)
+            .map(|m| &m.info.properties)
+    }
 }
 
+impl<R: gfx::Resources> CollisionShapes for BlockStates<R> {
+    fn collision_box(&self, block: BlockState) -> Option<([f32; 3], [f32; 3])> {
+        self.get_model(block)
+            .filter(|m| m.collides)
+            .and_then(|m| m.model.bounds())
+    }
+}
+
+impl<R: gfx::Resources> BlockBounds for BlockStates<R> {
+    fn block_bounds(&self, block: BlockState) -> Option<([f32; 3], [f32; 3])> {
+        self.get_model(block).and_then(|m| m.model.bounds())
+    }
+}
+
+// Adds the faces of a water or lava block to the buffers.
+fn fill_fluid<R: gfx::Resources, A>(
+    block_states: &BlockStates<R>,
+    biomes: &Biomes,
+    buffers: &mut [Vec<Vertex>; 3],
+    fluid: Fluid,
+    block_xyz: [f32; 3],
+    at: &A,
+    biome: Option<BiomeId>,
+) where
+    A: Fn([i32; 3]) -> (BlockState, LightLevel),
+{
+    let (textures, layer) = match fluid {
+        Fluid::Water => (&block_states.fluids[0], model::Layer::Translucent),
+        Fluid::Lava => (&block_states.fluids[1], model::Layer::Opaque),
+    };
+    let tint = match (fluid, biome) {
+        (Fluid::Water, Some(biome)) => biomes[biome].water_color,
+        _ => [0xff, 0xff, 0xff],
+    };
+    let faces = fluid::fluid_faces(block_states, fluid, textures, |dir| at(dir).0);
+    for (face, vertices) in faces {
+        // Lit by the brightest of the block and the one it faces.
+        let light = [at([0, 0, 0]).1, at(face.direction()).1]
+            .iter()
+            .map(|l| max(l.block_light(), l.sky_light()))
+            .max()
+            .unwrap();
+        let shade = match face {
+            cube::Up => 1.0,
+            cube::North | cube::South => 0.8,
+            cube::East | cube::West => 0.6,
+            cube::Down => 0.5,
+        };
+        let light_factor = (0.2 + light as f32 / 15.0 * 0.8) * shade;
+        let v = vertices.map(|vertex| Vertex {
+            xyz: vec3_add(block_xyz, vertex.xyz),
+            uv: vertex.uv,
+            rgb: tint.map(|x| x as f32 / 255.0 * light_factor),
+        });
+        buffers[layer as usize].extend([0, 1, 2, 2, 3, 0].iter().map(|&i| v[i]));
+    }
+}
+
+/// Builds the vertices of a section, one buffer per `model::Layer`,
+/// and finds which of its faces see each other.
 pub fn fill_buffer<R: gfx::Resources>(
     block_states: &BlockStates<R>,
     biomes: &Biomes,
-    buffer: &mut Vec<Vertex>,
+    buffers: &mut [Vec<Vertex>; 3],
     coords: [i32; 3],
     chunks: [[[&Chunk; 3]; 3]; 3],
     column_biomes: [[Option<&[[BiomeId; 16]; 16]>; 3]; 3],
-) {
+) -> VisibilityGraph {
     let chunk_xyz = coords.map(|x| x as f32 * 16.0);
     for y in 0..16_usize {
         for z in 0..16_usize {
@@ -423,9 +906,21 @@ pub fn fill_buffer<R: gfx::Resources>(
                     );
                     let chunk = chunks[y / 16][z / 16][x / 16];
                     let (x, y, z) = (x % 16, y % 16, z % 16);
-                    (chunk.blocks[y][z][x], chunk.light_levels[y][z][x])
+                    (chunk.block(x, y, z), chunk.light_level(x, y, z))
                 };
                 let this_block = at([0, 0, 0]).0;
+                if let Some(fluid) = block_states.fluid(this_block) {
+                    fill_fluid(
+                        block_states,
+                        biomes,
+                        buffers,
+                        fluid,
+                        vec3_add([x, y, z].map(|x| x as f32), chunk_xyz),
+                        &at,
+                        column_biomes[1][1].map(|b| b[z][x]),
+                    );
+                    continue;
+                }
                 let model = match block_states.get_model(this_block) {
                     Some(model) if !model.polymorph_oracle.is_empty() => {
                         let mut i = 0;
@@ -448,15 +943,7 @@ pub fn fill_buffer<R: gfx::Resources>(
                                             || block_states.get_opacity(other).is_opaque(),
                                         idx,
                                     )
-                                } /*IfGroup(dir, group, idx) => {
-                                      let other = at(dir.xyz()).0;
-                                      (block_states.models[other.value].group == group, idx)
-                                  }
-                                  IfGroupOrSolid(dir, group, idx) => {
-                                      let other = at(dir.xyz()).0;
-                                      (block_states.models[other.value].group == group ||
-                                       block_states.get_opacity(other).is_opaque(), idx)
-                                  }*/
+                                }
                             };
                             if cond {
                                 i += 1;
@@ -469,26 +956,49 @@ pub fn fill_buffer<R: gfx::Resources>(
                     Some(model) => model,
                     None => continue,
                 };
-                let block_xyz = vec3_add([x, y, z].map(|x| x as f32), chunk_xyz);
+                let pos = [0, 1, 2].map(|i| coords[i] * 16 + [x, y, z][i] as i32);
+                let block_xyz = pos.map(|x| x as f32);
                 let block_xyz = match model.random_offset {
                     RandomOffset::None => block_xyz,
                     random_offset => {
-                        let (x, z) = (block_xyz[0], block_xyz[2]);
-                        let seed = Wrapping((Wrapping(x as i32) * Wrapping(3129871)).0 as i64)
-                            ^ (Wrapping(z as i64) * Wrapping(116129781));
-                        let value = seed * seed * Wrapping(42317861) + seed * Wrapping(11);
-                        let ox = (((value.0 >> 16) & 15) as f32 / 15.0 - 0.5) * 0.5;
-                        let oz = (((value.0 >> 24) & 15) as f32 / 15.0 - 0.5) * 0.5;
+                        let value = position_random(pos[0], 0, pos[2]);
+                        let ox = (((value >> 16) & 15) as f32 / 15.0 - 0.5) * 0.5;
+                        let oz = (((value >> 24) & 15) as f32 / 15.0 - 0.5) * 0.5;
                         let oy = if random_offset == RandomOffset::XYZ {
-                            (((value.0 >> 20) & 15) as f32 / 15.0 - 1.0) * 0.2
+                            (((value >> 20) & 15) as f32 / 15.0 - 1.0) * 0.2
                         } else {
                             0.0
                         };
                         vec3_add(block_xyz, [ox, oy, oz])
                     }
                 };
-                let model = &model.model;
-                for face in model.faces.iter() {
+                let properties = if model.neighbor_properties.is_empty() {
+                    None
+                } else {
+                    let mut properties = model.info.properties.clone();
+                    neighbor::actual_properties(
+                        block_states,
+                        &model.neighbor_properties,
+                        |dir| at(dir).0,
+                        &mut properties,
+                    );
+                    Some(properties)
+                };
+                let properties = properties.as_ref();
+                let neighbor_variant = properties.and_then(|p| {
+                    let alternatives = model
+                        .neighbor_variants
+                        .get(&multipart::format_properties(p))?;
+                    pick_weighted(alternatives, pos)
+                });
+                let models = if !model.parts.is_empty() {
+                    let properties = properties.unwrap_or(&model.info.properties);
+                    Models::Parts(model.parts_at(properties, pos))
+                } else {
+                    let model = neighbor_variant.unwrap_or_else(|| model.model_at(pos));
+                    Models::Single(Some(model))
+                };
+                for (model, face) in models.flat_map(|m| m.faces.iter().map(move |f| (m, f))) {
                     if let Some(cull_face) = face.cull_face {
                         let (neighbor, _) = at(cull_face.direction());
                         if block_states.get_opacity(neighbor).is_opaque() {
@@ -564,7 +1074,7 @@ pub fn fill_buffer<R: gfx::Resources>(
                                     z.wrapping_add(dz as usize).wrapping_add(16),
                                 );
                                 let biome = match column_biomes[z / 16][x / 16] {
-                                    Some(biome) => biomes[biome[z % 16][x % 16]],
+                                    Some(biome) => &biomes[biome[z % 16][x % 16]],
                                     None => continue,
                                 };
                                 rgb = vec3_add(
@@ -608,9 +1118,202 @@ pub fn fill_buffer<R: gfx::Resources>(
                     });
 
                     // Split the clockwise quad into two clockwise triangles.
-                    buffer.extend([0, 1, 2, 2, 3, 0].iter().map(|&i| v[i]));
+                    buffers[face.layer as usize].extend([0, 1, 2, 2, 3, 0].iter().map(|&i| v[i]));
                 }
             }
         }
     }
+    VisibilityGraph::compute(|x, y, z| {
+        block_states
+            .get_opacity(chunks[1][1][1].block(x, y, z))
+            .is_opaque()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::{env, fs, process};
+
+    fn rotated(
+        face: cube::Face,
+        x: OrthoRotation,
+        y: OrthoRotation,
+        z: OrthoRotation,
+    ) -> model::Face {
+        let xyz = face.vertices([0.0, 0.0, 0.0], [1.0, 1.0, 1.0]);
+        let mut model = Model {
+            faces: vec![model::Face {
+                vertices: Array::from_fn(|i| model::Vertex {
+                    xyz: xyz[i],
+                    uv: [0.0, 0.0],
+                }),
+                tint: false,
+                cull_face: Some(face),
+                ao_face: Some(face),
+                layer: model::Layer::Opaque,
+            }],
+            opacity: model::Opacity::Opaque,
+            tint_source: model::Tint::None,
+        };
+        let variant = Variant {
+            model: String::new(),
+            rotate_x: x,
+            rotate_y: y,
+            rotate_z: z,
+            uvlock: false,
+            weight: 1,
+        };
+        variant.rotate(&mut model);
+        model.faces[0]
+    }
+
+    #[test]
+    fn rotate_like_vanilla() {
+        // Horizontal logs (x=90) show their top to the north.
+        let face = rotated(cube::Up, Rotate90, Rotate0, Rotate0);
+        assert_eq!(face.cull_face, Some(cube::North));
+        assert!(face.vertices.iter().all(|v| v.xyz[2] == 0.0));
+        // Stairs facing south (y=90) have their back to the south.
+        let face = rotated(cube::East, Rotate0, Rotate90, Rotate0);
+        assert_eq!(face.cull_face, Some(cube::South));
+        // x is applied before y.
+        let face = rotated(cube::Up, Rotate90, Rotate90, Rotate0);
+        assert_eq!(face.cull_face, Some(cube::East));
+    }
+
+    #[test]
+    fn rotate_z() {
+        let face = rotated(cube::East, Rotate0, Rotate0, Rotate90);
+        assert_eq!(face.cull_face, Some(cube::Down));
+        assert_eq!(face.ao_face, Some(cube::Down));
+        assert!(face.vertices.iter().all(|v| v.xyz[1] == 0.0));
+        let face = rotated(cube::Up, Rotate0, Rotate0, Rotate270);
+        assert_eq!(face.cull_face, Some(cube::West));
+        let face = rotated(cube::North, Rotate0, Rotate0, Rotate180);
+        assert_eq!(face.cull_face, Some(cube::North));
+    }
+
+    // Assets with a single blockstate file.
+    fn assets(test: &str, blockstate: &str) -> PathBuf {
+        let assets = env::temp_dir().join(format!("hematite_{}_{}", test, process::id()));
+        let dir = assets.join("minecraft/blockstates");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("fence.json"), blockstate).unwrap();
+        assets
+    }
+
+    #[test]
+    fn multipart() {
+        let assets = assets(
+            "multipart",
+            r#"{ "multipart": [
+                { "apply": { "model": "fence_post" } },
+                { "when": { "north": "true" },
+                  "apply": [{ "model": "fence_side" }, { "model": "fence_side", "y": 90 }] }
+            ] }"#,
+        );
+        let parts = match Definition::load("fence", &assets) {
+            Some(Definition::Multipart(parts)) => parts,
+            _ => panic!("not a multipart definition"),
+        };
+        fs::remove_dir_all(&assets).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].0.is_none());
+        assert_eq!(parts[0].1.len(), 1);
+        assert!(parts[1].0.is_some());
+        assert_eq!(parts[1].1.len(), 2);
+        assert!(Definition::load("wall", &assets).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "fence has a part with an empty 'apply'")]
+    fn multipart_empty_apply() {
+        let assets = assets(
+            "empty_apply",
+            r#"{ "multipart": [{ "when": { "north": "true" }, "apply": [] }] }"#,
+        );
+        let definition = std::panic::catch_unwind(|| Definition::load("fence", &assets));
+        fs::remove_dir_all(&assets).unwrap();
+        if let Err(e) = definition {
+            std::panic::resume_unwind(e);
+        }
+    }
+
+    #[test]
+    fn models() {
+        let (a, b) = (Model::empty(), Model::empty());
+        let single = Models::Single::<std::iter::Empty<_>>(Some(&a));
+        assert!(single.map(|m| m as *const _).eq([&a as *const _]));
+        let parts = Models::Parts(vec![&a, &b].into_iter());
+        assert!(parts
+            .map(|m| m as *const _)
+            .eq([&a as *const _, &b as *const _]));
+    }
+
+    // Which of `alternatives` is picked for a position.
+    fn picked(alternatives: &[(u32, Model)], pos: [i32; 3]) -> usize {
+        let model = pick_weighted(alternatives, pos).unwrap();
+        alternatives
+            .iter()
+            .position(|(_, m)| std::ptr::eq(m, model))
+            .unwrap()
+    }
+
+    #[test]
+    fn position_random_like_vanilla() {
+        // From MathHelper.getPositionRandom, run on the JVM.
+        assert_eq!(position_random(0, 0, 0), 0);
+        assert_eq!(position_random(1, 64, -1), 3347287841516340540);
+        assert_eq!(position_random(-100, 70, 250), -8904035397517712800);
+        assert_eq!(position_random(123456, 12, -654321), 621780761119133682);
+        assert_eq!(
+            position_random(30000000, 255, -30000000),
+            3799742114983578394
+        );
+    }
+
+    #[test]
+    fn pick_by_position() {
+        let alternatives = vec![
+            (1, Model::empty()),
+            (1, Model::empty()),
+            (1, Model::empty()),
+        ];
+        // The models vanilla picks for these positions.
+        assert_eq!(picked(&alternatives, [1, 64, -1]), 1);
+        assert_eq!(picked(&alternatives, [-100, 70, 250]), 0);
+        assert_eq!(picked(&alternatives, [123456, 12, -654321]), 2);
+        assert_eq!(picked(&alternatives, [30000000, 255, -30000000]), 1);
+
+        // Always the same model for a position.
+        let block = ModelAndBehavior {
+            alternatives,
+            ..ModelAndBehavior::empty()
+        };
+        for pos in [[1, 64, -1], [7, 3, -9], [-42, 200, 17]].iter() {
+            let model = block.model_at(*pos);
+            assert!(std::ptr::eq(block.model_at(*pos), model));
+        }
+        assert!(pick_weighted(&[], [0, 0, 0]).is_none());
+    }
+
+    #[test]
+    fn pick_by_weight() {
+        let alternatives = vec![
+            (1, Model::empty()),
+            (3, Model::empty()),
+            (0, Model::empty()),
+        ];
+        let mut counts = [0; 3];
+        for x in 0..64 {
+            for z in 0..64 {
+                counts[picked(&alternatives, [x, 64, z])] += 1;
+            }
+        }
+        // About a quarter and three quarters of 4096, never the last one.
+        assert!(counts[0] > 900 && counts[0] < 1150, "{:?}", counts);
+        assert_eq!(counts[0] + counts[1], 4096);
+    }
 }
@@ -1,3554 +0,0 @@
-// (name, temperature, humidity)
-pub static BIOMES: [Option<(&'static str, f32, f32)>; 256] = [
-    Some(("Ocean", 0.5, 0.5)),
-    Some(("Plains", 0.8, 0.4)),
-    Some(("Desert", 2.0, 0.0)),
-    Some(("Extreme Hills", 0.2, 0.3)),
-    Some(("Forest", 0.7, 0.8)),
-    Some(("Taiga", 0.3, 0.8)),
-    Some(("Swampland", 0.8, 0.9)),
-    Some(("River", 0.5, 0.5)),
-    Some(("Hell", 2.0, 0.0)),
-    Some(("The End", 0.5, 0.5)),
-    Some(("FrozenOcean", 0.0, 0.5)),
-    Some(("FrozenRiver", 0.0, 0.5)),
-    Some(("Ice Plains", 0.0, 0.5)),
-    Some(("Ice Mountains", 0.0, 0.5)),
-    Some(("MushroomIsland", 0.9, 1.0)),
-    Some(("MushroomIslandShore", 0.9, 1.0)),
-    Some(("Beach", 0.8, 0.4)),
-    Some(("DesertHills", 2.0, 0.0)),
-    Some(("ForestHills", 0.7, 0.8)),
-    Some(("TaigaHills", 0.3, 0.8)),
-    Some(("Extreme Hills Edge", 0.2, 0.3)),
-    Some(("Jungle", 0.9, 0.9)),
-    Some(("JungleHills", 0.9, 0.9)),
-    Some(("JungleEdge", 0.9, 0.8)),
-    Some(("Deep Ocean", 0.5, 0.5)),
-    Some(("Stone Beach", 0.2, 0.3)),
-    Some(("Cold Beach", 0.1, 0.3)),
-    Some(("Birch Forest", 0.6, 0.6)),
-    Some(("Birch Forest Hills", 0.6, 0.6)),
-    Some(("Roofed Forest", 0.7, 0.8)),
-    Some(("Cold Taiga", -0.5, 0.4)),
-    Some(("Cold Taiga Hills", -0.5, 0.4)),
-    Some(("Mega Taiga", 0.3, 0.8)),
-    Some(("Mega Taiga Hills", 0.3, 0.8)),
-    Some(("Extreme Hills+", 0.2, 0.3)),
-    Some(("Savanna", 1.2, 0.0)),
-    Some(("Savanna Plateau", 1.0, 0.0)),
-    Some(("Mesa", 2.0, 0.0)),
-    Some(("Mesa Plateau F", 2.0, 0.0)),
-    Some(("Mesa Plateau", 2.0, 0.0)),
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    Some(("Sunflower Plains", 0.8, 0.4)),
-    Some(("Desert M", 2.0, 0.0)),
-    Some(("Extreme Hills M", 0.2, 0.3)),
-    Some(("Flower Forest", 0.7, 0.8)),
-    Some(("Taiga M", 0.3, 0.8)),
-    Some(("Swampland M", 0.8, 0.9)),
-    None,
-    None,
-    None,
-    None,
-    None,
-    Some(("Ice Plains Spikes", 0.0, 0.5)),
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    Some(("Jungle M", 0.9, 0.9)),
-    None,
-    Some(("JungleEdge M", 0.9, 0.8)),
-    None,
-    None,
-    None,
-    Some(("Birch Forest M", 0.6, 0.6)),
-    Some(("Birch Forest Hills M", 0.6, 0.6)),
-    Some(("Roofed Forest M", 0.7, 0.8)),
-    Some(("Cold Taiga M", -0.5, 0.4)),
-    None,
-    Some(("Mega Spruce Taiga", 0.3, 0.8)),
-    Some(("Redwood Taiga Hills M", 0.3, 0.8)),
-    Some(("Extreme Hills+ M", 0.2, 0.3)),
-    Some(("Savanna M", 1.1, 0.0)),
-    Some(("Savanna Plateau M", 1.0, 0.0)),
-    Some(("Mesa (Bryce)", 2.0, 0.0)),
-    Some(("Mesa Plateau F M", 2.0, 0.0)),
-    Some(("Mesa Plateau M", 2.0, 0.0)),
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-];
-
-// (id, name, variant)
-pub static BLOCK_STATES: &[(u16, &str, &str)] = &[
-    // 0000: "minecraft:air" (null)
-    (0x0010, "stone", "normal"),
-    (0x0011, "granite", "normal"),
-    (0x0012, "smooth_granite", "normal"),
-    (0x0013, "diorite", "normal"),
-    (0x0014, "smooth_diorite", "normal"),
-    (0x0015, "andesite", "normal"),
-    (0x0016, "smooth_andesite", "normal"),
-    (0x0020, "grass", "snowy=false"),
-    (0x0030, "dirt", "normal"),
-    (0x0031, "coarse_dirt", "normal"),
-    (0x0032, "podzol", "snowy=false"),
-    (0x0040, "cobblestone", "normal"),
-    (0x0050, "oak_planks", "normal"),
-    (0x0051, "spruce_planks", "normal"),
-    (0x0052, "birch_planks", "normal"),
-    (0x0053, "jungle_planks", "normal"),
-    (0x0054, "acacia_planks", "normal"),
-    (0x0055, "dark_oak_planks", "normal"),
-    (0x0060, "oak_sapling", "stage=0"),
-    (0x0061, "spruce_sapling", "stage=0"),
-    (0x0062, "birch_sapling", "stage=0"),
-    (0x0063, "jungle_sapling", "stage=0"),
-    (0x0064, "acacia_sapling", "stage=0"),
-    (0x0065, "dark_oak_sapling", "stage=0"),
-    (0x0068, "oak_sapling", "stage=1"),
-    (0x0069, "spruce_sapling", "stage=1"),
-    (0x006a, "birch_sapling", "stage=1"),
-    (0x006b, "jungle_sapling", "stage=1"),
-    (0x006c, "acacia_sapling", "stage=1"),
-    (0x006d, "dark_oak_sapling", "stage=1"),
-    (0x0070, "bedrock", "normal"),
-    // 0080: "minecraft:flowing_water[level=0]" (null)
-    // 0081: "minecraft:flowing_water[level=1]" (null)
-    // 0082: "minecraft:flowing_water[level=2]" (null)
-    // 0083: "minecraft:flowing_water[level=3]" (null)
-    // 0084: "minecraft:flowing_water[level=4]" (null)
-    // 0085: "minecraft:flowing_water[level=5]" (null)
-    // 0086: "minecraft:flowing_water[level=6]" (null)
-    // 0087: "minecraft:flowing_water[level=7]" (null)
-    // 0088: "minecraft:flowing_water[level=8]" (null)
-    // 0089: "minecraft:flowing_water[level=9]" (null)
-    // 008a: "minecraft:flowing_water[level=10]" (null)
-    // 008b: "minecraft:flowing_water[level=11]" (null)
-    // 008c: "minecraft:flowing_water[level=12]" (null)
-    // 008d: "minecraft:flowing_water[level=13]" (null)
-    // 008e: "minecraft:flowing_water[level=14]" (null)
-    // 008f: "minecraft:flowing_water[level=15]" (null)
-    // 0090: "minecraft:water[level=0]" (null)
-    // 0091: "minecraft:water[level=1]" (null)
-    // 0092: "minecraft:water[level=2]" (null)
-    // 0093: "minecraft:water[level=3]" (null)
-    // 0094: "minecraft:water[level=4]" (null)
-    // 0095: "minecraft:water[level=5]" (null)
-    // 0096: "minecraft:water[level=6]" (null)
-    // 0097: "minecraft:water[level=7]" (null)
-    // 0098: "minecraft:water[level=8]" (null)
-    // 0099: "minecraft:water[level=9]" (null)
-    // 009a: "minecraft:water[level=10]" (null)
-    // 009b: "minecraft:water[level=11]" (null)
-    // 009c: "minecraft:water[level=12]" (null)
-    // 009d: "minecraft:water[level=13]" (null)
-    // 009e: "minecraft:water[level=14]" (null)
-    // 009f: "minecraft:water[level=15]" (null)
-    // 00a0: "minecraft:flowing_lava[level=0]" (null)
-    // 00a1: "minecraft:flowing_lava[level=1]" (null)
-    // 00a2: "minecraft:flowing_lava[level=2]" (null)
-    // 00a3: "minecraft:flowing_lava[level=3]" (null)
-    // 00a4: "minecraft:flowing_lava[level=4]" (null)
-    // 00a5: "minecraft:flowing_lava[level=5]" (null)
-    // 00a6: "minecraft:flowing_lava[level=6]" (null)
-    // 00a7: "minecraft:flowing_lava[level=7]" (null)
-    // 00a8: "minecraft:flowing_lava[level=8]" (null)
-    // 00a9: "minecraft:flowing_lava[level=9]" (null)
-    // 00aa: "minecraft:flowing_lava[level=10]" (null)
-    // 00ab: "minecraft:flowing_lava[level=11]" (null)
-    // 00ac: "minecraft:flowing_lava[level=12]" (null)
-    // 00ad: "minecraft:flowing_lava[level=13]" (null)
-    // 00ae: "minecraft:flowing_lava[level=14]" (null)
-    // 00af: "minecraft:flowing_lava[level=15]" (null)
-    // 00b0: "minecraft:lava[level=0]" (null)
-    // 00b1: "minecraft:lava[level=1]" (null)
-    // 00b2: "minecraft:lava[level=2]" (null)
-    // 00b3: "minecraft:lava[level=3]" (null)
-    // 00b4: "minecraft:lava[level=4]" (null)
-    // 00b5: "minecraft:lava[level=5]" (null)
-    // 00b6: "minecraft:lava[level=6]" (null)
-    // 00b7: "minecraft:lava[level=7]" (null)
-    // 00b8: "minecraft:lava[level=8]" (null)
-    // 00b9: "minecraft:lava[level=9]" (null)
-    // 00ba: "minecraft:lava[level=10]" (null)
-    // 00bb: "minecraft:lava[level=11]" (null)
-    // 00bc: "minecraft:lava[level=12]" (null)
-    // 00bd: "minecraft:lava[level=13]" (null)
-    // 00be: "minecraft:lava[level=14]" (null)
-    // 00bf: "minecraft:lava[level=15]" (null)
-    (0x00c0, "sand", "normal"),
-    (0x00c1, "red_sand", "normal"),
-    (0x00d0, "gravel", "normal"),
-    (0x00e0, "gold_ore", "normal"),
-    (0x00f0, "iron_ore", "normal"),
-    (0x0100, "coal_ore", "normal"),
-    (0x0110, "oak_log", "axis=y"),
-    (0x0111, "spruce_log", "axis=y"),
-    (0x0112, "birch_log", "axis=y"),
-    (0x0113, "jungle_log", "axis=y"),
-    (0x0114, "oak_log", "axis=x"),
-    (0x0115, "spruce_log", "axis=x"),
-    (0x0116, "birch_log", "axis=x"),
-    (0x0117, "jungle_log", "axis=x"),
-    (0x0118, "oak_log", "axis=z"),
-    (0x0119, "spruce_log", "axis=z"),
-    (0x011a, "birch_log", "axis=z"),
-    (0x011b, "jungle_log", "axis=z"),
-    (0x011c, "oak_log", "axis=none"),
-    (0x011d, "spruce_log", "axis=none"),
-    (0x011e, "birch_log", "axis=none"),
-    (0x011f, "jungle_log", "axis=none"),
-    (0x0120, "oak_leaves", "normal"),
-    (0x0121, "spruce_leaves", "normal"),
-    (0x0122, "birch_leaves", "normal"),
-    (0x0123, "jungle_leaves", "normal"),
-    (0x0124, "oak_leaves", "normal"),
-    (0x0125, "spruce_leaves", "normal"),
-    (0x0126, "birch_leaves", "normal"),
-    (0x0127, "jungle_leaves", "normal"),
-    (0x0128, "oak_leaves", "normal"),
-    (0x0129, "spruce_leaves", "normal"),
-    (0x012a, "birch_leaves", "normal"),
-    (0x012b, "jungle_leaves", "normal"),
-    (0x012c, "oak_leaves", "normal"),
-    (0x012d, "spruce_leaves", "normal"),
-    (0x012e, "birch_leaves", "normal"),
-    (0x012f, "jungle_leaves", "normal"),
-    (0x0130, "sponge", "wet=false"),
-    (0x0131, "sponge", "wet=true"),
-    (0x0140, "glass", "normal"),
-    (0x0150, "lapis_ore", "normal"),
-    (0x0160, "lapis_block", "normal"),
-    (0x0170, "dispenser", "facing=down"),
-    (0x0171, "dispenser", "facing=up"),
-    (0x0172, "dispenser", "facing=north"),
-    (0x0173, "dispenser", "facing=south"),
-    (0x0174, "dispenser", "facing=west"),
-    (0x0175, "dispenser", "facing=east"),
-    (0x0178, "dispenser", "facing=down"),
-    (0x0179, "dispenser", "facing=up"),
-    (0x017a, "dispenser", "facing=north"),
-    (0x017b, "dispenser", "facing=south"),
-    (0x017c, "dispenser", "facing=west"),
-    (0x017d, "dispenser", "facing=east"),
-    (0x0180, "sandstone", "normal"),
-    (0x0181, "chiseled_sandstone", "normal"),
-    (0x0182, "smooth_sandstone", "normal"),
-    (0x0190, "noteblock", "normal"),
-    (0x01a0, "bed", "facing=south,part=foot"),
-    (0x01a1, "bed", "facing=west,part=foot"),
-    (0x01a2, "bed", "facing=north,part=foot"),
-    (0x01a3, "bed", "facing=east,part=foot"),
-    (0x01a8, "bed", "facing=south,part=head"),
-    (0x01a9, "bed", "facing=west,part=head"),
-    (0x01aa, "bed", "facing=north,part=head"),
-    (0x01ab, "bed", "facing=east,part=head"),
-    (0x01ac, "bed", "facing=south,part=head"),
-    (0x01ad, "bed", "facing=west,part=head"),
-    (0x01ae, "bed", "facing=north,part=head"),
-    (0x01af, "bed", "facing=east,part=head"),
-    (0x01b0, "golden_rail", "powered=false,shape=north_south"),
-    (0x01b1, "golden_rail", "powered=false,shape=east_west"),
-    (0x01b2, "golden_rail", "powered=false,shape=ascending_east"),
-    (0x01b3, "golden_rail", "powered=false,shape=ascending_west"),
-    (0x01b4, "golden_rail", "powered=false,shape=ascending_north"),
-    (0x01b5, "golden_rail", "powered=false,shape=ascending_south"),
-    (0x01b8, "golden_rail", "powered=true,shape=north_south"),
-    (0x01b9, "golden_rail", "powered=true,shape=east_west"),
-    (0x01ba, "golden_rail", "powered=true,shape=ascending_east"),
-    (0x01bb, "golden_rail", "powered=true,shape=ascending_west"),
-    (0x01bc, "golden_rail", "powered=true,shape=ascending_north"),
-    (0x01bd, "golden_rail", "powered=true,shape=ascending_south"),
-    (0x01c0, "detector_rail", "powered=false,shape=north_south"),
-    (0x01c1, "detector_rail", "powered=false,shape=east_west"),
-    (
-        0x01c2,
-        "detector_rail",
-        "powered=false,shape=ascending_east",
-    ),
-    (
-        0x01c3,
-        "detector_rail",
-        "powered=false,shape=ascending_west",
-    ),
-    (
-        0x01c4,
-        "detector_rail",
-        "powered=false,shape=ascending_north",
-    ),
-    (
-        0x01c5,
-        "detector_rail",
-        "powered=false,shape=ascending_south",
-    ),
-    (0x01c8, "detector_rail", "powered=true,shape=north_south"),
-    (0x01c9, "detector_rail", "powered=true,shape=east_west"),
-    (0x01ca, "detector_rail", "powered=true,shape=ascending_east"),
-    (0x01cb, "detector_rail", "powered=true,shape=ascending_west"),
-    (
-        0x01cc,
-        "detector_rail",
-        "powered=true,shape=ascending_north",
-    ),
-    (
-        0x01cd,
-        "detector_rail",
-        "powered=true,shape=ascending_south",
-    ),
-    (0x01d0, "sticky_piston", "extended=false,facing=down"),
-    (0x01d1, "sticky_piston", "extended=false,facing=up"),
-    (0x01d2, "sticky_piston", "extended=false,facing=north"),
-    (0x01d3, "sticky_piston", "extended=false,facing=south"),
-    (0x01d4, "sticky_piston", "extended=false,facing=west"),
-    (0x01d5, "sticky_piston", "extended=false,facing=east"),
-    (0x01d8, "sticky_piston", "extended=true,facing=down"),
-    (0x01d9, "sticky_piston", "extended=true,facing=up"),
-    (0x01da, "sticky_piston", "extended=true,facing=north"),
-    (0x01db, "sticky_piston", "extended=true,facing=south"),
-    (0x01dc, "sticky_piston", "extended=true,facing=west"),
-    (0x01dd, "sticky_piston", "extended=true,facing=east"),
-    (0x01e0, "web", "normal"),
-    (0x01f0, "dead_bush", "normal"),
-    (0x01f1, "tall_grass", "normal"),
-    (0x01f2, "fern", "normal"),
-    (0x0200, "dead_bush", "normal"),
-    (0x0210, "piston", "extended=false,facing=down"),
-    (0x0211, "piston", "extended=false,facing=up"),
-    (0x0212, "piston", "extended=false,facing=north"),
-    (0x0213, "piston", "extended=false,facing=south"),
-    (0x0214, "piston", "extended=false,facing=west"),
-    (0x0215, "piston", "extended=false,facing=east"),
-    (0x0218, "piston", "extended=true,facing=down"),
-    (0x0219, "piston", "extended=true,facing=up"),
-    (0x021a, "piston", "extended=true,facing=north"),
-    (0x021b, "piston", "extended=true,facing=south"),
-    (0x021c, "piston", "extended=true,facing=west"),
-    (0x021d, "piston", "extended=true,facing=east"),
-    (0x0220, "piston_head", "facing=down,short=false,type=normal"),
-    (0x0221, "piston_head", "facing=up,short=false,type=normal"),
-    (
-        0x0222,
-        "piston_head",
-        "facing=north,short=false,type=normal",
-    ),
-    (
-        0x0223,
-        "piston_head",
-        "facing=south,short=false,type=normal",
-    ),
-    (0x0224, "piston_head", "facing=west,short=false,type=normal"),
-    (0x0225, "piston_head", "facing=east,short=false,type=normal"),
-    (0x0228, "piston_head", "facing=down,short=false,type=sticky"),
-    (0x0229, "piston_head", "facing=up,short=false,type=sticky"),
-    (
-        0x022a,
-        "piston_head",
-        "facing=north,short=false,type=sticky",
-    ),
-    (
-        0x022b,
-        "piston_head",
-        "facing=south,short=false,type=sticky",
-    ),
-    (0x022c, "piston_head", "facing=west,short=false,type=sticky"),
-    (0x022d, "piston_head", "facing=east,short=false,type=sticky"),
-    (0x0230, "white_wool", "normal"),
-    (0x0231, "orange_wool", "normal"),
-    (0x0232, "magenta_wool", "normal"),
-    (0x0233, "light_blue_wool", "normal"),
-    (0x0234, "yellow_wool", "normal"),
-    (0x0235, "lime_wool", "normal"),
-    (0x0236, "pink_wool", "normal"),
-    (0x0237, "gray_wool", "normal"),
-    (0x0238, "silver_wool", "normal"),
-    (0x0239, "cyan_wool", "normal"),
-    (0x023a, "purple_wool", "normal"),
-    (0x023b, "blue_wool", "normal"),
-    (0x023c, "brown_wool", "normal"),
-    (0x023d, "green_wool", "normal"),
-    (0x023e, "red_wool", "normal"),
-    (0x023f, "black_wool", "normal"),
-    // 0240: "minecraft:piston_extension[facing=down,type=normal]" (null)
-    // 0241: "minecraft:piston_extension[facing=up,type=normal]" (null)
-    // 0242: "minecraft:piston_extension[facing=north,type=normal]" (null)
-    // 0243: "minecraft:piston_extension[facing=south,type=normal]" (null)
-    // 0244: "minecraft:piston_extension[facing=west,type=normal]" (null)
-    // 0245: "minecraft:piston_extension[facing=east,type=normal]" (null)
-    // 0248: "minecraft:piston_extension[facing=down,type=sticky]" (null)
-    // 0249: "minecraft:piston_extension[facing=up,type=sticky]" (null)
-    // 024a: "minecraft:piston_extension[facing=north,type=sticky]" (null)
-    // 024b: "minecraft:piston_extension[facing=south,type=sticky]" (null)
-    // 024c: "minecraft:piston_extension[facing=west,type=sticky]" (null)
-    // 024d: "minecraft:piston_extension[facing=east,type=sticky]" (null)
-    (0x0250, "dandelion", "normal"),
-    (0x0260, "poppy", "normal"),
-    (0x0261, "blue_orchid", "normal"),
-    (0x0262, "allium", "normal"),
-    (0x0263, "houstonia", "normal"),
-    (0x0264, "red_tulip", "normal"),
-    (0x0265, "orange_tulip", "normal"),
-    (0x0266, "white_tulip", "normal"),
-    (0x0267, "pink_tulip", "normal"),
-    (0x0268, "oxeye_daisy", "normal"),
-    (0x0270, "brown_mushroom", "normal"),
-    (0x0280, "red_mushroom", "normal"),
-    (0x0290, "gold_block", "normal"),
-    (0x02a0, "iron_block", "normal"),
-    (0x02b0, "stone_double_slab", "normal"),
-    (0x02b1, "sandstone_double_slab", "normal"),
-    (0x02b2, "wood_old_double_slab", "normal"),
-    (0x02b3, "cobblestone_double_slab", "normal"),
-    (0x02b4, "brick_double_slab", "normal"),
-    (0x02b5, "stone_brick_double_slab", "normal"),
-    (0x02b6, "nether_brick_double_slab", "normal"),
-    (0x02b7, "quartz_double_slab", "normal"),
-    (0x02b8, "stone_double_slab", "all"),
-    (0x02b9, "sandstone_double_slab", "all"),
-    (0x02ba, "wood_old_double_slab", "all"),
-    (0x02bb, "cobblestone_double_slab", "all"),
-    (0x02bc, "brick_double_slab", "all"),
-    (0x02bd, "stone_brick_double_slab", "all"),
-    (0x02be, "nether_brick_double_slab", "all"),
-    (0x02bf, "quartz_double_slab", "all"),
-    (0x02c0, "stone_slab", "half=bottom"),
-    (0x02c1, "sandstone_slab", "half=bottom"),
-    (0x02c2, "wood_old_slab", "half=bottom"),
-    (0x02c3, "cobblestone_slab", "half=bottom"),
-    (0x02c4, "brick_slab", "half=bottom"),
-    (0x02c5, "stone_brick_slab", "half=bottom"),
-    (0x02c6, "nether_brick_slab", "half=bottom"),
-    (0x02c7, "quartz_slab", "half=bottom"),
-    (0x02c8, "stone_slab", "half=top"),
-    (0x02c9, "sandstone_slab", "half=top"),
-    (0x02ca, "wood_old_slab", "half=top"),
-    (0x02cb, "cobblestone_slab", "half=top"),
-    (0x02cc, "brick_slab", "half=top"),
-    (0x02cd, "stone_brick_slab", "half=top"),
-    (0x02ce, "nether_brick_slab", "half=top"),
-    (0x02cf, "quartz_slab", "half=top"),
-    (0x02d0, "brick_block", "normal"),
-    (0x02e0, "tnt", "normal"),
-    (0x02e1, "tnt", "normal"),
-    (0x02f0, "bookshelf", "normal"),
-    (0x0300, "mossy_cobblestone", "normal"),
-    (0x0310, "obsidian", "normal"),
-    (0x0321, "torch", "facing=east"),
-    (0x0322, "torch", "facing=west"),
-    (0x0323, "torch", "facing=south"),
-    (0x0324, "torch", "facing=north"),
-    (0x0325, "torch", "facing=up"),
-    (
-        0x0330,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x0331,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x0332,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x0333,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x0334,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x0335,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x0336,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x0337,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x0338,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x0339,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x033a,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x033b,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x033c,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x033d,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x033e,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (
-        0x033f,
-        "fire",
-        "alt=false,east=false,flip=false,north=false,south=false,upper=2,west=false",
-    ),
-    (0x0340, "mob_spawner", "normal"),
-    (
-        0x0350,
-        "oak_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0351,
-        "oak_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0352,
-        "oak_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0353,
-        "oak_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0354,
-        "oak_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0355,
-        "oak_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0356,
-        "oak_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0357,
-        "oak_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    // 0362: "minecraft:chest[facing=north]" (null)
-    // 0363: "minecraft:chest[facing=south]" (null)
-    // 0364: "minecraft:chest[facing=west]" (null)
-    // 0365: "minecraft:chest[facing=east]" (null)
-    (
-        0x0370,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x0371,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x0372,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x0373,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x0374,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x0375,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x0376,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x0377,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x0378,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x0379,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x037a,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x037b,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x037c,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x037d,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x037e,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (
-        0x037f,
-        "redstone_wire",
-        "east=none,north=none,south=none,west=none",
-    ),
-    (0x0380, "diamond_ore", "normal"),
-    (0x0390, "diamond_block", "normal"),
-    (0x03a0, "crafting_table", "normal"),
-    (0x03b0, "wheat", "age=0"),
-    (0x03b1, "wheat", "age=1"),
-    (0x03b2, "wheat", "age=2"),
-    (0x03b3, "wheat", "age=3"),
-    (0x03b4, "wheat", "age=4"),
-    (0x03b5, "wheat", "age=5"),
-    (0x03b6, "wheat", "age=6"),
-    (0x03b7, "wheat", "age=7"),
-    (0x03c0, "farmland", "moisture=0"),
-    (0x03c1, "farmland", "moisture=1"),
-    (0x03c2, "farmland", "moisture=2"),
-    (0x03c3, "farmland", "moisture=3"),
-    (0x03c4, "farmland", "moisture=4"),
-    (0x03c5, "farmland", "moisture=5"),
-    (0x03c6, "farmland", "moisture=6"),
-    (0x03c7, "farmland", "moisture=7"),
-    (0x03d2, "furnace", "facing=north"),
-    (0x03d3, "furnace", "facing=south"),
-    (0x03d4, "furnace", "facing=west"),
-    (0x03d5, "furnace", "facing=east"),
-    (0x03e2, "lit_furnace", "facing=north"),
-    (0x03e3, "lit_furnace", "facing=south"),
-    (0x03e4, "lit_furnace", "facing=west"),
-    (0x03e5, "lit_furnace", "facing=east"),
-    // 03f0: "minecraft:standing_sign[rotation=0]" (null)
-    // 03f1: "minecraft:standing_sign[rotation=1]" (null)
-    // 03f2: "minecraft:standing_sign[rotation=2]" (null)
-    // 03f3: "minecraft:standing_sign[rotation=3]" (null)
-    // 03f4: "minecraft:standing_sign[rotation=4]" (null)
-    // 03f5: "minecraft:standing_sign[rotation=5]" (null)
-    // 03f6: "minecraft:standing_sign[rotation=6]" (null)
-    // 03f7: "minecraft:standing_sign[rotation=7]" (null)
-    // 03f8: "minecraft:standing_sign[rotation=8]" (null)
-    // 03f9: "minecraft:standing_sign[rotation=9]" (null)
-    // 03fa: "minecraft:standing_sign[rotation=10]" (null)
-    // 03fb: "minecraft:standing_sign[rotation=11]" (null)
-    // 03fc: "minecraft:standing_sign[rotation=12]" (null)
-    // 03fd: "minecraft:standing_sign[rotation=13]" (null)
-    // 03fe: "minecraft:standing_sign[rotation=14]" (null)
-    // 03ff: "minecraft:standing_sign[rotation=15]" (null)
-    (
-        0x0400,
-        "wooden_door",
-        "facing=east,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0401,
-        "wooden_door",
-        "facing=south,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0402,
-        "wooden_door",
-        "facing=west,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0403,
-        "wooden_door",
-        "facing=north,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0404,
-        "wooden_door",
-        "facing=east,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0405,
-        "wooden_door",
-        "facing=south,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0406,
-        "wooden_door",
-        "facing=west,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0407,
-        "wooden_door",
-        "facing=north,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0408,
-        "wooden_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0409,
-        "wooden_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x040a,
-        "wooden_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x040b,
-        "wooden_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (0x0412, "ladder", "facing=north"),
-    (0x0413, "ladder", "facing=south"),
-    (0x0414, "ladder", "facing=west"),
-    (0x0415, "ladder", "facing=east"),
-    (0x0420, "rail", "shape=north_south"),
-    (0x0421, "rail", "shape=east_west"),
-    (0x0422, "rail", "shape=ascending_east"),
-    (0x0423, "rail", "shape=ascending_west"),
-    (0x0424, "rail", "shape=ascending_north"),
-    (0x0425, "rail", "shape=ascending_south"),
-    (0x0426, "rail", "shape=south_east"),
-    (0x0427, "rail", "shape=south_west"),
-    (0x0428, "rail", "shape=north_west"),
-    (0x0429, "rail", "shape=north_east"),
-    (
-        0x0430,
-        "stone_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0431,
-        "stone_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0432,
-        "stone_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0433,
-        "stone_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0434,
-        "stone_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0435,
-        "stone_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0436,
-        "stone_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0437,
-        "stone_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    // 0442: "minecraft:wall_sign[facing=north]" (null)
-    // 0443: "minecraft:wall_sign[facing=south]" (null)
-    // 0444: "minecraft:wall_sign[facing=west]" (null)
-    // 0445: "minecraft:wall_sign[facing=east]" (null)
-    (0x0450, "lever", "facing=down_x,powered=false"),
-    (0x0451, "lever", "facing=east,powered=false"),
-    (0x0452, "lever", "facing=west,powered=false"),
-    (0x0453, "lever", "facing=south,powered=false"),
-    (0x0454, "lever", "facing=north,powered=false"),
-    (0x0455, "lever", "facing=up_z,powered=false"),
-    (0x0456, "lever", "facing=up_x,powered=false"),
-    (0x0457, "lever", "facing=down_z,powered=false"),
-    (0x0458, "lever", "facing=down_x,powered=true"),
-    (0x0459, "lever", "facing=east,powered=true"),
-    (0x045a, "lever", "facing=west,powered=true"),
-    (0x045b, "lever", "facing=south,powered=true"),
-    (0x045c, "lever", "facing=north,powered=true"),
-    (0x045d, "lever", "facing=up_z,powered=true"),
-    (0x045e, "lever", "facing=up_x,powered=true"),
-    (0x045f, "lever", "facing=down_z,powered=true"),
-    (0x0460, "stone_pressure_plate", "powered=false"),
-    (0x0461, "stone_pressure_plate", "powered=true"),
-    (
-        0x0470,
-        "iron_door",
-        "facing=east,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0471,
-        "iron_door",
-        "facing=south,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0472,
-        "iron_door",
-        "facing=west,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0473,
-        "iron_door",
-        "facing=north,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0474,
-        "iron_door",
-        "facing=east,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0475,
-        "iron_door",
-        "facing=south,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0476,
-        "iron_door",
-        "facing=west,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0477,
-        "iron_door",
-        "facing=north,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0478,
-        "iron_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0479,
-        "iron_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x047a,
-        "iron_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x047b,
-        "iron_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (0x0480, "wooden_pressure_plate", "powered=false"),
-    (0x0481, "wooden_pressure_plate", "powered=true"),
-    (0x0490, "redstone_ore", "normal"),
-    (0x04a0, "lit_redstone_ore", "normal"),
-    (0x04b1, "unlit_redstone_torch", "facing=east"),
-    (0x04b2, "unlit_redstone_torch", "facing=west"),
-    (0x04b3, "unlit_redstone_torch", "facing=south"),
-    (0x04b4, "unlit_redstone_torch", "facing=north"),
-    (0x04b5, "unlit_redstone_torch", "facing=up"),
-    (0x04c1, "redstone_torch", "facing=east"),
-    (0x04c2, "redstone_torch", "facing=west"),
-    (0x04c3, "redstone_torch", "facing=south"),
-    (0x04c4, "redstone_torch", "facing=north"),
-    (0x04c5, "redstone_torch", "facing=up"),
-    (0x04d0, "stone_button", "facing=down,powered=false"),
-    (0x04d1, "stone_button", "facing=east,powered=false"),
-    (0x04d2, "stone_button", "facing=west,powered=false"),
-    (0x04d3, "stone_button", "facing=south,powered=false"),
-    (0x04d4, "stone_button", "facing=north,powered=false"),
-    (0x04d5, "stone_button", "facing=up,powered=false"),
-    (0x04d8, "stone_button", "facing=down,powered=true"),
-    (0x04d9, "stone_button", "facing=east,powered=true"),
-    (0x04da, "stone_button", "facing=west,powered=true"),
-    (0x04db, "stone_button", "facing=south,powered=true"),
-    (0x04dc, "stone_button", "facing=north,powered=true"),
-    (0x04dd, "stone_button", "facing=up,powered=true"),
-    (0x04e0, "snow_layer", "layers=1"),
-    (0x04e1, "snow_layer", "layers=2"),
-    (0x04e2, "snow_layer", "layers=3"),
-    (0x04e3, "snow_layer", "layers=4"),
-    (0x04e4, "snow_layer", "layers=5"),
-    (0x04e5, "snow_layer", "layers=6"),
-    (0x04e6, "snow_layer", "layers=7"),
-    (0x04e7, "snow_layer", "layers=8"),
-    (0x04f0, "ice", "normal"),
-    (0x0500, "snow", "normal"),
-    (0x0510, "cactus", "normal"),
-    (0x0511, "cactus", "normal"),
-    (0x0512, "cactus", "normal"),
-    (0x0513, "cactus", "normal"),
-    (0x0514, "cactus", "normal"),
-    (0x0515, "cactus", "normal"),
-    (0x0516, "cactus", "normal"),
-    (0x0517, "cactus", "normal"),
-    (0x0518, "cactus", "normal"),
-    (0x0519, "cactus", "normal"),
-    (0x051a, "cactus", "normal"),
-    (0x051b, "cactus", "normal"),
-    (0x051c, "cactus", "normal"),
-    (0x051d, "cactus", "normal"),
-    (0x051e, "cactus", "normal"),
-    (0x051f, "cactus", "normal"),
-    (0x0520, "clay", "normal"),
-    (0x0530, "reeds", "normal"),
-    (0x0531, "reeds", "normal"),
-    (0x0532, "reeds", "normal"),
-    (0x0533, "reeds", "normal"),
-    (0x0534, "reeds", "normal"),
-    (0x0535, "reeds", "normal"),
-    (0x0536, "reeds", "normal"),
-    (0x0537, "reeds", "normal"),
-    (0x0538, "reeds", "normal"),
-    (0x0539, "reeds", "normal"),
-    (0x053a, "reeds", "normal"),
-    (0x053b, "reeds", "normal"),
-    (0x053c, "reeds", "normal"),
-    (0x053d, "reeds", "normal"),
-    (0x053e, "reeds", "normal"),
-    (0x053f, "reeds", "normal"),
-    (0x0540, "jukebox", "normal"),
-    (0x0541, "jukebox", "normal"),
-    (
-        0x0550,
-        "fence",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (0x0560, "pumpkin", "facing=south"),
-    (0x0561, "pumpkin", "facing=west"),
-    (0x0562, "pumpkin", "facing=north"),
-    (0x0563, "pumpkin", "facing=east"),
-    (0x0570, "netherrack", "normal"),
-    (0x0580, "soul_sand", "normal"),
-    (0x0590, "glowstone", "normal"),
-    (0x05a1, "portal", "axis=x"),
-    (0x05a2, "portal", "axis=z"),
-    (0x05b0, "lit_pumpkin", "facing=south"),
-    (0x05b1, "lit_pumpkin", "facing=west"),
-    (0x05b2, "lit_pumpkin", "facing=north"),
-    (0x05b3, "lit_pumpkin", "facing=east"),
-    (0x05c0, "cake", "bites=0"),
-    (0x05c1, "cake", "bites=1"),
-    (0x05c2, "cake", "bites=2"),
-    (0x05c3, "cake", "bites=3"),
-    (0x05c4, "cake", "bites=4"),
-    (0x05c5, "cake", "bites=5"),
-    (0x05c6, "cake", "bites=6"),
-    (
-        0x05d0,
-        "unpowered_repeater",
-        "delay=1,facing=south,locked=false",
-    ),
-    (
-        0x05d1,
-        "unpowered_repeater",
-        "delay=1,facing=west,locked=false",
-    ),
-    (
-        0x05d2,
-        "unpowered_repeater",
-        "delay=1,facing=north,locked=false",
-    ),
-    (
-        0x05d3,
-        "unpowered_repeater",
-        "delay=1,facing=east,locked=false",
-    ),
-    (
-        0x05d4,
-        "unpowered_repeater",
-        "delay=2,facing=south,locked=false",
-    ),
-    (
-        0x05d5,
-        "unpowered_repeater",
-        "delay=2,facing=west,locked=false",
-    ),
-    (
-        0x05d6,
-        "unpowered_repeater",
-        "delay=2,facing=north,locked=false",
-    ),
-    (
-        0x05d7,
-        "unpowered_repeater",
-        "delay=2,facing=east,locked=false",
-    ),
-    (
-        0x05d8,
-        "unpowered_repeater",
-        "delay=3,facing=south,locked=false",
-    ),
-    (
-        0x05d9,
-        "unpowered_repeater",
-        "delay=3,facing=west,locked=false",
-    ),
-    (
-        0x05da,
-        "unpowered_repeater",
-        "delay=3,facing=north,locked=false",
-    ),
-    (
-        0x05db,
-        "unpowered_repeater",
-        "delay=3,facing=east,locked=false",
-    ),
-    (
-        0x05dc,
-        "unpowered_repeater",
-        "delay=4,facing=south,locked=false",
-    ),
-    (
-        0x05dd,
-        "unpowered_repeater",
-        "delay=4,facing=west,locked=false",
-    ),
-    (
-        0x05de,
-        "unpowered_repeater",
-        "delay=4,facing=north,locked=false",
-    ),
-    (
-        0x05df,
-        "unpowered_repeater",
-        "delay=4,facing=east,locked=false",
-    ),
-    (
-        0x05e0,
-        "powered_repeater",
-        "delay=1,facing=south,locked=false",
-    ),
-    (
-        0x05e1,
-        "powered_repeater",
-        "delay=1,facing=west,locked=false",
-    ),
-    (
-        0x05e2,
-        "powered_repeater",
-        "delay=1,facing=north,locked=false",
-    ),
-    (
-        0x05e3,
-        "powered_repeater",
-        "delay=1,facing=east,locked=false",
-    ),
-    (
-        0x05e4,
-        "powered_repeater",
-        "delay=2,facing=south,locked=false",
-    ),
-    (
-        0x05e5,
-        "powered_repeater",
-        "delay=2,facing=west,locked=false",
-    ),
-    (
-        0x05e6,
-        "powered_repeater",
-        "delay=2,facing=north,locked=false",
-    ),
-    (
-        0x05e7,
-        "powered_repeater",
-        "delay=2,facing=east,locked=false",
-    ),
-    (
-        0x05e8,
-        "powered_repeater",
-        "delay=3,facing=south,locked=false",
-    ),
-    (
-        0x05e9,
-        "powered_repeater",
-        "delay=3,facing=west,locked=false",
-    ),
-    (
-        0x05ea,
-        "powered_repeater",
-        "delay=3,facing=north,locked=false",
-    ),
-    (
-        0x05eb,
-        "powered_repeater",
-        "delay=3,facing=east,locked=false",
-    ),
-    (
-        0x05ec,
-        "powered_repeater",
-        "delay=4,facing=south,locked=false",
-    ),
-    (
-        0x05ed,
-        "powered_repeater",
-        "delay=4,facing=west,locked=false",
-    ),
-    (
-        0x05ee,
-        "powered_repeater",
-        "delay=4,facing=north,locked=false",
-    ),
-    (
-        0x05ef,
-        "powered_repeater",
-        "delay=4,facing=east,locked=false",
-    ),
-    (0x05f0, "white_stained_glass", "normal"),
-    (0x05f1, "orange_stained_glass", "normal"),
-    (0x05f2, "magenta_stained_glass", "normal"),
-    (0x05f3, "light_blue_stained_glass", "normal"),
-    (0x05f4, "yellow_stained_glass", "normal"),
-    (0x05f5, "lime_stained_glass", "normal"),
-    (0x05f6, "pink_stained_glass", "normal"),
-    (0x05f7, "gray_stained_glass", "normal"),
-    (0x05f8, "silver_stained_glass", "normal"),
-    (0x05f9, "cyan_stained_glass", "normal"),
-    (0x05fa, "purple_stained_glass", "normal"),
-    (0x05fb, "blue_stained_glass", "normal"),
-    (0x05fc, "brown_stained_glass", "normal"),
-    (0x05fd, "green_stained_glass", "normal"),
-    (0x05fe, "red_stained_glass", "normal"),
-    (0x05ff, "black_stained_glass", "normal"),
-    (0x0600, "trapdoor", "facing=north,half=bottom,open=false"),
-    (0x0601, "trapdoor", "facing=south,half=bottom,open=false"),
-    (0x0602, "trapdoor", "facing=west,half=bottom,open=false"),
-    (0x0603, "trapdoor", "facing=east,half=bottom,open=false"),
-    (0x0604, "trapdoor", "facing=north,half=bottom,open=true"),
-    (0x0605, "trapdoor", "facing=south,half=bottom,open=true"),
-    (0x0606, "trapdoor", "facing=west,half=bottom,open=true"),
-    (0x0607, "trapdoor", "facing=east,half=bottom,open=true"),
-    (0x0608, "trapdoor", "facing=north,half=top,open=false"),
-    (0x0609, "trapdoor", "facing=south,half=top,open=false"),
-    (0x060a, "trapdoor", "facing=west,half=top,open=false"),
-    (0x060b, "trapdoor", "facing=east,half=top,open=false"),
-    (0x060c, "trapdoor", "facing=north,half=top,open=true"),
-    (0x060d, "trapdoor", "facing=south,half=top,open=true"),
-    (0x060e, "trapdoor", "facing=west,half=top,open=true"),
-    (0x060f, "trapdoor", "facing=east,half=top,open=true"),
-    (0x0610, "stone_monster_egg", "normal"),
-    (0x0611, "cobblestone_monster_egg", "normal"),
-    (0x0612, "stone_brick_monster_egg", "normal"),
-    (0x0613, "mossy_brick_monster_egg", "normal"),
-    (0x0614, "cracked_brick_monster_egg", "normal"),
-    (0x0615, "chiseled_brick_monster_egg", "normal"),
-    (0x0620, "stonebrick", "normal"),
-    (0x0621, "mossy_stonebrick", "normal"),
-    (0x0622, "cracked_stonebrick", "normal"),
-    (0x0623, "chiseled_stonebrick", "normal"),
-    (0x0630, "brown_mushroom_block", "variant=all_inside"),
-    (0x0631, "brown_mushroom_block", "variant=north_west"),
-    (0x0632, "brown_mushroom_block", "variant=north"),
-    (0x0633, "brown_mushroom_block", "variant=north_east"),
-    (0x0634, "brown_mushroom_block", "variant=west"),
-    (0x0635, "brown_mushroom_block", "variant=center"),
-    (0x0636, "brown_mushroom_block", "variant=east"),
-    (0x0637, "brown_mushroom_block", "variant=south_west"),
-    (0x0638, "brown_mushroom_block", "variant=south"),
-    (0x0639, "brown_mushroom_block", "variant=south_east"),
-    (0x063a, "brown_mushroom_block", "variant=stem"),
-    (0x063e, "brown_mushroom_block", "variant=all_outside"),
-    (0x063f, "brown_mushroom_block", "variant=all_stem"),
-    (0x0640, "red_mushroom_block", "variant=all_inside"),
-    (0x0641, "red_mushroom_block", "variant=north_west"),
-    (0x0642, "red_mushroom_block", "variant=north"),
-    (0x0643, "red_mushroom_block", "variant=north_east"),
-    (0x0644, "red_mushroom_block", "variant=west"),
-    (0x0645, "red_mushroom_block", "variant=center"),
-    (0x0646, "red_mushroom_block", "variant=east"),
-    (0x0647, "red_mushroom_block", "variant=south_west"),
-    (0x0648, "red_mushroom_block", "variant=south"),
-    (0x0649, "red_mushroom_block", "variant=south_east"),
-    (0x064a, "red_mushroom_block", "variant=stem"),
-    (0x064e, "red_mushroom_block", "variant=all_outside"),
-    (0x064f, "red_mushroom_block", "variant=all_stem"),
-    (
-        0x0650,
-        "iron_bars",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0660,
-        "glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (0x0670, "melon_block", "normal"),
-    (0x0680, "pumpkin_stem", "facing=east"),
-    (0x0681, "pumpkin_stem", "facing=east"),
-    (0x0682, "pumpkin_stem", "facing=east"),
-    (0x0683, "pumpkin_stem", "facing=east"),
-    (0x0684, "pumpkin_stem", "facing=east"),
-    (0x0685, "pumpkin_stem", "facing=east"),
-    (0x0686, "pumpkin_stem", "facing=east"),
-    (0x0687, "pumpkin_stem", "facing=east"),
-    (0x0690, "melon_stem", "facing=east"),
-    (0x0691, "melon_stem", "facing=east"),
-    (0x0692, "melon_stem", "facing=east"),
-    (0x0693, "melon_stem", "facing=east"),
-    (0x0694, "melon_stem", "facing=east"),
-    (0x0695, "melon_stem", "facing=east"),
-    (0x0696, "melon_stem", "facing=east"),
-    (0x0697, "melon_stem", "facing=east"),
-    (
-        0x06a0,
-        "vine",
-        "east=false,north=false,south=false,up=false,west=false",
-    ),
-    (
-        0x06a1,
-        "vine",
-        "east=false,north=false,south=true,up=false,west=false",
-    ),
-    (
-        0x06a2,
-        "vine",
-        "east=false,north=false,south=false,up=false,west=true",
-    ),
-    (
-        0x06a3,
-        "vine",
-        "east=false,north=false,south=true,up=false,west=true",
-    ),
-    (
-        0x06a4,
-        "vine",
-        "east=false,north=true,south=false,up=false,west=false",
-    ),
-    (
-        0x06a5,
-        "vine",
-        "east=false,north=true,south=true,up=false,west=false",
-    ),
-    (
-        0x06a6,
-        "vine",
-        "east=false,north=true,south=false,up=false,west=true",
-    ),
-    (
-        0x06a7,
-        "vine",
-        "east=false,north=true,south=true,up=false,west=true",
-    ),
-    (
-        0x06a8,
-        "vine",
-        "east=true,north=false,south=false,up=false,west=false",
-    ),
-    (
-        0x06a9,
-        "vine",
-        "east=true,north=false,south=true,up=false,west=false",
-    ),
-    (
-        0x06aa,
-        "vine",
-        "east=true,north=false,south=false,up=false,west=true",
-    ),
-    (
-        0x06ab,
-        "vine",
-        "east=true,north=false,south=true,up=false,west=true",
-    ),
-    (
-        0x06ac,
-        "vine",
-        "east=true,north=true,south=false,up=false,west=false",
-    ),
-    (
-        0x06ad,
-        "vine",
-        "east=true,north=true,south=true,up=false,west=false",
-    ),
-    (
-        0x06ae,
-        "vine",
-        "east=true,north=true,south=false,up=false,west=true",
-    ),
-    (
-        0x06af,
-        "vine",
-        "east=true,north=true,south=true,up=false,west=true",
-    ),
-    (
-        0x06b0,
-        "fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (0x06b1, "fence_gate", "facing=west,in_wall=false,open=false"),
-    (
-        0x06b2,
-        "fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (0x06b3, "fence_gate", "facing=east,in_wall=false,open=false"),
-    (0x06b4, "fence_gate", "facing=south,in_wall=false,open=true"),
-    (0x06b5, "fence_gate", "facing=west,in_wall=false,open=true"),
-    (0x06b6, "fence_gate", "facing=north,in_wall=false,open=true"),
-    (0x06b7, "fence_gate", "facing=east,in_wall=false,open=true"),
-    (
-        0x06b8,
-        "fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (0x06b9, "fence_gate", "facing=west,in_wall=false,open=false"),
-    (
-        0x06ba,
-        "fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (0x06bb, "fence_gate", "facing=east,in_wall=false,open=false"),
-    (0x06bc, "fence_gate", "facing=south,in_wall=false,open=true"),
-    (0x06bd, "fence_gate", "facing=west,in_wall=false,open=true"),
-    (0x06be, "fence_gate", "facing=north,in_wall=false,open=true"),
-    (0x06bf, "fence_gate", "facing=east,in_wall=false,open=true"),
-    (
-        0x06c0,
-        "brick_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x06c1,
-        "brick_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x06c2,
-        "brick_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x06c3,
-        "brick_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x06c4,
-        "brick_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x06c5,
-        "brick_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x06c6,
-        "brick_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x06c7,
-        "brick_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (
-        0x06d0,
-        "stone_brick_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x06d1,
-        "stone_brick_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x06d2,
-        "stone_brick_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x06d3,
-        "stone_brick_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x06d4,
-        "stone_brick_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x06d5,
-        "stone_brick_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x06d6,
-        "stone_brick_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x06d7,
-        "stone_brick_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (0x06e0, "mycelium", "snowy=false"),
-    (0x06f0, "waterlily", "normal"),
-    (0x0700, "nether_brick", "normal"),
-    (
-        0x0710,
-        "nether_brick_fence",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0720,
-        "nether_brick_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0721,
-        "nether_brick_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0722,
-        "nether_brick_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0723,
-        "nether_brick_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0724,
-        "nether_brick_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0725,
-        "nether_brick_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0726,
-        "nether_brick_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0727,
-        "nether_brick_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (0x0730, "nether_wart", "age=0"),
-    (0x0731, "nether_wart", "age=1"),
-    (0x0732, "nether_wart", "age=2"),
-    (0x0733, "nether_wart", "age=3"),
-    (0x0740, "enchanting_table", "normal"),
-    (
-        0x0750,
-        "brewing_stand",
-        "has_bottle_0=false,has_bottle_1=false,has_bottle_2=false",
-    ),
-    (
-        0x0751,
-        "brewing_stand",
-        "has_bottle_0=true,has_bottle_1=false,has_bottle_2=false",
-    ),
-    (
-        0x0752,
-        "brewing_stand",
-        "has_bottle_0=false,has_bottle_1=true,has_bottle_2=false",
-    ),
-    (
-        0x0753,
-        "brewing_stand",
-        "has_bottle_0=true,has_bottle_1=true,has_bottle_2=false",
-    ),
-    (
-        0x0754,
-        "brewing_stand",
-        "has_bottle_0=false,has_bottle_1=false,has_bottle_2=true",
-    ),
-    (
-        0x0755,
-        "brewing_stand",
-        "has_bottle_0=true,has_bottle_1=false,has_bottle_2=true",
-    ),
-    (
-        0x0756,
-        "brewing_stand",
-        "has_bottle_0=false,has_bottle_1=true,has_bottle_2=true",
-    ),
-    (
-        0x0757,
-        "brewing_stand",
-        "has_bottle_0=true,has_bottle_1=true,has_bottle_2=true",
-    ),
-    (0x0760, "cauldron", "level=0"),
-    (0x0761, "cauldron", "level=1"),
-    (0x0762, "cauldron", "level=2"),
-    (0x0763, "cauldron", "level=3"),
-    // 0770: "minecraft:end_portal" (null)
-    (0x0780, "end_portal_frame", "eye=false,facing=south"),
-    (0x0781, "end_portal_frame", "eye=false,facing=west"),
-    (0x0782, "end_portal_frame", "eye=false,facing=north"),
-    (0x0783, "end_portal_frame", "eye=false,facing=east"),
-    (0x0784, "end_portal_frame", "eye=true,facing=south"),
-    (0x0785, "end_portal_frame", "eye=true,facing=west"),
-    (0x0786, "end_portal_frame", "eye=true,facing=north"),
-    (0x0787, "end_portal_frame", "eye=true,facing=east"),
-    (0x0790, "end_stone", "normal"),
-    (0x07a0, "dragon_egg", "normal"),
-    (0x07b0, "redstone_lamp", "normal"),
-    (0x07c0, "lit_redstone_lamp", "normal"),
-    (0x07d0, "oak_double_slab", "normal"),
-    (0x07d1, "spruce_double_slab", "normal"),
-    (0x07d2, "birch_double_slab", "normal"),
-    (0x07d3, "jungle_double_slab", "normal"),
-    (0x07d4, "acacia_double_slab", "normal"),
-    (0x07d5, "dark_oak_double_slab", "normal"),
-    (0x07e0, "oak_slab", "half=bottom"),
-    (0x07e1, "spruce_slab", "half=bottom"),
-    (0x07e2, "birch_slab", "half=bottom"),
-    (0x07e3, "jungle_slab", "half=bottom"),
-    (0x07e4, "acacia_slab", "half=bottom"),
-    (0x07e5, "dark_oak_slab", "half=bottom"),
-    (0x07e8, "oak_slab", "half=top"),
-    (0x07e9, "spruce_slab", "half=top"),
-    (0x07ea, "birch_slab", "half=top"),
-    (0x07eb, "jungle_slab", "half=top"),
-    (0x07ec, "acacia_slab", "half=top"),
-    (0x07ed, "dark_oak_slab", "half=top"),
-    (0x07f0, "cocoa", "age=0,facing=south"),
-    (0x07f1, "cocoa", "age=0,facing=west"),
-    (0x07f2, "cocoa", "age=0,facing=north"),
-    (0x07f3, "cocoa", "age=0,facing=east"),
-    (0x07f4, "cocoa", "age=1,facing=south"),
-    (0x07f5, "cocoa", "age=1,facing=west"),
-    (0x07f6, "cocoa", "age=1,facing=north"),
-    (0x07f7, "cocoa", "age=1,facing=east"),
-    (0x07f8, "cocoa", "age=2,facing=south"),
-    (0x07f9, "cocoa", "age=2,facing=west"),
-    (0x07fa, "cocoa", "age=2,facing=north"),
-    (0x07fb, "cocoa", "age=2,facing=east"),
-    (
-        0x0800,
-        "sandstone_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0801,
-        "sandstone_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0802,
-        "sandstone_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0803,
-        "sandstone_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0804,
-        "sandstone_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0805,
-        "sandstone_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0806,
-        "sandstone_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0807,
-        "sandstone_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (0x0810, "emerald_ore", "normal"),
-    // 0822: "minecraft:ender_chest[facing=north]" (null)
-    // 0823: "minecraft:ender_chest[facing=south]" (null)
-    // 0824: "minecraft:ender_chest[facing=west]" (null)
-    // 0825: "minecraft:ender_chest[facing=east]" (null)
-    (
-        0x0830,
-        "tripwire_hook",
-        "attached=false,facing=south,powered=false,suspended=false",
-    ),
-    (
-        0x0831,
-        "tripwire_hook",
-        "attached=false,facing=west,powered=false,suspended=false",
-    ),
-    (
-        0x0832,
-        "tripwire_hook",
-        "attached=false,facing=north,powered=false,suspended=false",
-    ),
-    (
-        0x0833,
-        "tripwire_hook",
-        "attached=false,facing=east,powered=false,suspended=false",
-    ),
-    (
-        0x0834,
-        "tripwire_hook",
-        "attached=true,facing=south,powered=false,suspended=false",
-    ),
-    (
-        0x0835,
-        "tripwire_hook",
-        "attached=true,facing=west,powered=false,suspended=false",
-    ),
-    (
-        0x0836,
-        "tripwire_hook",
-        "attached=true,facing=north,powered=false,suspended=false",
-    ),
-    (
-        0x0837,
-        "tripwire_hook",
-        "attached=true,facing=east,powered=false,suspended=false",
-    ),
-    (
-        0x0838,
-        "tripwire_hook",
-        "attached=false,facing=south,powered=true,suspended=false",
-    ),
-    (
-        0x0839,
-        "tripwire_hook",
-        "attached=false,facing=west,powered=true,suspended=false",
-    ),
-    (
-        0x083a,
-        "tripwire_hook",
-        "attached=false,facing=north,powered=true,suspended=false",
-    ),
-    (
-        0x083b,
-        "tripwire_hook",
-        "attached=false,facing=east,powered=true,suspended=false",
-    ),
-    (
-        0x083c,
-        "tripwire_hook",
-        "attached=true,facing=south,powered=true,suspended=false",
-    ),
-    (
-        0x083d,
-        "tripwire_hook",
-        "attached=true,facing=west,powered=true,suspended=false",
-    ),
-    (
-        0x083e,
-        "tripwire_hook",
-        "attached=true,facing=north,powered=true,suspended=false",
-    ),
-    (
-        0x083f,
-        "tripwire_hook",
-        "attached=true,facing=east,powered=true,suspended=false",
-    ),
-    (
-        0x0840,
-        "tripwire",
-        "attached=false,east=false,north=false,south=false,suspended=false,west=false",
-    ),
-    (
-        0x0841,
-        "tripwire",
-        "attached=false,east=false,north=false,south=false,suspended=false,west=false",
-    ),
-    (
-        0x0842,
-        "tripwire",
-        "attached=false,east=false,north=false,south=false,suspended=true,west=false",
-    ),
-    (
-        0x0843,
-        "tripwire",
-        "attached=false,east=false,north=false,south=false,suspended=true,west=false",
-    ),
-    (
-        0x0844,
-        "tripwire",
-        "attached=true,east=false,north=false,south=false,suspended=false,west=false",
-    ),
-    (
-        0x0845,
-        "tripwire",
-        "attached=true,east=false,north=false,south=false,suspended=false,west=false",
-    ),
-    (
-        0x0846,
-        "tripwire",
-        "attached=true,east=false,north=false,south=false,suspended=true,west=false",
-    ),
-    (
-        0x0847,
-        "tripwire",
-        "attached=true,east=false,north=false,south=false,suspended=true,west=false",
-    ),
-    (
-        0x0848,
-        "tripwire",
-        "attached=false,east=false,north=false,south=false,suspended=false,west=false",
-    ),
-    (
-        0x0849,
-        "tripwire",
-        "attached=false,east=false,north=false,south=false,suspended=false,west=false",
-    ),
-    (
-        0x084a,
-        "tripwire",
-        "attached=false,east=false,north=false,south=false,suspended=true,west=false",
-    ),
-    (
-        0x084b,
-        "tripwire",
-        "attached=false,east=false,north=false,south=false,suspended=true,west=false",
-    ),
-    (
-        0x084c,
-        "tripwire",
-        "attached=true,east=false,north=false,south=false,suspended=false,west=false",
-    ),
-    (
-        0x084d,
-        "tripwire",
-        "attached=true,east=false,north=false,south=false,suspended=false,west=false",
-    ),
-    (
-        0x084e,
-        "tripwire",
-        "attached=true,east=false,north=false,south=false,suspended=true,west=false",
-    ),
-    (
-        0x084f,
-        "tripwire",
-        "attached=true,east=false,north=false,south=false,suspended=true,west=false",
-    ),
-    (0x0850, "emerald_block", "normal"),
-    (
-        0x0860,
-        "spruce_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0861,
-        "spruce_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0862,
-        "spruce_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0863,
-        "spruce_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0864,
-        "spruce_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0865,
-        "spruce_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0866,
-        "spruce_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0867,
-        "spruce_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (
-        0x0870,
-        "birch_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0871,
-        "birch_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0872,
-        "birch_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0873,
-        "birch_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0874,
-        "birch_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0875,
-        "birch_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0876,
-        "birch_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0877,
-        "birch_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (
-        0x0880,
-        "jungle_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0881,
-        "jungle_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0882,
-        "jungle_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0883,
-        "jungle_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0884,
-        "jungle_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0885,
-        "jungle_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0886,
-        "jungle_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0887,
-        "jungle_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (0x0890, "command_block", "normal"),
-    (0x0891, "command_block", "normal"),
-    (0x08a0, "beacon", "normal"),
-    (
-        0x08b0,
-        "cobblestone_wall",
-        "east=false,north=false,south=false,up=false,west=false",
-    ),
-    (
-        0x08b1,
-        "mossy_cobblestone_wall",
-        "east=false,north=false,south=false,up=false,west=false",
-    ),
-    (0x08c0, "flower_pot", "contents=cactus"),
-    (0x08c1, "flower_pot", "contents=cactus"),
-    (0x08c2, "flower_pot", "contents=cactus"),
-    (0x08c3, "flower_pot", "contents=cactus"),
-    (0x08c4, "flower_pot", "contents=cactus"),
-    (0x08c5, "flower_pot", "contents=cactus"),
-    (0x08c6, "flower_pot", "contents=cactus"),
-    (0x08c7, "flower_pot", "contents=cactus"),
-    (0x08c8, "flower_pot", "contents=cactus"),
-    (0x08c9, "flower_pot", "contents=cactus"),
-    (0x08ca, "flower_pot", "contents=cactus"),
-    (0x08cb, "flower_pot", "contents=cactus"),
-    (0x08cc, "flower_pot", "contents=cactus"),
-    (0x08cd, "flower_pot", "contents=cactus"),
-    (0x08ce, "flower_pot", "contents=cactus"),
-    (0x08cf, "flower_pot", "contents=cactus"),
-    (0x08d0, "carrots", "age=0"),
-    (0x08d1, "carrots", "age=1"),
-    (0x08d2, "carrots", "age=2"),
-    (0x08d3, "carrots", "age=3"),
-    (0x08d4, "carrots", "age=4"),
-    (0x08d5, "carrots", "age=5"),
-    (0x08d6, "carrots", "age=6"),
-    (0x08d7, "carrots", "age=7"),
-    (0x08e0, "potatoes", "age=0"),
-    (0x08e1, "potatoes", "age=1"),
-    (0x08e2, "potatoes", "age=2"),
-    (0x08e3, "potatoes", "age=3"),
-    (0x08e4, "potatoes", "age=4"),
-    (0x08e5, "potatoes", "age=5"),
-    (0x08e6, "potatoes", "age=6"),
-    (0x08e7, "potatoes", "age=7"),
-    (0x08f0, "wooden_button", "facing=down,powered=false"),
-    (0x08f1, "wooden_button", "facing=east,powered=false"),
-    (0x08f2, "wooden_button", "facing=west,powered=false"),
-    (0x08f3, "wooden_button", "facing=south,powered=false"),
-    (0x08f4, "wooden_button", "facing=north,powered=false"),
-    (0x08f5, "wooden_button", "facing=up,powered=false"),
-    (0x08f8, "wooden_button", "facing=down,powered=true"),
-    (0x08f9, "wooden_button", "facing=east,powered=true"),
-    (0x08fa, "wooden_button", "facing=west,powered=true"),
-    (0x08fb, "wooden_button", "facing=south,powered=true"),
-    (0x08fc, "wooden_button", "facing=north,powered=true"),
-    (0x08fd, "wooden_button", "facing=up,powered=true"),
-    // 0900: "minecraft:skull[facing=down,nodrop=false]" (null)
-    // 0901: "minecraft:skull[facing=up,nodrop=false]" (null)
-    // 0902: "minecraft:skull[facing=north,nodrop=false]" (null)
-    // 0903: "minecraft:skull[facing=south,nodrop=false]" (null)
-    // 0904: "minecraft:skull[facing=west,nodrop=false]" (null)
-    // 0905: "minecraft:skull[facing=east,nodrop=false]" (null)
-    // 0908: "minecraft:skull[facing=down,nodrop=true]" (null)
-    // 0909: "minecraft:skull[facing=up,nodrop=true]" (null)
-    // 090a: "minecraft:skull[facing=north,nodrop=true]" (null)
-    // 090b: "minecraft:skull[facing=south,nodrop=true]" (null)
-    // 090c: "minecraft:skull[facing=west,nodrop=true]" (null)
-    // 090d: "minecraft:skull[facing=east,nodrop=true]" (null)
-    (0x0910, "anvil", "damage=0,facing=south"),
-    (0x0911, "anvil", "damage=0,facing=west"),
-    (0x0912, "anvil", "damage=0,facing=north"),
-    (0x0913, "anvil", "damage=0,facing=east"),
-    (0x0914, "anvil", "damage=1,facing=south"),
-    (0x0915, "anvil", "damage=1,facing=west"),
-    (0x0916, "anvil", "damage=1,facing=north"),
-    (0x0917, "anvil", "damage=1,facing=east"),
-    (0x0918, "anvil", "damage=2,facing=south"),
-    (0x0919, "anvil", "damage=2,facing=west"),
-    (0x091a, "anvil", "damage=2,facing=north"),
-    (0x091b, "anvil", "damage=2,facing=east"),
-    // 0922: "minecraft:trapped_chest[facing=north]" (null)
-    // 0923: "minecraft:trapped_chest[facing=south]" (null)
-    // 0924: "minecraft:trapped_chest[facing=west]" (null)
-    // 0925: "minecraft:trapped_chest[facing=east]" (null)
-    (0x0930, "light_weighted_pressure_plate", "power=0"),
-    (0x0931, "light_weighted_pressure_plate", "power=1"),
-    (0x0932, "light_weighted_pressure_plate", "power=2"),
-    (0x0933, "light_weighted_pressure_plate", "power=3"),
-    (0x0934, "light_weighted_pressure_plate", "power=4"),
-    (0x0935, "light_weighted_pressure_plate", "power=5"),
-    (0x0936, "light_weighted_pressure_plate", "power=6"),
-    (0x0937, "light_weighted_pressure_plate", "power=7"),
-    (0x0938, "light_weighted_pressure_plate", "power=8"),
-    (0x0939, "light_weighted_pressure_plate", "power=9"),
-    (0x093a, "light_weighted_pressure_plate", "power=10"),
-    (0x093b, "light_weighted_pressure_plate", "power=11"),
-    (0x093c, "light_weighted_pressure_plate", "power=12"),
-    (0x093d, "light_weighted_pressure_plate", "power=13"),
-    (0x093e, "light_weighted_pressure_plate", "power=14"),
-    (0x093f, "light_weighted_pressure_plate", "power=15"),
-    (0x0940, "heavy_weighted_pressure_plate", "power=0"),
-    (0x0941, "heavy_weighted_pressure_plate", "power=1"),
-    (0x0942, "heavy_weighted_pressure_plate", "power=2"),
-    (0x0943, "heavy_weighted_pressure_plate", "power=3"),
-    (0x0944, "heavy_weighted_pressure_plate", "power=4"),
-    (0x0945, "heavy_weighted_pressure_plate", "power=5"),
-    (0x0946, "heavy_weighted_pressure_plate", "power=6"),
-    (0x0947, "heavy_weighted_pressure_plate", "power=7"),
-    (0x0948, "heavy_weighted_pressure_plate", "power=8"),
-    (0x0949, "heavy_weighted_pressure_plate", "power=9"),
-    (0x094a, "heavy_weighted_pressure_plate", "power=10"),
-    (0x094b, "heavy_weighted_pressure_plate", "power=11"),
-    (0x094c, "heavy_weighted_pressure_plate", "power=12"),
-    (0x094d, "heavy_weighted_pressure_plate", "power=13"),
-    (0x094e, "heavy_weighted_pressure_plate", "power=14"),
-    (0x094f, "heavy_weighted_pressure_plate", "power=15"),
-    (
-        0x0950,
-        "unpowered_comparator",
-        "facing=south,mode=compare,powered=false",
-    ),
-    (
-        0x0951,
-        "unpowered_comparator",
-        "facing=west,mode=compare,powered=false",
-    ),
-    (
-        0x0952,
-        "unpowered_comparator",
-        "facing=north,mode=compare,powered=false",
-    ),
-    (
-        0x0953,
-        "unpowered_comparator",
-        "facing=east,mode=compare,powered=false",
-    ),
-    (
-        0x0954,
-        "unpowered_comparator",
-        "facing=south,mode=subtract,powered=false",
-    ),
-    (
-        0x0955,
-        "unpowered_comparator",
-        "facing=west,mode=subtract,powered=false",
-    ),
-    (
-        0x0956,
-        "unpowered_comparator",
-        "facing=north,mode=subtract,powered=false",
-    ),
-    (
-        0x0957,
-        "unpowered_comparator",
-        "facing=east,mode=subtract,powered=false",
-    ),
-    (
-        0x0958,
-        "unpowered_comparator",
-        "facing=south,mode=compare,powered=true",
-    ),
-    (
-        0x0959,
-        "unpowered_comparator",
-        "facing=west,mode=compare,powered=true",
-    ),
-    (
-        0x095a,
-        "unpowered_comparator",
-        "facing=north,mode=compare,powered=true",
-    ),
-    (
-        0x095b,
-        "unpowered_comparator",
-        "facing=east,mode=compare,powered=true",
-    ),
-    (
-        0x095c,
-        "unpowered_comparator",
-        "facing=south,mode=subtract,powered=true",
-    ),
-    (
-        0x095d,
-        "unpowered_comparator",
-        "facing=west,mode=subtract,powered=true",
-    ),
-    (
-        0x095e,
-        "unpowered_comparator",
-        "facing=north,mode=subtract,powered=true",
-    ),
-    (
-        0x095f,
-        "unpowered_comparator",
-        "facing=east,mode=subtract,powered=true",
-    ),
-    (
-        0x0960,
-        "powered_comparator",
-        "facing=south,mode=compare,powered=false",
-    ),
-    (
-        0x0961,
-        "powered_comparator",
-        "facing=west,mode=compare,powered=false",
-    ),
-    (
-        0x0962,
-        "powered_comparator",
-        "facing=north,mode=compare,powered=false",
-    ),
-    (
-        0x0963,
-        "powered_comparator",
-        "facing=east,mode=compare,powered=false",
-    ),
-    (
-        0x0964,
-        "powered_comparator",
-        "facing=south,mode=subtract,powered=false",
-    ),
-    (
-        0x0965,
-        "powered_comparator",
-        "facing=west,mode=subtract,powered=false",
-    ),
-    (
-        0x0966,
-        "powered_comparator",
-        "facing=north,mode=subtract,powered=false",
-    ),
-    (
-        0x0967,
-        "powered_comparator",
-        "facing=east,mode=subtract,powered=false",
-    ),
-    (
-        0x0968,
-        "powered_comparator",
-        "facing=south,mode=compare,powered=true",
-    ),
-    (
-        0x0969,
-        "powered_comparator",
-        "facing=west,mode=compare,powered=true",
-    ),
-    (
-        0x096a,
-        "powered_comparator",
-        "facing=north,mode=compare,powered=true",
-    ),
-    (
-        0x096b,
-        "powered_comparator",
-        "facing=east,mode=compare,powered=true",
-    ),
-    (
-        0x096c,
-        "powered_comparator",
-        "facing=south,mode=subtract,powered=true",
-    ),
-    (
-        0x096d,
-        "powered_comparator",
-        "facing=west,mode=subtract,powered=true",
-    ),
-    (
-        0x096e,
-        "powered_comparator",
-        "facing=north,mode=subtract,powered=true",
-    ),
-    (
-        0x096f,
-        "powered_comparator",
-        "facing=east,mode=subtract,powered=true",
-    ),
-    (0x0970, "daylight_detector", "power=0"),
-    (0x0971, "daylight_detector", "power=1"),
-    (0x0972, "daylight_detector", "power=2"),
-    (0x0973, "daylight_detector", "power=3"),
-    (0x0974, "daylight_detector", "power=4"),
-    (0x0975, "daylight_detector", "power=5"),
-    (0x0976, "daylight_detector", "power=6"),
-    (0x0977, "daylight_detector", "power=7"),
-    (0x0978, "daylight_detector", "power=8"),
-    (0x0979, "daylight_detector", "power=9"),
-    (0x097a, "daylight_detector", "power=10"),
-    (0x097b, "daylight_detector", "power=11"),
-    (0x097c, "daylight_detector", "power=12"),
-    (0x097d, "daylight_detector", "power=13"),
-    (0x097e, "daylight_detector", "power=14"),
-    (0x097f, "daylight_detector", "power=15"),
-    (0x0980, "redstone_block", "normal"),
-    (0x0990, "quartz_ore", "normal"),
-    (0x09a0, "hopper", "facing=down"),
-    (0x09a2, "hopper", "facing=north"),
-    (0x09a3, "hopper", "facing=south"),
-    (0x09a4, "hopper", "facing=west"),
-    (0x09a5, "hopper", "facing=east"),
-    (0x09a8, "hopper", "facing=down"),
-    (0x09aa, "hopper", "facing=north"),
-    (0x09ab, "hopper", "facing=south"),
-    (0x09ac, "hopper", "facing=west"),
-    (0x09ad, "hopper", "facing=east"),
-    (0x09b0, "quartz_block", "normal"),
-    (0x09b1, "chiseled_quartz_block", "normal"),
-    (0x09b2, "quartz_column", "axis=y"),
-    (0x09b3, "quartz_column", "axis=x"),
-    (0x09b4, "quartz_column", "axis=z"),
-    (
-        0x09c0,
-        "quartz_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x09c1,
-        "quartz_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x09c2,
-        "quartz_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x09c3,
-        "quartz_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x09c4,
-        "quartz_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x09c5,
-        "quartz_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x09c6,
-        "quartz_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x09c7,
-        "quartz_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (0x09d0, "activator_rail", "powered=false,shape=north_south"),
-    (0x09d1, "activator_rail", "powered=false,shape=east_west"),
-    (
-        0x09d2,
-        "activator_rail",
-        "powered=false,shape=ascending_east",
-    ),
-    (
-        0x09d3,
-        "activator_rail",
-        "powered=false,shape=ascending_west",
-    ),
-    (
-        0x09d4,
-        "activator_rail",
-        "powered=false,shape=ascending_north",
-    ),
-    (
-        0x09d5,
-        "activator_rail",
-        "powered=false,shape=ascending_south",
-    ),
-    (0x09d8, "activator_rail", "powered=true,shape=north_south"),
-    (0x09d9, "activator_rail", "powered=true,shape=east_west"),
-    (
-        0x09da,
-        "activator_rail",
-        "powered=true,shape=ascending_east",
-    ),
-    (
-        0x09db,
-        "activator_rail",
-        "powered=true,shape=ascending_west",
-    ),
-    (
-        0x09dc,
-        "activator_rail",
-        "powered=true,shape=ascending_north",
-    ),
-    (
-        0x09dd,
-        "activator_rail",
-        "powered=true,shape=ascending_south",
-    ),
-    (0x09e0, "dropper", "facing=down"),
-    (0x09e1, "dropper", "facing=up"),
-    (0x09e2, "dropper", "facing=north"),
-    (0x09e3, "dropper", "facing=south"),
-    (0x09e4, "dropper", "facing=west"),
-    (0x09e5, "dropper", "facing=east"),
-    (0x09e8, "dropper", "facing=down"),
-    (0x09e9, "dropper", "facing=up"),
-    (0x09ea, "dropper", "facing=north"),
-    (0x09eb, "dropper", "facing=south"),
-    (0x09ec, "dropper", "facing=west"),
-    (0x09ed, "dropper", "facing=east"),
-    (0x09f0, "white_stained_hardened_clay", "normal"),
-    (0x09f1, "orange_stained_hardened_clay", "normal"),
-    (0x09f2, "magenta_stained_hardened_clay", "normal"),
-    (0x09f3, "light_blue_stained_hardened_clay", "normal"),
-    (0x09f4, "yellow_stained_hardened_clay", "normal"),
-    (0x09f5, "lime_stained_hardened_clay", "normal"),
-    (0x09f6, "pink_stained_hardened_clay", "normal"),
-    (0x09f7, "gray_stained_hardened_clay", "normal"),
-    (0x09f8, "silver_stained_hardened_clay", "normal"),
-    (0x09f9, "cyan_stained_hardened_clay", "normal"),
-    (0x09fa, "purple_stained_hardened_clay", "normal"),
-    (0x09fb, "blue_stained_hardened_clay", "normal"),
-    (0x09fc, "brown_stained_hardened_clay", "normal"),
-    (0x09fd, "green_stained_hardened_clay", "normal"),
-    (0x09fe, "red_stained_hardened_clay", "normal"),
-    (0x09ff, "black_stained_hardened_clay", "normal"),
-    (
-        0x0a00,
-        "white_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a01,
-        "orange_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a02,
-        "magenta_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a03,
-        "light_blue_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a04,
-        "yellow_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a05,
-        "lime_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a06,
-        "pink_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a07,
-        "gray_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a08,
-        "silver_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a09,
-        "cyan_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a0a,
-        "purple_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a0b,
-        "blue_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a0c,
-        "brown_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a0d,
-        "green_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a0e,
-        "red_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0a0f,
-        "black_stained_glass_pane",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (0x0a10, "acacia_leaves", "normal"),
-    (0x0a11, "dark_oak_leaves", "normal"),
-    (0x0a14, "acacia_leaves", "normal"),
-    (0x0a15, "dark_oak_leaves", "normal"),
-    (0x0a18, "acacia_leaves", "normal"),
-    (0x0a19, "dark_oak_leaves", "normal"),
-    (0x0a1c, "acacia_leaves", "normal"),
-    (0x0a1d, "dark_oak_leaves", "normal"),
-    (0x0a20, "acacia_log", "axis=y"),
-    (0x0a21, "dark_oak_log", "axis=y"),
-    (0x0a24, "acacia_log", "axis=x"),
-    (0x0a25, "dark_oak_log", "axis=x"),
-    (0x0a28, "acacia_log", "axis=z"),
-    (0x0a29, "dark_oak_log", "axis=z"),
-    (0x0a2c, "acacia_log", "axis=none"),
-    (0x0a2d, "dark_oak_log", "axis=none"),
-    (
-        0x0a30,
-        "acacia_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0a31,
-        "acacia_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0a32,
-        "acacia_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0a33,
-        "acacia_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0a34,
-        "acacia_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0a35,
-        "acacia_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0a36,
-        "acacia_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0a37,
-        "acacia_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (
-        0x0a40,
-        "dark_oak_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0a41,
-        "dark_oak_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0a42,
-        "dark_oak_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0a43,
-        "dark_oak_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0a44,
-        "dark_oak_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0a45,
-        "dark_oak_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0a46,
-        "dark_oak_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0a47,
-        "dark_oak_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (0x0a50, "slime", "normal"),
-    // 0a60: "minecraft:barrier" (null)
-    (
-        0x0a70,
-        "iron_trapdoor",
-        "facing=north,half=bottom,open=false",
-    ),
-    (
-        0x0a71,
-        "iron_trapdoor",
-        "facing=south,half=bottom,open=false",
-    ),
-    (
-        0x0a72,
-        "iron_trapdoor",
-        "facing=west,half=bottom,open=false",
-    ),
-    (
-        0x0a73,
-        "iron_trapdoor",
-        "facing=east,half=bottom,open=false",
-    ),
-    (
-        0x0a74,
-        "iron_trapdoor",
-        "facing=north,half=bottom,open=true",
-    ),
-    (
-        0x0a75,
-        "iron_trapdoor",
-        "facing=south,half=bottom,open=true",
-    ),
-    (0x0a76, "iron_trapdoor", "facing=west,half=bottom,open=true"),
-    (0x0a77, "iron_trapdoor", "facing=east,half=bottom,open=true"),
-    (0x0a78, "iron_trapdoor", "facing=north,half=top,open=false"),
-    (0x0a79, "iron_trapdoor", "facing=south,half=top,open=false"),
-    (0x0a7a, "iron_trapdoor", "facing=west,half=top,open=false"),
-    (0x0a7b, "iron_trapdoor", "facing=east,half=top,open=false"),
-    (0x0a7c, "iron_trapdoor", "facing=north,half=top,open=true"),
-    (0x0a7d, "iron_trapdoor", "facing=south,half=top,open=true"),
-    (0x0a7e, "iron_trapdoor", "facing=west,half=top,open=true"),
-    (0x0a7f, "iron_trapdoor", "facing=east,half=top,open=true"),
-    (0x0a80, "prismarine", "normal"),
-    (0x0a81, "prismarine_bricks", "normal"),
-    (0x0a82, "dark_prismarine", "normal"),
-    (0x0a90, "sea_lantern", "normal"),
-    (0x0aa0, "hay_block", "axis=y"),
-    (0x0aa4, "hay_block", "axis=x"),
-    (0x0aa8, "hay_block", "axis=z"),
-    (0x0ab0, "white_carpet", "normal"),
-    (0x0ab1, "orange_carpet", "normal"),
-    (0x0ab2, "magenta_carpet", "normal"),
-    (0x0ab3, "light_blue_carpet", "normal"),
-    (0x0ab4, "yellow_carpet", "normal"),
-    (0x0ab5, "lime_carpet", "normal"),
-    (0x0ab6, "pink_carpet", "normal"),
-    (0x0ab7, "gray_carpet", "normal"),
-    (0x0ab8, "silver_carpet", "normal"),
-    (0x0ab9, "cyan_carpet", "normal"),
-    (0x0aba, "purple_carpet", "normal"),
-    (0x0abb, "blue_carpet", "normal"),
-    (0x0abc, "brown_carpet", "normal"),
-    (0x0abd, "green_carpet", "normal"),
-    (0x0abe, "red_carpet", "normal"),
-    (0x0abf, "black_carpet", "normal"),
-    (0x0ac0, "hardened_clay", "normal"),
-    (0x0ad0, "coal_block", "normal"),
-    (0x0ae0, "packed_ice", "normal"),
-    (0x0af0, "sunflower", "half=lower"),
-    (0x0af1, "syringa", "half=lower"),
-    (0x0af2, "double_grass", "half=lower"),
-    (0x0af3, "double_fern", "half=lower"),
-    (0x0af4, "double_rose", "half=lower"),
-    (0x0af5, "paeonia", "half=lower"),
-    (0x0af8, "paeonia", "half=upper"),
-    // 0b00: "minecraft:standing_banner[rotation=0]" (null)
-    // 0b01: "minecraft:standing_banner[rotation=1]" (null)
-    // 0b02: "minecraft:standing_banner[rotation=2]" (null)
-    // 0b03: "minecraft:standing_banner[rotation=3]" (null)
-    // 0b04: "minecraft:standing_banner[rotation=4]" (null)
-    // 0b05: "minecraft:standing_banner[rotation=5]" (null)
-    // 0b06: "minecraft:standing_banner[rotation=6]" (null)
-    // 0b07: "minecraft:standing_banner[rotation=7]" (null)
-    // 0b08: "minecraft:standing_banner[rotation=8]" (null)
-    // 0b09: "minecraft:standing_banner[rotation=9]" (null)
-    // 0b0a: "minecraft:standing_banner[rotation=10]" (null)
-    // 0b0b: "minecraft:standing_banner[rotation=11]" (null)
-    // 0b0c: "minecraft:standing_banner[rotation=12]" (null)
-    // 0b0d: "minecraft:standing_banner[rotation=13]" (null)
-    // 0b0e: "minecraft:standing_banner[rotation=14]" (null)
-    // 0b0f: "minecraft:standing_banner[rotation=15]" (null)
-    // 0b12: "minecraft:wall_banner[facing=north]" (null)
-    // 0b13: "minecraft:wall_banner[facing=south]" (null)
-    // 0b14: "minecraft:wall_banner[facing=west]" (null)
-    // 0b15: "minecraft:wall_banner[facing=east]" (null)
-    (0x0b20, "daylight_detector_inverted", "power=0"),
-    (0x0b21, "daylight_detector_inverted", "power=1"),
-    (0x0b22, "daylight_detector_inverted", "power=2"),
-    (0x0b23, "daylight_detector_inverted", "power=3"),
-    (0x0b24, "daylight_detector_inverted", "power=4"),
-    (0x0b25, "daylight_detector_inverted", "power=5"),
-    (0x0b26, "daylight_detector_inverted", "power=6"),
-    (0x0b27, "daylight_detector_inverted", "power=7"),
-    (0x0b28, "daylight_detector_inverted", "power=8"),
-    (0x0b29, "daylight_detector_inverted", "power=9"),
-    (0x0b2a, "daylight_detector_inverted", "power=10"),
-    (0x0b2b, "daylight_detector_inverted", "power=11"),
-    (0x0b2c, "daylight_detector_inverted", "power=12"),
-    (0x0b2d, "daylight_detector_inverted", "power=13"),
-    (0x0b2e, "daylight_detector_inverted", "power=14"),
-    (0x0b2f, "daylight_detector_inverted", "power=15"),
-    (0x0b30, "red_sandstone", "normal"),
-    (0x0b31, "chiseled_red_sandstone", "normal"),
-    (0x0b32, "smooth_red_sandstone", "normal"),
-    (
-        0x0b40,
-        "red_sandstone_stairs",
-        "facing=east,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0b41,
-        "red_sandstone_stairs",
-        "facing=west,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0b42,
-        "red_sandstone_stairs",
-        "facing=south,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0b43,
-        "red_sandstone_stairs",
-        "facing=north,half=bottom,shape=outer_right",
-    ),
-    (
-        0x0b44,
-        "red_sandstone_stairs",
-        "facing=east,half=top,shape=outer_right",
-    ),
-    (
-        0x0b45,
-        "red_sandstone_stairs",
-        "facing=west,half=top,shape=outer_right",
-    ),
-    (
-        0x0b46,
-        "red_sandstone_stairs",
-        "facing=south,half=top,shape=outer_right",
-    ),
-    (
-        0x0b47,
-        "red_sandstone_stairs",
-        "facing=north,half=top,shape=outer_right",
-    ),
-    (0x0b50, "red_sandstone_double_slab", "normal"),
-    (0x0b58, "red_sandstone_double_slab", "all"),
-    (0x0b60, "red_sandstone_slab", "half=bottom"),
-    (0x0b68, "red_sandstone_slab", "half=top"),
-    (
-        0x0b70,
-        "spruce_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0b71,
-        "spruce_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0b72,
-        "spruce_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0b73,
-        "spruce_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0b74,
-        "spruce_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0b75,
-        "spruce_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0b76,
-        "spruce_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0b77,
-        "spruce_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0b78,
-        "spruce_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0b79,
-        "spruce_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0b7a,
-        "spruce_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0b7b,
-        "spruce_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0b7c,
-        "spruce_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0b7d,
-        "spruce_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0b7e,
-        "spruce_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0b7f,
-        "spruce_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0b80,
-        "birch_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0b81,
-        "birch_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0b82,
-        "birch_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0b83,
-        "birch_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0b84,
-        "birch_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0b85,
-        "birch_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0b86,
-        "birch_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0b87,
-        "birch_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0b88,
-        "birch_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0b89,
-        "birch_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0b8a,
-        "birch_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0b8b,
-        "birch_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0b8c,
-        "birch_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0b8d,
-        "birch_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0b8e,
-        "birch_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0b8f,
-        "birch_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0b90,
-        "jungle_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0b91,
-        "jungle_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0b92,
-        "jungle_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0b93,
-        "jungle_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0b94,
-        "jungle_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0b95,
-        "jungle_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0b96,
-        "jungle_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0b97,
-        "jungle_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0b98,
-        "jungle_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0b99,
-        "jungle_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0b9a,
-        "jungle_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0b9b,
-        "jungle_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0b9c,
-        "jungle_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0b9d,
-        "jungle_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0b9e,
-        "jungle_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0b9f,
-        "jungle_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0ba0,
-        "dark_oak_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0ba1,
-        "dark_oak_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0ba2,
-        "dark_oak_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0ba3,
-        "dark_oak_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0ba4,
-        "dark_oak_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0ba5,
-        "dark_oak_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0ba6,
-        "dark_oak_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0ba7,
-        "dark_oak_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0ba8,
-        "dark_oak_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0ba9,
-        "dark_oak_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0baa,
-        "dark_oak_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0bab,
-        "dark_oak_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0bac,
-        "dark_oak_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0bad,
-        "dark_oak_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0bae,
-        "dark_oak_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0baf,
-        "dark_oak_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0bb0,
-        "acacia_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0bb1,
-        "acacia_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0bb2,
-        "acacia_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0bb3,
-        "acacia_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0bb4,
-        "acacia_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0bb5,
-        "acacia_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0bb6,
-        "acacia_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0bb7,
-        "acacia_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0bb8,
-        "acacia_fence_gate",
-        "facing=south,in_wall=false,open=false",
-    ),
-    (
-        0x0bb9,
-        "acacia_fence_gate",
-        "facing=west,in_wall=false,open=false",
-    ),
-    (
-        0x0bba,
-        "acacia_fence_gate",
-        "facing=north,in_wall=false,open=false",
-    ),
-    (
-        0x0bbb,
-        "acacia_fence_gate",
-        "facing=east,in_wall=false,open=false",
-    ),
-    (
-        0x0bbc,
-        "acacia_fence_gate",
-        "facing=south,in_wall=false,open=true",
-    ),
-    (
-        0x0bbd,
-        "acacia_fence_gate",
-        "facing=west,in_wall=false,open=true",
-    ),
-    (
-        0x0bbe,
-        "acacia_fence_gate",
-        "facing=north,in_wall=false,open=true",
-    ),
-    (
-        0x0bbf,
-        "acacia_fence_gate",
-        "facing=east,in_wall=false,open=true",
-    ),
-    (
-        0x0bc0,
-        "spruce_fence",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0bd0,
-        "birch_fence",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0be0,
-        "jungle_fence",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0bf0,
-        "dark_oak_fence",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0c00,
-        "acacia_fence",
-        "east=false,north=false,south=false,west=false",
-    ),
-    (
-        0x0c10,
-        "spruce_door",
-        "facing=east,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c11,
-        "spruce_door",
-        "facing=south,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c12,
-        "spruce_door",
-        "facing=west,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c13,
-        "spruce_door",
-        "facing=north,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c14,
-        "spruce_door",
-        "facing=east,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c15,
-        "spruce_door",
-        "facing=south,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c16,
-        "spruce_door",
-        "facing=west,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c17,
-        "spruce_door",
-        "facing=north,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c18,
-        "spruce_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c19,
-        "spruce_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x0c1a,
-        "spruce_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c1b,
-        "spruce_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x0c20,
-        "birch_door",
-        "facing=east,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c21,
-        "birch_door",
-        "facing=south,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c22,
-        "birch_door",
-        "facing=west,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c23,
-        "birch_door",
-        "facing=north,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c24,
-        "birch_door",
-        "facing=east,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c25,
-        "birch_door",
-        "facing=south,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c26,
-        "birch_door",
-        "facing=west,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c27,
-        "birch_door",
-        "facing=north,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c28,
-        "birch_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c29,
-        "birch_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x0c2a,
-        "birch_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c2b,
-        "birch_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x0c30,
-        "jungle_door",
-        "facing=east,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c31,
-        "jungle_door",
-        "facing=south,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c32,
-        "jungle_door",
-        "facing=west,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c33,
-        "jungle_door",
-        "facing=north,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c34,
-        "jungle_door",
-        "facing=east,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c35,
-        "jungle_door",
-        "facing=south,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c36,
-        "jungle_door",
-        "facing=west,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c37,
-        "jungle_door",
-        "facing=north,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c38,
-        "jungle_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c39,
-        "jungle_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x0c3a,
-        "jungle_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c3b,
-        "jungle_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x0c40,
-        "acacia_door",
-        "facing=east,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c41,
-        "acacia_door",
-        "facing=south,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c42,
-        "acacia_door",
-        "facing=west,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c43,
-        "acacia_door",
-        "facing=north,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c44,
-        "acacia_door",
-        "facing=east,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c45,
-        "acacia_door",
-        "facing=south,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c46,
-        "acacia_door",
-        "facing=west,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c47,
-        "acacia_door",
-        "facing=north,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c48,
-        "acacia_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c49,
-        "acacia_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x0c4a,
-        "acacia_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c4b,
-        "acacia_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x0c50,
-        "dark_oak_door",
-        "facing=east,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c51,
-        "dark_oak_door",
-        "facing=south,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c52,
-        "dark_oak_door",
-        "facing=west,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c53,
-        "dark_oak_door",
-        "facing=north,half=lower,hinge=right,open=false",
-    ),
-    (
-        0x0c54,
-        "dark_oak_door",
-        "facing=east,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c55,
-        "dark_oak_door",
-        "facing=south,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c56,
-        "dark_oak_door",
-        "facing=west,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c57,
-        "dark_oak_door",
-        "facing=north,half=lower,hinge=right,open=true",
-    ),
-    (
-        0x0c58,
-        "dark_oak_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c59,
-        "dark_oak_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-    (
-        0x0c5a,
-        "dark_oak_door",
-        "facing=east,half=upper,hinge=left,open=false",
-    ),
-    (
-        0x0c5b,
-        "dark_oak_door",
-        "facing=east,half=upper,hinge=right,open=false",
-    ),
-];
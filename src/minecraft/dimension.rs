@@ -0,0 +1,140 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::minecraft::nbt::Nbt;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Dimension {
+    Overworld,
+    Nether,
+    End,
+    // Datapack dimension, stored in `dimensions/<namespace>/<name>`.
+    Custom(String, String),
+}
+
+impl Dimension {
+    /// Legacy numeric dimension ID, as saved before 1.16.
+    pub fn from_id(id: i32) -> Option<Dimension> {
+        match id {
+            -1 => Some(Dimension::Nether),
+            0 => Some(Dimension::Overworld),
+            1 => Some(Dimension::End),
+            _ => None,
+        }
+    }
+
+    /// Reads the `Dimension` tag of a player, which is either
+    /// a numeric ID or a namespaced name depending on the version.
+    pub fn from_nbt(nbt: &Nbt) -> Option<Dimension> {
+        match *nbt {
+            Nbt::Int(id) => Dimension::from_id(id),
+            Nbt::String(ref name) => name.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Folder containing the dimension data, relative to the world.
+    pub fn path(&self, world: &Path) -> PathBuf {
+        match *self {
+            Dimension::Overworld => world.to_path_buf(),
+            Dimension::Nether => world.join("DIM-1"),
+            Dimension::End => world.join("DIM1"),
+            Dimension::Custom(ref namespace, ref name) => {
+                world.join("dimensions").join(namespace).join(name)
+            }
+        }
+    }
+
+    pub fn region_path(&self, world: &Path) -> PathBuf {
+        self.path(world).join("region")
+    }
+
+    /// Color used for clearing the screen, matching the vanilla fog.
+    pub fn sky_color(&self) -> [f32; 4] {
+        match *self {
+            Dimension::Overworld | Dimension::Custom(..) => [0.81, 0.8, 1.0, 1.0],
+            Dimension::Nether => [0.2, 0.03, 0.03, 1.0],
+            Dimension::End => [0.094, 0.075, 0.094, 1.0],
+        }
+    }
+}
+
+impl FromStr for Dimension {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Dimension, String> {
+        if let Ok(id) = s.parse() {
+            return Dimension::from_id(id).ok_or_else(|| format!("unknown dimension ID {}", id));
+        }
+        let (namespace, name) = match s.find(':') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => ("minecraft", s),
+        };
+        Ok(match (namespace, name) {
+            ("minecraft", "overworld") => Dimension::Overworld,
+            ("minecraft", "nether") | ("minecraft", "the_nether") => Dimension::Nether,
+            ("minecraft", "end") | ("minecraft", "the_end") => Dimension::End,
+            ("minecraft", _) => return Err(format!("unknown dimension {}", s)),
+            (namespace, name) => Dimension::Custom(namespace.to_string(), name.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Dimension::Overworld => write!(f, "minecraft:overworld"),
+            Dimension::Nether => write!(f, "minecraft:the_nether"),
+            Dimension::End => write!(f, "minecraft:the_end"),
+            Dimension::Custom(ref namespace, ref name) => write!(f, "{}:{}", namespace, name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_ids() {
+        assert_eq!("-1".parse(), Ok(Dimension::Nether));
+        assert_eq!("0".parse(), Ok(Dimension::Overworld));
+        assert_eq!("1".parse(), Ok(Dimension::End));
+        assert!("2".parse::<Dimension>().is_err());
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!("minecraft:the_nether".parse(), Ok(Dimension::Nether));
+        assert_eq!("the_end".parse(), Ok(Dimension::End));
+        assert_eq!("overworld".parse(), Ok(Dimension::Overworld));
+        assert!("minecraft:moon".parse::<Dimension>().is_err());
+        let custom = Dimension::Custom("mod".to_string(), "moon".to_string());
+        assert_eq!("mod:moon".parse(), Ok(custom.clone()));
+        assert_eq!(custom.to_string(), "mod:moon");
+        assert_eq!(Dimension::Nether.to_string().parse(), Ok(Dimension::Nether));
+    }
+
+    #[test]
+    fn from_nbt() {
+        assert_eq!(Dimension::from_nbt(&Nbt::Int(-1)), Some(Dimension::Nether));
+        assert_eq!(Dimension::from_nbt(&Nbt::Int(5)), None);
+        let name = Nbt::String("minecraft:the_end".to_string());
+        assert_eq!(Dimension::from_nbt(&name), Some(Dimension::End));
+        assert_eq!(Dimension::from_nbt(&Nbt::Byte(0)), None);
+    }
+
+    #[test]
+    fn region_paths() {
+        let world = Path::new("world");
+        let region = |dimension: Dimension| dimension.region_path(world);
+        assert_eq!(region(Dimension::Overworld), Path::new("world/region"));
+        assert_eq!(region(Dimension::Nether), Path::new("world/DIM-1/region"));
+        assert_eq!(region(Dimension::End), Path::new("world/DIM1/region"));
+        assert_eq!(
+            region(Dimension::Custom("mod".to_string(), "moon".to_string())),
+            Path::new("world/dimensions/mod/moon/region")
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use crate::chunk::BlockState;
+use crate::cube;
+use crate::minecraft::atlas::AtlasBuilder;
+use crate::minecraft::model::{Opacity, Vertex};
+use vecmath::vec2_normalized;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Fluid {
+    Water,
+    Lava,
+}
+
+impl Fluid {
+    /// The fluid of a block, by its name in the registry.
+    pub fn from_name(name: &str) -> Option<Fluid> {
+        match name {
+            "water" | "flowing_water" => Some(Fluid::Water),
+            "lava" | "flowing_lava" => Some(Fluid::Lava),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Fluid::Water => "water",
+            Fluid::Lava => "lava",
+        }
+    }
+}
+
+/// What meshing fluids needs to know about blocks.
+pub trait FluidBlocks {
+    fn fluid(&self, block: BlockState) -> Option<Fluid>;
+
+    fn opacity(&self, block: BlockState) -> Opacity;
+}
+
+// The `level` property: 0 for sources, 1 to 7 as the fluid
+// spreads out, 8 and more for falling fluid.
+fn level(block: BlockState) -> u16 {
+    block.value & 0xf
+}
+
+// How far a fluid has spread, or `None` for other blocks.
+fn flow_decay<B: FluidBlocks>(blocks: &B, fluid: Fluid, block: BlockState) -> Option<i32> {
+    if blocks.fluid(block) != Some(fluid) {
+        return None;
+    }
+    Some(match level(block) {
+        l if l >= 8 => 0,
+        l => l as i32,
+    })
+}
+
+/// Where the textures of a fluid are in the atlas.
+#[derive(Copy, Clone)]
+pub struct FluidTextures {
+    pub still: [f32; 2],
+    pub flow: [f32; 2],
+}
+
+impl FluidTextures {
+    pub fn load(fluid: Fluid, atlas: &mut AtlasBuilder) -> FluidTextures {
+        let name = fluid.name();
+        let (u, v) = atlas.load(&format!("blocks/{}_still", name));
+        let still = [u as f32, v as f32];
+        let (u, v) = atlas.load(&format!("blocks/{}_flow", name));
+        let flow = [u as f32, v as f32];
+        FluidTextures { still, flow }
+    }
+
+    /// Scales the positions from pixels to the size of the atlas.
+    pub fn scale(&mut self, u_unit: f32, v_unit: f32) {
+        for uv in [&mut self.still, &mut self.flow].iter_mut() {
+            uv[0] *= u_unit;
+            uv[1] *= v_unit;
+        }
+    }
+}
+
+// Height of the fluid at the corner on the min side of `corner`, from
+// the blocks sharing it, like vanilla. `at` returns the block at the
+// given offset from the corner's block.
+fn corner_height<B, A>(blocks: &B, fluid: Fluid, at: &A, corner: [i32; 2]) -> f32
+where
+    B: FluidBlocks,
+    A: Fn([i32; 3]) -> BlockState,
+{
+    let (mut sum, mut weight) = (0.0, 0.0);
+    for &(dx, dz) in [(-1, -1), (0, -1), (-1, 0), (0, 0)].iter() {
+        let (x, z) = (corner[0] + dx, corner[1] + dz);
+        if blocks.fluid(at([x, 1, z])) == Some(fluid) {
+            return 1.0;
+        }
+        let block = at([x, 0, z]);
+        if blocks.fluid(block) == Some(fluid) {
+            let level = level(block);
+            let percent = (if level >= 8 { 0 } else { level } + 1) as f32 / 9.0;
+            if level == 0 || level >= 8 {
+                sum += percent * 10.0;
+                weight += 10.0;
+            }
+            sum += percent;
+            weight += 1.0;
+        } else if !blocks.opacity(block).is_solid() {
+            sum += 1.0;
+            weight += 1.0;
+        }
+    }
+    1.0 - sum / weight
+}
+
+// Direction the fluid flows in, on the xz plane.
+fn flow_direction<B, A>(blocks: &B, fluid: Fluid, at: &A) -> [f32; 2]
+where
+    B: FluidBlocks,
+    A: Fn([i32; 3]) -> BlockState,
+{
+    let decay = flow_decay(blocks, fluid, at([0, 0, 0])).unwrap_or(0);
+    let mut flow = [0.0, 0.0];
+    for &face in [cube::North, cube::South, cube::West, cube::East].iter() {
+        let [dx, _, dz] = face.direction();
+        let neighbor = at([dx, 0, dz]);
+        let diff = match flow_decay(blocks, fluid, neighbor) {
+            Some(d) => d - decay,
+            // Fluid falling down next to this one pulls it along.
+            None if !blocks.opacity(neighbor).is_solid() => {
+                match flow_decay(blocks, fluid, at([dx, -1, dz])) {
+                    Some(d) => d - (decay - 8),
+                    None => continue,
+                }
+            }
+            None => continue,
+        };
+        flow[0] += (dx * diff) as f32;
+        flow[1] += (dz * diff) as f32;
+    }
+    if flow == [0.0, 0.0] {
+        flow
+    } else {
+        vec2_normalized(flow)
+    }
+}
+
+/// Builds the visible faces of a fluid block, in block coordinates,
+/// with texture coordinates in the atlas. `at` returns blocks by
+/// offset from this one, and their opacity decides which neighbors
+/// hide faces and which ones the fluid can flow through.
+pub fn fluid_faces<B, A>(
+    blocks: &B,
+    fluid: Fluid,
+    textures: &FluidTextures,
+    at: A,
+) -> Vec<(cube::Face, [Vertex; 4])>
+where
+    B: FluidBlocks,
+    A: Fn([i32; 3]) -> BlockState,
+{
+    let heights =
+        [[0, 0], [0, 1], [1, 0], [1, 1]].map(|[x, z]| corner_height(blocks, fluid, &at, [x, z]));
+    let height = |x: f32, z: f32| heights[x as usize * 2 + z as usize];
+    let flow = flow_direction(blocks, fluid, &at);
+
+    // Texture coordinates, in pixels of a 16x16 tile, are
+    // brought closer to the middle to avoid seams.
+    let uv = |base: [f32; 2], u: f32, v: f32| {
+        let inset = |x: f32| x - (x - 8.0).signum() / 128.0;
+        [base[0] + inset(u), base[1] + inset(v)]
+    };
+
+    let mut faces = vec![];
+    for face in cube::FaceIterator::new() {
+        let neighbor = at(face.direction());
+        if blocks.fluid(neighbor) == Some(fluid) {
+            continue;
+        }
+        if face != cube::Up && blocks.opacity(neighbor).is_opaque() {
+            continue;
+        }
+        let mut vertices = face
+            .vertices([0.0; 3], [1.0; 3])
+            .map(|xyz| Vertex { xyz, uv: [0.0; 2] });
+        for vertex in vertices.iter_mut() {
+            let [x, y, z] = vertex.xyz;
+            if y == 1.0 {
+                vertex.xyz[1] = height(x, z);
+            }
+            let [x, y, z] = vertex.xyz;
+            vertex.uv = match face {
+                cube::Up if flow == [0.0, 0.0] => uv(textures.still, x * 16.0, z * 16.0),
+                cube::Up => {
+                    // Turn the flowing texture along the flow,
+                    // only showing its middle part, like vanilla.
+                    let (s, c) = (flow[0], flow[1]);
+                    let (x, z) = (x - 0.5, z - 0.5);
+                    let (u, v) = (x * c - z * s, x * s + z * c);
+                    uv(textures.flow, 8.0 + u * 8.0, 8.0 + v * 8.0)
+                }
+                cube::Down => uv(textures.still, x * 16.0, z * 16.0),
+                cube::North | cube::South => uv(textures.flow, x * 8.0, (1.0 - y) * 8.0),
+                cube::West | cube::East => uv(textures.flow, z * 8.0, (1.0 - y) * 8.0),
+            };
+        }
+        faces.push((face, vertices));
+    }
+    faces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const AIR: BlockState = BlockState { value: 0 };
+    const STONE: BlockState = BlockState { value: 1 << 4 };
+
+    fn water(level: u16) -> BlockState {
+        BlockState {
+            value: 8 << 4 | level,
+        }
+    }
+
+    // Blocks by offset from the fluid block, air elsewhere.
+    struct Grid(HashMap<[i32; 3], BlockState>);
+
+    impl Grid {
+        fn new(blocks: &[([i32; 3], BlockState)]) -> Grid {
+            Grid(blocks.iter().cloned().collect())
+        }
+
+        fn at(&self) -> impl Fn([i32; 3]) -> BlockState + '_ {
+            move |p| self.0.get(&p).cloned().unwrap_or(AIR)
+        }
+    }
+
+    struct Blocks;
+
+    impl FluidBlocks for Blocks {
+        fn fluid(&self, block: BlockState) -> Option<Fluid> {
+            if block.value >> 4 == 8 {
+                Some(Fluid::Water)
+            } else {
+                None
+            }
+        }
+
+        fn opacity(&self, block: BlockState) -> Opacity {
+            if block == STONE {
+                Opacity::Opaque
+            } else {
+                Opacity::Transparent
+            }
+        }
+    }
+
+    fn heights(grid: &Grid) -> [f32; 4] {
+        [[0, 0], [0, 1], [1, 0], [1, 1]]
+            .map(|c| corner_height(&Blocks, Fluid::Water, &grid.at(), c))
+    }
+
+    fn flow(grid: &Grid) -> [f32; 2] {
+        flow_direction(&Blocks, Fluid::Water, &grid.at())
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(Fluid::from_name("flowing_water"), Some(Fluid::Water));
+        assert_eq!(Fluid::from_name("water"), Some(Fluid::Water));
+        assert_eq!(Fluid::from_name("flowing_lava"), Some(Fluid::Lava));
+        assert_eq!(Fluid::from_name("lava"), Some(Fluid::Lava));
+        assert_eq!(Fluid::from_name("waterlily"), None);
+    }
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn pool_height() {
+        // A source surrounded by sources is 8/9 high.
+        let mut blocks = vec![];
+        for z in -1..2 {
+            for x in -1..2 {
+                blocks.push(([x, 0, z], water(0)));
+            }
+        }
+        for &h in heights(&Grid::new(&blocks)).iter() {
+            assert_near(h, 8.0 / 9.0);
+        }
+    }
+
+    #[test]
+    fn lone_source_height() {
+        // (10 + 1) / 9 for the source, 1 for each of the 3 air blocks.
+        let grid = Grid::new(&[([0, 0, 0], water(0))]);
+        for &h in heights(&grid).iter() {
+            assert_near(h, 1.0 - (11.0 / 9.0 + 3.0) / 14.0);
+        }
+    }
+
+    #[test]
+    fn slope_heights() {
+        // Spreading to the east, against stone on the west.
+        let grid = Grid::new(&[
+            ([-1, 0, -1], STONE),
+            ([-1, 0, 0], STONE),
+            ([-1, 0, 1], STONE),
+            ([0, 0, -1], water(2)),
+            ([0, 0, 0], water(2)),
+            ([0, 0, 1], water(2)),
+            ([1, 0, -1], water(3)),
+            ([1, 0, 0], water(3)),
+            ([1, 0, 1], water(3)),
+        ]);
+        let h = heights(&grid);
+        assert_near(h[0], 1.0 - 3.0 / 9.0);
+        assert_near(h[1], 1.0 - 3.0 / 9.0);
+        assert_near(h[2], 1.0 - 3.5 / 9.0);
+        assert_near(h[3], 1.0 - 3.5 / 9.0);
+    }
+
+    #[test]
+    fn full_under_fluid() {
+        let grid = Grid::new(&[([0, 0, 0], water(3)), ([-1, 1, -1], water(0))]);
+        let h = heights(&grid);
+        assert_eq!(h[0], 1.0);
+        assert!(h[3] < 1.0);
+    }
+
+    #[test]
+    fn flow_directions() {
+        // Still in a pool.
+        let mut blocks = vec![];
+        for z in -1..2 {
+            for x in -1..2 {
+                blocks.push(([x, 0, z], water(0)));
+            }
+        }
+        assert_eq!(flow(&Grid::new(&blocks)), [0.0, 0.0]);
+
+        // Towards the more spread out fluid, away from the source.
+        let grid = Grid::new(&[
+            ([0, 0, 0], water(1)),
+            ([-1, 0, 0], water(0)),
+            ([1, 0, 0], water(2)),
+            ([0, 0, -1], STONE),
+            ([0, 0, 1], STONE),
+        ]);
+        assert_eq!(flow(&grid), [1.0, 0.0]);
+
+        // Diagonally, between two lower neighbors.
+        let grid = Grid::new(&[
+            ([0, 0, 0], water(1)),
+            ([-1, 0, 0], water(0)),
+            ([0, 0, -1], water(0)),
+            ([1, 0, 0], water(2)),
+            ([0, 0, 1], water(2)),
+        ]);
+        let [x, z] = flow(&grid);
+        assert_near(x, 0.5f32.sqrt());
+        assert_near(z, 0.5f32.sqrt());
+
+        // Towards a drop, stronger than the spreading on the other side.
+        let grid = Grid::new(&[
+            ([0, 0, 0], water(1)),
+            ([0, 0, -1], water(2)),
+            ([0, -1, 1], water(8)),
+            ([-1, 0, 0], STONE),
+            ([1, 0, 0], STONE),
+            ([0, -1, -1], STONE),
+        ]);
+        assert_eq!(flow(&grid), [0.0, 1.0]);
+    }
+
+    #[test]
+    fn top_texture() {
+        let textures = FluidTextures {
+            still: [0.0, 0.0],
+            flow: [100.0, 0.0],
+        };
+        let top = |grid: &Grid| {
+            let faces = fluid_faces(&Blocks, Fluid::Water, &textures, grid.at());
+            faces.into_iter().find(|&(f, _)| f == cube::Up).unwrap().1
+        };
+        let still = top(&Grid::new(&[]));
+        assert!(still.iter().all(|v| v.uv[0] < 16.0));
+        let flowing = top(&Grid::new(&[([1, 0, 0], water(2)), ([0, 0, 0], water(1))]));
+        assert!(flowing.iter().all(|v| v.uv[0] >= 100.0 && v.uv[0] < 116.0));
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use crate::minecraft::nbt::{Compound, Nbt, NbtReaderError};
+use flate2::read::GzDecoder;
+
+pub type LevelResult<T> = Result<T, LevelError>;
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(io::Error),
+    Nbt(NbtReaderError),
+    MissingField(&'static str),
+    InvalidField(&'static str),
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            LevelError::Io(ref err) => write!(f, "couldn't read level.dat: {}", err),
+            LevelError::Nbt(ref err) => write!(f, "invalid NBT in level.dat: {:?}", err),
+            LevelError::MissingField(name) => write!(f, "level.dat is missing '{}'", name),
+            LevelError::InvalidField(name) => write!(f, "level.dat has invalid '{}'", name),
+        }
+    }
+}
+
+impl From<io::Error> for LevelError {
+    fn from(err: io::Error) -> LevelError {
+        LevelError::Io(err)
+    }
+}
+
+impl From<NbtReaderError> for LevelError {
+    fn from(err: NbtReaderError) -> LevelError {
+        LevelError::Nbt(err)
+    }
+}
+
+/// Game version which last saved the world (1.9 and newer).
+#[derive(Clone, Debug)]
+pub struct Version {
+    pub id: i32,
+    pub name: String,
+    pub snapshot: bool,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Weather {
+    pub raining: bool,
+    pub rain_time: i32,
+    pub thundering: bool,
+    pub thunder_time: i32,
+    pub clear_weather_time: i32,
+}
+
+/// The `Data` compound of `level.dat`.
+#[derive(Clone, Debug)]
+pub struct LevelData {
+    pub level_name: String,
+    pub data_version: Option<i32>,
+    pub version: Option<Version>,
+    pub seed: Option<i64>,
+    pub spawn: [i32; 3],
+    pub time: i64,
+    pub day_time: i64,
+    pub weather: Weather,
+    pub game_rules: HashMap<String, String>,
+    // Only present in singleplayer worlds.
+    pub player: Option<Compound>,
+}
+
+fn field<'a>(data: &'a Compound, name: &'static str) -> LevelResult<&'a Nbt> {
+    data.get(name).ok_or(LevelError::MissingField(name))
+}
+
+fn int(data: &Compound, name: &'static str) -> LevelResult<i32> {
+    field(data, name)?
+        .as_int()
+        .ok_or(LevelError::InvalidField(name))
+}
+
+fn optional<T, F>(data: &Compound, name: &'static str, f: F) -> LevelResult<Option<T>>
+where
+    F: FnOnce(&Nbt) -> Option<T>,
+{
+    match data.get(name) {
+        Some(nbt) => f(nbt).map(Some).ok_or(LevelError::InvalidField(name)),
+        None => Ok(None),
+    }
+}
+
+impl LevelData {
+    pub fn open(world: &Path) -> LevelResult<LevelData> {
+        let reader = GzDecoder::new(File::open(world.join("level.dat"))?);
+        LevelData::from_nbt(Nbt::from_reader(reader)?)
+    }
+
+    pub fn from_nbt(nbt: Nbt) -> LevelResult<LevelData> {
+        let mut root = nbt
+            .into_compound()
+            .map_err(|_| LevelError::InvalidField("root"))?;
+        let mut data = root
+            .remove("Data")
+            .ok_or(LevelError::MissingField("Data"))?
+            .into_compound()
+            .map_err(|_| LevelError::InvalidField("Data"))?;
+
+        let version = match data.get("Version").and_then(|v| v.as_compound()) {
+            Some(version) => Some(Version {
+                id: int(version, "Id")?,
+                name: field(version, "Name")?
+                    .as_string()
+                    .ok_or(LevelError::InvalidField("Name"))?
+                    .to_string(),
+                snapshot: optional(version, "Snapshot", Nbt::as_byte)?.is_some_and(|b| b != 0),
+            }),
+            None => None,
+        };
+
+        // Moved into `WorldGenSettings` in 1.16.
+        let seed = match optional(&data, "RandomSeed", Nbt::as_long)? {
+            Some(seed) => Some(seed),
+            None => match data.get("WorldGenSettings").and_then(|s| s.as_compound()) {
+                Some(settings) => optional(settings, "seed", Nbt::as_long)?,
+                None => None,
+            },
+        };
+
+        let flag = |name| optional(&data, name, Nbt::as_byte).map(|b| b.is_some_and(|b| b != 0));
+        let weather = Weather {
+            raining: flag("raining")?,
+            rain_time: optional(&data, "rainTime", Nbt::as_int)?.unwrap_or(0),
+            thundering: flag("thundering")?,
+            thunder_time: optional(&data, "thunderTime", Nbt::as_int)?.unwrap_or(0),
+            clear_weather_time: optional(&data, "clearWeatherTime", Nbt::as_int)?.unwrap_or(0),
+        };
+
+        let game_rules = match data.get("GameRules").and_then(|r| r.as_compound()) {
+            Some(rules) => rules
+                .iter()
+                .map(|(k, v)| {
+                    let value = match v.as_string() {
+                        Some(s) => s.to_string(),
+                        None => v.to_string(),
+                    };
+                    (k.clone(), value)
+                })
+                .collect(),
+            None => HashMap::new(),
+        };
+
+        Ok(LevelData {
+            level_name: optional(&data, "LevelName", |n| n.as_string().map(|n| n.to_string()))?
+                .unwrap_or_default(),
+            data_version: optional(&data, "DataVersion", Nbt::as_int)?,
+            version,
+            seed,
+            spawn: [
+                int(&data, "SpawnX")?,
+                int(&data, "SpawnY")?,
+                int(&data, "SpawnZ")?,
+            ],
+            time: optional(&data, "Time", Nbt::as_long)?.unwrap_or(0),
+            day_time: optional(&data, "DayTime", Nbt::as_long)?.unwrap_or(0),
+            weather,
+            game_rules,
+            player: match data.remove("Player") {
+                Some(player) => Some(
+                    player
+                        .into_compound()
+                        .map_err(|_| LevelError::InvalidField("Player"))?,
+                ),
+                None => None,
+            },
+        })
+    }
+
+    /// Time of day in ticks, from 0 (sunrise) to 24000.
+    pub fn time_of_day(&self) -> i64 {
+        self.day_time.rem_euclid(24000)
+    }
+
+    pub fn game_rule(&self, name: &str) -> Option<&str> {
+        self.game_rules.get(name).map(|s| &s[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compound(fields: Vec<(&str, Nbt)>) -> Nbt {
+        Nbt::Compound(
+            fields
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+
+    fn level(fields: Vec<(&str, Nbt)>) -> LevelResult<LevelData> {
+        let spawn = vec![
+            ("SpawnX", Nbt::Int(1)),
+            ("SpawnY", Nbt::Int(64)),
+            ("SpawnZ", Nbt::Int(-3)),
+        ];
+        let data = compound(spawn.into_iter().chain(fields).collect());
+        LevelData::from_nbt(compound(vec![("Data", data)]))
+    }
+
+    #[test]
+    fn minimal() {
+        let level = level(vec![]).unwrap();
+        assert_eq!(level.spawn, [1, 64, -3]);
+        assert_eq!(level.level_name, "");
+        assert_eq!(level.data_version, None);
+        assert!(level.version.is_none());
+        assert_eq!(level.seed, None);
+        assert_eq!(level.time, 0);
+        assert!(!level.weather.raining);
+        assert!(level.game_rules.is_empty());
+        assert!(level.player.is_none());
+    }
+
+    #[test]
+    fn optional_fields() {
+        let level = level(vec![
+            ("LevelName", Nbt::String("Test".to_string())),
+            ("DataVersion", Nbt::Int(2586)),
+            (
+                "Version",
+                compound(vec![
+                    ("Id", Nbt::Int(2586)),
+                    ("Name", Nbt::String("1.16.5".to_string())),
+                    ("Snapshot", Nbt::Byte(0)),
+                ]),
+            ),
+            ("DayTime", Nbt::Long(24000 * 3 + 6000)),
+            ("raining", Nbt::Byte(1)),
+            ("rainTime", Nbt::Int(1200)),
+            (
+                "GameRules",
+                compound(vec![("doDaylightCycle", Nbt::String("false".to_string()))]),
+            ),
+            ("Player", compound(vec![("Health", Nbt::Float(20.0))])),
+        ])
+        .unwrap();
+        assert_eq!(level.level_name, "Test");
+        assert_eq!(level.data_version, Some(2586));
+        let version = level.version.as_ref().unwrap();
+        assert_eq!((version.id, &version.name[..]), (2586, "1.16.5"));
+        assert!(!version.snapshot);
+        assert_eq!(level.time_of_day(), 6000);
+        assert!(level.weather.raining);
+        assert_eq!(level.weather.rain_time, 1200);
+        assert_eq!(level.game_rule("doDaylightCycle"), Some("false"));
+        assert!(level.player.unwrap().contains_key("Health"));
+    }
+
+    #[test]
+    fn seed() {
+        let old = level(vec![("RandomSeed", Nbt::Long(42))]).unwrap();
+        assert_eq!(old.seed, Some(42));
+
+        let settings = compound(vec![("seed", Nbt::Long(-7))]);
+        let new = level(vec![("WorldGenSettings", settings.clone())]).unwrap();
+        assert_eq!(new.seed, Some(-7));
+
+        // `RandomSeed` wins if both are there.
+        let both = level(vec![
+            ("RandomSeed", Nbt::Long(42)),
+            ("WorldGenSettings", settings),
+        ]);
+        assert_eq!(both.unwrap().seed, Some(42));
+    }
+
+    #[test]
+    fn errors() {
+        match LevelData::from_nbt(Nbt::Int(0)) {
+            Err(LevelError::InvalidField("root")) => {}
+            other => panic!("{:?}", other),
+        }
+        match LevelData::from_nbt(compound(vec![])) {
+            Err(LevelError::MissingField("Data")) => {}
+            other => panic!("{:?}", other),
+        }
+        match LevelData::from_nbt(compound(vec![("Data", compound(vec![]))])) {
+            Err(LevelError::MissingField("SpawnX")) => {}
+            other => panic!("{:?}", other),
+        }
+        match level(vec![("RandomSeed", Nbt::Int(42))]) {
+            Err(LevelError::InvalidField("RandomSeed")) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+}
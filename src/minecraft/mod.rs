@@ -1,11 +1,17 @@
+pub mod atlas;
 pub mod biome;
 pub mod block_state;
-pub mod data_1_8_pre2;
+pub mod dimension;
+pub mod fluid;
+pub mod level;
 pub mod model;
+pub mod multipart;
 pub mod nbt;
+pub mod neighbor;
+pub mod player;
 pub mod region;
+pub mod registry;
 
-pub use crate::minecraft::data_1_8_pre2 as data;
 use std::env;
 use std::fs::{self, File};
 use std::io;
@@ -1,6 +1,6 @@
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
-use std::f32::consts::{PI, SQRT_2};
+use std::f32::consts::PI;
 use std::fs::File;
 use std::path::Path;
 use std::str::FromStr;
@@ -9,8 +9,9 @@ use self::OrthoRotation::*;
 
 use crate::array::*;
 use crate::cube;
-use gfx_voxel::texture::AtlasBuilder;
+use crate::minecraft::atlas::AtlasBuilder;
 use rustc_serialize::json;
+use vecmath::vec3_dot;
 
 #[derive(Copy, Clone)]
 pub struct Vertex {
@@ -26,6 +27,20 @@ pub enum Tint {
     Redstone,
 }
 
+/// How a face is drawn, depending on the alpha of its texture.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Layer {
+    Opaque,
+    // Pixels are either fully opaque or left out.
+    Cutout,
+    // Blended with whatever is behind.
+    Translucent,
+}
+
+impl Layer {
+    pub const ALL: [Layer; 3] = [Layer::Opaque, Layer::Cutout, Layer::Translucent];
+}
+
 #[derive(Copy, Clone)]
 pub enum OrthoRotation {
     Rotate0,
@@ -54,6 +69,77 @@ pub struct Face {
     pub tint: bool,
     pub cull_face: Option<cube::Face>,
     pub ao_face: Option<cube::Face>,
+    pub layer: Layer,
+}
+
+/// The rotation of an element around an axis through `origin`.
+#[derive(Copy, Clone)]
+struct ElementRotation {
+    origin: [f32; 3],
+    // The rotated coordinates, e.g. (2, 1) around x.
+    axes: (usize, usize),
+    // In radians, counterclockwise when looking down the axis.
+    angle: f32,
+    // Whether to stretch the element to keep it as wide as the block.
+    rescale: bool,
+}
+
+impl ElementRotation {
+    fn from_json(json: &json::Json) -> ElementRotation {
+        let angle = json.find("angle").unwrap().as_f64().unwrap();
+        let axes = match json.find("axis").unwrap().as_string().unwrap() {
+            "x" => (2, 1),
+            "y" => (0, 2),
+            "z" => (1, 0),
+            axis => panic!("invalid rotation axis {}", axis),
+        };
+        ElementRotation {
+            origin: array3_num(json.find("origin").unwrap(), |x| x as f32 / 16.0),
+            axes,
+            angle: angle as f32 / 180.0 * PI,
+            rescale: json
+                .find("rescale")
+                .is_some_and(|x| x.as_boolean().unwrap()),
+        }
+    }
+
+    fn rotate(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        let (s, c) = (self.angle.sin(), self.angle.cos());
+        [x * c + y * s, -x * s + y * c]
+    }
+
+    fn apply(&self, face: &mut Face) {
+        let (ix, iy) = self.axes;
+        // Like vanilla, 1 / cos(angle), e.g. √2 at 45°.
+        let scale = if self.rescale {
+            1.0 / self.angle.cos()
+        } else {
+            1.0
+        };
+        let (ox, oy) = (self.origin[ix], self.origin[iy]);
+        for v in face.vertices.iter_mut() {
+            let [x, y] = self.rotate([v.xyz[ix] - ox, v.xyz[iy] - oy]);
+            v.xyz[ix] = x * scale + ox;
+            v.xyz[iy] = y * scale + oy;
+        }
+
+        // Light the face like the side it is closest to facing,
+        // keeping the original one when halfway between two.
+        face.ao_face = face.ao_face.map(|ao_face| {
+            let mut normal = ao_face.direction().map(|x| x as f32);
+            let [x, y] = self.rotate([normal[ix], normal[iy]]);
+            normal[ix] = x;
+            normal[iy] = y;
+            let dot = |f: cube::Face| vec3_dot(normal, f.direction().map(|x| x as f32));
+            cube::FaceIterator::new().fold(ao_face, |best, f| {
+                if dot(f) > dot(best) + 1e-4 {
+                    f
+                } else {
+                    best
+                }
+            })
+        });
+    }
 }
 
 #[derive(Clone)]
@@ -252,47 +338,16 @@ impl PartialModel {
                             tint,
                             cull_face,
                             ao_face: Some(face),
+                            layer: Layer::Opaque,
                         },
                         tex,
                     ));
                 }
 
                 if let Some(r) = element.find("rotation") {
-                    let angle = r.find("angle").unwrap().as_f64().unwrap();
-                    let angle = angle as f32 / 180.0 * PI;
-                    let rescale = r.find("rescale").map_or(false, |x| x.as_boolean().unwrap());
-                    let origin = array3_num(r.find("origin").unwrap(), |x| x as f32 / 16.0);
-
-                    let (s, c) = (angle.sin(), angle.cos());
-                    let mut rot = |ix: usize, iy: usize| {
-                        for &mut (ref mut face, _) in model.faces[element_start..].iter_mut() {
-                            face.ao_face = None;
-
-                            let (ox, oy) = (origin[ix], origin[iy]);
-                            for v in face.vertices.iter_mut() {
-                                let (x, y) = (v.xyz[ix] - ox, v.xyz[iy] - oy);
-                                v.xyz[ix] = x * c + y * s;
-                                v.xyz[iy] = -x * s + y * c;
-                            }
-
-                            if rescale {
-                                for v in face.vertices.iter_mut() {
-                                    v.xyz[ix] *= SQRT_2;
-                                    v.xyz[iy] *= SQRT_2;
-                                }
-                            }
-
-                            for v in face.vertices.iter_mut() {
-                                v.xyz[ix] += ox;
-                                v.xyz[iy] += oy;
-                            }
-                        }
-                    };
-                    match r.find("axis").unwrap().as_string().unwrap() {
-                        "x" => rot(2, 1),
-                        "y" => rot(0, 2),
-                        "z" => rot(1, 0),
-                        axis => panic!("invalid rotation axis {}", axis),
+                    let rotation = ElementRotation::from_json(r);
+                    for &mut (ref mut face, _) in model.faces[element_start..].iter_mut() {
+                        rotation.apply(face);
                     }
                 }
             }
@@ -305,6 +360,22 @@ impl PartialModel {
     }
 }
 
+// The pixels of the atlas used by a face.
+fn texture_rect(face: &Face) -> [u32; 4] {
+    let (mut min_u, mut min_v) = (f32::INFINITY, f32::INFINITY);
+    let (mut max_u, mut max_v) = (0.0, 0.0);
+    for vertex in face.vertices.iter() {
+        let (u, v) = (vertex.uv[0], vertex.uv[1]);
+        min_u = u.min(min_u);
+        min_v = v.min(min_v);
+        max_u = u.max(max_u);
+        max_v = v.max(max_v);
+    }
+    let (u0, v0) = (min_u.floor() as u32, min_v.floor() as u32);
+    let (u1, v1) = (max_u.ceil() as u32, max_v.ceil() as u32);
+    [u0, v0, u1 - u0, v1 - v0]
+}
+
 impl Model {
     pub fn load(
         name: &str,
@@ -343,6 +414,14 @@ impl Model {
                     })
                     .collect();
 
+                for face in faces.iter_mut() {
+                    face.layer = match atlas.min_alpha(texture_rect(face)) {
+                        0 => Layer::Cutout,
+                        255 => Layer::Opaque,
+                        _ => Layer::Translucent,
+                    };
+                }
+
                 let mut full_faces = [Opacity::Transparent; 6];
                 if partial.full_faces.len() >= 6 {
                     for &i in partial.full_faces.iter() {
@@ -350,22 +429,10 @@ impl Model {
                         if full_faces[face] == Opacity::Opaque {
                             continue;
                         }
-                        let (mut min_u, mut min_v) = (f32::INFINITY, f32::INFINITY);
-                        let (mut max_u, mut max_v) = (0.0, 0.0);
-                        for vertex in faces[i].vertices.iter() {
-                            let (u, v) = (vertex.uv[0], vertex.uv[1]);
-                            min_u = u.min(min_u);
-                            min_v = v.min(min_v);
-                            max_u = u.max(max_u);
-                            max_v = v.max(max_v);
-                        }
-                        let (u0, v0) = (min_u.floor() as u32, min_v.floor() as u32);
-                        let (u1, v1) = (max_u.ceil() as u32, max_v.ceil() as u32);
-                        let rect = [u0, v0, u1 - u0, v1 - v0];
-                        let opacity = match atlas.min_alpha(rect) {
-                            0 => Opacity::TransparentSolid,
-                            255 => Opacity::Opaque,
-                            _ => Opacity::TranslucentSolid,
+                        let opacity = match faces[i].layer {
+                            Layer::Cutout => Opacity::TransparentSolid,
+                            Layer::Opaque => Opacity::Opaque,
+                            Layer::Translucent => Opacity::TranslucentSolid,
                         };
                         if full_faces[face] < opacity {
                             full_faces[face] = opacity;
@@ -431,4 +498,150 @@ impl Model {
     pub fn is_empty(&self) -> bool {
         self.faces.is_empty()
     }
+
+    /// Adds the faces of another model, e.g. from a multipart blockstate.
+    pub fn merge(&mut self, other: &Model) {
+        self.faces.extend(other.faces.iter().cloned());
+        self.opacity = self.opacity.max(other.opacity);
+        if let Tint::None = self.tint_source {
+            self.tint_source = other.tint_source;
+        }
+    }
+
+    /// The box enclosing all the faces, in block coordinates.
+    pub fn bounds(&self) -> Option<([f32; 3], [f32; 3])> {
+        let mut xyz = self
+            .faces
+            .iter()
+            .flat_map(|face| face.vertices.iter().map(|v| v.xyz));
+        let first = xyz.next()?;
+        Some(xyz.fold((first, first), |(min, max), v| {
+            (
+                Array::from_fn(|i| min[i].min(v[i])),
+                Array::from_fn(|i| max[i].max(v[i])),
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A face of an element of a vanilla model, in blocks.
+    fn element(from: [f32; 3], to: [f32; 3], face: cube::Face, rotation: &str) -> Face {
+        let from = from.map(|x| x / 16.0);
+        let to = to.map(|x| x / 16.0);
+        let scale = [to[0] - from[0], to[1] - from[1], to[2] - from[2]];
+        let xyz = face.vertices(from, scale);
+        let mut face = Face {
+            vertices: Array::from_fn(|i| Vertex {
+                xyz: xyz[i],
+                uv: [0.0, 0.0],
+            }),
+            tint: false,
+            cull_face: None,
+            ao_face: Some(face),
+            layer: Layer::Opaque,
+        };
+        ElementRotation::from_json(&json::Json::from_str(rotation).unwrap()).apply(&mut face);
+        face
+    }
+
+    fn assert_vertices(face: &Face, expected: &[[f32; 3]]) {
+        for v in face.vertices.iter() {
+            assert!(
+                expected
+                    .iter()
+                    .any(|e| (0..3).all(|i| (v.xyz[i] - e[i]).abs() < 1e-4)),
+                "unexpected vertex {:?}",
+                v.xyz
+            );
+        }
+    }
+
+    #[test]
+    fn cross() {
+        // block/cross.json, reaching the corners of the block.
+        let face = element(
+            [0.8, 0.0, 8.0],
+            [15.2, 16.0, 8.0],
+            cube::North,
+            r#"{"origin": [8, 8, 8], "axis": "y", "angle": 45, "rescale": true}"#,
+        );
+        assert_vertices(
+            &face,
+            &[
+                [0.05, 0.0, 0.95],
+                [0.05, 1.0, 0.95],
+                [0.95, 0.0, 0.05],
+                [0.95, 1.0, 0.05],
+            ],
+        );
+        // Halfway between north and west.
+        assert_eq!(face.ao_face, Some(cube::North));
+    }
+
+    #[test]
+    fn rescale_22_5() {
+        // Stretched by 1 / cos(22.5°) to span the block again.
+        let face = element(
+            [0.0, 0.0, 8.0],
+            [16.0, 16.0, 8.0],
+            cube::South,
+            r#"{"origin": [8, 8, 8], "axis": "y", "angle": 22.5, "rescale": true}"#,
+        );
+        let offset = 0.5 * (PI / 8.0).tan();
+        assert_vertices(
+            &face,
+            &[
+                [0.0, 0.0, 0.5 + offset],
+                [0.0, 1.0, 0.5 + offset],
+                [1.0, 0.0, 0.5 - offset],
+                [1.0, 1.0, 0.5 - offset],
+            ],
+        );
+        assert_eq!(face.ao_face, Some(cube::South));
+    }
+
+    #[test]
+    fn wall_torch() {
+        // block/torch_wall.json, leaning away from the wall.
+        let face = element(
+            [-1.0, 3.5, 7.0],
+            [1.0, 13.5, 9.0],
+            cube::West,
+            r#"{"origin": [0, 3.5, 8], "axis": "z", "angle": -22.5}"#,
+        );
+        assert_vertices(
+            &face,
+            &[
+                [-0.05774, 0.24267, 0.4375],
+                [-0.05774, 0.24267, 0.5625],
+                [0.18143, 0.82009, 0.4375],
+                [0.18143, 0.82009, 0.5625],
+            ],
+        );
+        assert_eq!(face.ao_face, Some(cube::West));
+    }
+
+    #[test]
+    fn arbitrary_angle() {
+        let face = element(
+            [0.0, 0.0, 8.0],
+            [16.0, 16.0, 8.0],
+            cube::North,
+            r#"{"origin": [8, 8, 8], "axis": "y", "angle": 60}"#,
+        );
+        assert_vertices(
+            &face,
+            &[
+                [0.25, 0.0, 0.93301],
+                [0.25, 1.0, 0.93301],
+                [0.75, 0.0, 0.06699],
+                [0.75, 1.0, 0.06699],
+            ],
+        );
+        assert_eq!(face.ao_face, Some(cube::West));
+    }
 }
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use rustc_serialize::json;
+
+/// The properties of a block state, e.g. `facing=north`.
+pub type Properties = BTreeMap<String, String>;
+
+/// Parses properties written like in blockstate variants,
+/// e.g. `facing=north,half=top`.
+pub fn parse_properties(variant: &str) -> Properties {
+    variant
+        .split(',')
+        .filter_map(|kv| {
+            let mut kv = kv.splitn(2, '=');
+            Some((kv.next()?.to_string(), kv.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// Writes properties back like in blockstate variants.
+pub fn format_properties(properties: &Properties) -> String {
+    let properties: Vec<_> = properties
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+    properties.join(",")
+}
+
+/// When a part of a multipart model is used.
+#[derive(Clone, Debug)]
+pub enum Condition {
+    // Each property has one of the listed values.
+    All(Vec<(String, Vec<String>)>),
+    Or(Vec<Condition>),
+    And(Vec<Condition>),
+}
+
+impl Condition {
+    pub fn from_json(json: &json::Json) -> Condition {
+        let object = match json.as_object() {
+            Some(object) => object,
+            None => panic!("invalid multipart condition {}", json),
+        };
+        let list = |json: &json::Json| match json.as_array() {
+            Some(list) => list.iter().map(Condition::from_json).collect(),
+            None => panic!("invalid multipart condition list {}", json),
+        };
+        if let Some(or) = object.get("OR") {
+            return Condition::Or(list(or));
+        }
+        if let Some(and) = object.get("AND") {
+            return Condition::And(list(and));
+        }
+        Condition::All(
+            object
+                .iter()
+                .map(|(k, v)| {
+                    let values = match *v {
+                        json::Json::String(ref s) => s.clone(),
+                        ref v => v.to_string(),
+                    };
+                    (
+                        k.clone(),
+                        values.split('|').map(|v| v.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    pub fn matches(&self, properties: &Properties) -> bool {
+        match *self {
+            Condition::All(ref values) => values.iter().all(|(k, values)| {
+                properties
+                    .get(k)
+                    .is_some_and(|v| values.iter().any(|x| x == v))
+            }),
+            Condition::Or(ref conditions) => conditions.iter().any(|c| c.matches(properties)),
+            Condition::And(ref conditions) => conditions.iter().all(|c| c.matches(properties)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(json: &str) -> Condition {
+        Condition::from_json(&json::Json::from_str(json).unwrap())
+    }
+
+    fn matches(condition: &Condition, properties: &str) -> bool {
+        condition.matches(&parse_properties(properties))
+    }
+
+    #[test]
+    fn properties() {
+        let properties = parse_properties("north=true,east=false,normal");
+        assert_eq!(properties.len(), 2);
+        assert_eq!(properties["north"], "true");
+        assert_eq!(format_properties(&properties), "east=false,north=true");
+        assert!(parse_properties("normal").is_empty());
+    }
+
+    #[test]
+    fn all() {
+        let c = condition(r#"{"facing": "north", "half": "top"}"#);
+        assert!(matches(&c, "facing=north,half=top,shape=straight"));
+        assert!(!matches(&c, "facing=north,half=bottom"));
+        // Missing properties don't match.
+        assert!(!matches(&c, "facing=north"));
+        // An empty condition matches anything.
+        assert!(matches(&condition("{}"), ""));
+    }
+
+    #[test]
+    fn alternatives() {
+        let c = condition(r#"{"east": "side|up"}"#);
+        assert!(matches(&c, "east=side"));
+        assert!(matches(&c, "east=up"));
+        assert!(!matches(&c, "east=none"));
+    }
+
+    #[test]
+    fn non_string_values() {
+        let c = condition(r#"{"north": true, "level": 3}"#);
+        assert!(matches(&c, "level=3,north=true"));
+        assert!(!matches(&c, "level=3,north=false"));
+    }
+
+    #[test]
+    fn or() {
+        let c = condition(
+            r#"{"OR": [
+                {"north": "none", "east": "none"},
+                {"north": "side|up"}
+            ]}"#,
+        );
+        assert!(matches(&c, "east=none,north=none"));
+        assert!(matches(&c, "east=side,north=up"));
+        assert!(!matches(&c, "east=side,north=none"));
+    }
+
+    #[test]
+    fn and() {
+        let c = condition(
+            r#"{"AND": [
+                {"OR": [{"north": "true"}, {"south": "true"}]},
+                {"up": "false"}
+            ]}"#,
+        );
+        assert!(matches(&c, "north=false,south=true,up=false"));
+        assert!(!matches(&c, "north=true,south=true,up=true"));
+        assert!(!matches(&c, "north=false,south=false,up=false"));
+    }
+}
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 use std::fmt;
 use std::io;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::ops::Index;
 use std::string::{self, ToString};
 
-use byteorder::{BigEndian, ReadBytesExt};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
 use rustc_serialize;
 use rustc_serialize::hex::ToHex;
 
@@ -98,6 +100,38 @@ impl Nbt {
         Nbt::from_reader(reader)
     }
 
+    pub fn to_writer<W: Write>(&self, name: &str, w: W) -> io::Result<()> {
+        NbtWriter::new(w).tag(name, self)
+    }
+
+    pub fn to_gzip(&self, name: &str) -> io::Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        self.to_writer(name, &mut encoder)?;
+        encoder.finish()
+    }
+
+    pub fn to_zlib(&self, name: &str) -> io::Result<Vec<u8>> {
+        let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+        self.to_writer(name, &mut encoder)?;
+        encoder.finish()
+    }
+
+    fn tag_type(&self) -> i8 {
+        match *self {
+            Nbt::Byte(_) => TAG_BYTE,
+            Nbt::Short(_) => TAG_SHORT,
+            Nbt::Int(_) => TAG_INT,
+            Nbt::Long(_) => TAG_LONG,
+            Nbt::Float(_) => TAG_FLOAT,
+            Nbt::Double(_) => TAG_DOUBLE,
+            Nbt::ByteArray(_) => TAG_BYTE_ARRAY,
+            Nbt::IntArray(_) => TAG_INT_ARRAY,
+            Nbt::String(_) => TAG_STRING,
+            Nbt::List(_) => TAG_LIST,
+            Nbt::Compound(_) => TAG_COMPOUND,
+        }
+    }
+
     pub fn as_byte(&self) -> Option<i8> {
         match *self {
             Nbt::Byte(b) => Some(b),
@@ -105,6 +139,55 @@ impl Nbt {
         }
     }
 
+    pub fn as_short(&self) -> Option<i16> {
+        match *self {
+            Nbt::Short(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i32> {
+        match *self {
+            Nbt::Int(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    pub fn as_long(&self) -> Option<i64> {
+        match *self {
+            Nbt::Long(l) => Some(l),
+            _ => None,
+        }
+    }
+
+    pub fn as_string(&self) -> Option<&str> {
+        match *self {
+            Nbt::String(ref s) => Some(&s[..]),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound(&self) -> Option<&Compound> {
+        match *self {
+            Nbt::Compound(ref c) => Some(c),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound_mut(&mut self) -> Option<&mut Compound> {
+        match *self {
+            Nbt::Compound(ref mut c) => Some(c),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound_list(&self) -> Option<&[Compound]> {
+        match *self {
+            Nbt::List(List::Compound(ref c)) => Some(&c[..]),
+            _ => None,
+        }
+    }
+
     pub fn into_compound(self) -> Result<Compound, Nbt> {
         match self {
             Nbt::Compound(c) => Ok(c),
@@ -126,6 +209,13 @@ impl Nbt {
         }
     }
 
+    pub fn as_intarray(&self) -> Option<&[i32]> {
+        match *self {
+            Nbt::IntArray(ref i) => Some(&i[..]),
+            _ => None,
+        }
+    }
+
     pub fn into_bytearray(self) -> Result<Vec<u8>, Nbt> {
         match self {
             Nbt::ByteArray(b) => Ok(b),
@@ -320,6 +410,111 @@ impl<R: Read> NbtReader<R> {
     }
 }
 
+pub struct NbtWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> NbtWriter<W> {
+    pub fn new(writer: W) -> NbtWriter<W> {
+        NbtWriter { writer }
+    }
+
+    fn string(&mut self, s: &str) -> io::Result<()> {
+        self.writer.write_u16::<BigEndian>(s.len() as u16)?;
+        self.writer.write_all(s.as_bytes())
+    }
+
+    fn array<T, F>(&mut self, values: &[T], mut write: F) -> io::Result<()>
+    where
+        F: FnMut(&mut NbtWriter<W>, &T) -> io::Result<()>,
+    {
+        self.writer.write_i32::<BigEndian>(values.len() as i32)?;
+        for value in values {
+            write(self, value)?;
+        }
+        Ok(())
+    }
+
+    fn compound(&mut self, c: &Compound) -> io::Result<()> {
+        // Sorted, so that the same data is always written the same way.
+        let mut names: Vec<_> = c.keys().collect();
+        names.sort();
+        for name in names {
+            self.tag(name, &c[name])?;
+        }
+        self.writer.write_i8(TAG_END)
+    }
+
+    fn list(&mut self, list: &List) -> io::Result<()> {
+        match *list {
+            List::Byte(ref v) => {
+                self.writer.write_i8(TAG_BYTE)?;
+                self.array(v, |w, &x| w.writer.write_i8(x))
+            }
+            List::Short(ref v) => {
+                self.writer.write_i8(TAG_SHORT)?;
+                self.array(v, |w, &x| w.writer.write_i16::<BigEndian>(x))
+            }
+            List::Int(ref v) => {
+                self.writer.write_i8(TAG_INT)?;
+                self.array(v, |w, &x| w.writer.write_i32::<BigEndian>(x))
+            }
+            List::Long(ref v) => {
+                self.writer.write_i8(TAG_LONG)?;
+                self.array(v, |w, &x| w.writer.write_i64::<BigEndian>(x))
+            }
+            List::Float(ref v) => {
+                self.writer.write_i8(TAG_FLOAT)?;
+                self.array(v, |w, &x| w.writer.write_f32::<BigEndian>(x))
+            }
+            List::Double(ref v) => {
+                self.writer.write_i8(TAG_DOUBLE)?;
+                self.array(v, |w, &x| w.writer.write_f64::<BigEndian>(x))
+            }
+            List::ByteArray(ref v) => {
+                self.writer.write_i8(TAG_BYTE_ARRAY)?;
+                self.array(v, |w, x| w.array(x, |w, &b| w.writer.write_u8(b)))
+            }
+            List::IntArray(ref v) => {
+                self.writer.write_i8(TAG_INT_ARRAY)?;
+                self.array(v, |w, x| {
+                    w.array(x, |w, &i| w.writer.write_i32::<BigEndian>(i))
+                })
+            }
+            List::String(ref v) => {
+                self.writer.write_i8(TAG_STRING)?;
+                self.array(v, |w, x| w.string(x))
+            }
+            List::List(ref v) => {
+                self.writer.write_i8(TAG_LIST)?;
+                self.array(v, |w, x| w.list(x))
+            }
+            List::Compound(ref v) => {
+                self.writer.write_i8(TAG_COMPOUND)?;
+                self.array(v, |w, x| w.compound(x))
+            }
+        }
+    }
+
+    pub fn tag(&mut self, name: &str, nbt: &Nbt) -> io::Result<()> {
+        self.writer.write_i8(nbt.tag_type())?;
+        self.string(name)?;
+        match *nbt {
+            Nbt::Byte(x) => self.writer.write_i8(x),
+            Nbt::Short(x) => self.writer.write_i16::<BigEndian>(x),
+            Nbt::Int(x) => self.writer.write_i32::<BigEndian>(x),
+            Nbt::Long(x) => self.writer.write_i64::<BigEndian>(x),
+            Nbt::Float(x) => self.writer.write_f32::<BigEndian>(x),
+            Nbt::Double(x) => self.writer.write_f64::<BigEndian>(x),
+            Nbt::ByteArray(ref x) => self.array(x, |w, &b| w.writer.write_u8(b)),
+            Nbt::IntArray(ref x) => self.array(x, |w, &i| w.writer.write_i32::<BigEndian>(i)),
+            Nbt::String(ref x) => self.string(x),
+            Nbt::List(ref x) => self.list(x),
+            Nbt::Compound(ref x) => self.compound(x),
+        }
+    }
+}
+
 /// A structure to decode NBT to values in rust.
 pub struct Decoder {
     stack: Vec<DecodeResult<Nbt>>,
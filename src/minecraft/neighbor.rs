@@ -0,0 +1,549 @@
+use crate::chunk::BlockState;
+use crate::minecraft::block_state::Dir;
+use crate::minecraft::multipart::Properties;
+
+/// Kinds of blocks which connect to their neighbors, or which
+/// other blocks connect to.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Group {
+    None,
+    WoodenFence,
+    NetherBrickFence,
+    FenceGate,
+    Wall,
+    // Glass panes and iron bars.
+    Pane,
+    Glass,
+    RedstoneWire,
+    Repeater,
+    // Other blocks redstone wire points at, e.g. torches and levers.
+    RedstoneSource,
+    // Opaque, but fences and walls don't connect to them.
+    Gourd,
+    Stairs,
+}
+
+impl Group {
+    pub fn of(name: &str) -> Group {
+        match name {
+            "nether_brick_fence" => Group::NetherBrickFence,
+            n if n.ends_with("fence_gate") => Group::FenceGate,
+            n if n.ends_with("fence") => Group::WoodenFence,
+            n if n.ends_with("_wall") => Group::Wall,
+            n if n == "iron_bars" || n.ends_with("glass_pane") => Group::Pane,
+            n if n == "glass" || n.ends_with("stained_glass") => Group::Glass,
+            "redstone_wire" => Group::RedstoneWire,
+            n if n.ends_with("repeater") => Group::Repeater,
+            "redstone_torch"
+            | "unlit_redstone_torch"
+            | "redstone_wall_torch"
+            | "lever"
+            | "redstone_block"
+            | "tripwire_hook"
+            | "detector_rail"
+            | "trapped_chest" => Group::RedstoneSource,
+            n if n.ends_with("button")
+                || n.ends_with("pressure_plate")
+                || n.ends_with("comparator")
+                || n.starts_with("daylight_detector") =>
+            {
+                Group::RedstoneSource
+            }
+            "pumpkin" | "lit_pumpkin" | "carved_pumpkin" | "jack_o_lantern" | "melon_block"
+            | "melon" => Group::Gourd,
+            n if n.ends_with("_stairs") => Group::Stairs,
+            _ => Group::None,
+        }
+    }
+}
+
+/// Derives a property of a block from its neighbors, like vanilla's
+/// "actual state", which isn't stored in the world.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum NeighborRule {
+    // "true" if the block connects to its neighbor, "false" otherwise.
+    Connects(Dir),
+    // Whether a wall has a post: "false" only for straight walls
+    // with nothing above them.
+    WallPost,
+    // "side" or "up" if redstone wire connects to the side,
+    // going up if the wire next to it is a block higher, or "none".
+    Redstone(Dir),
+    // "straight", or an inner or outer corner when other stairs
+    // are turned sideways behind or in front.
+    StairShape,
+}
+
+impl NeighborRule {
+    /// The rule for a property of blocks in `group`, if any.
+    pub fn for_property(group: Group, name: &str) -> Option<NeighborRule> {
+        match (group, name) {
+            (Group::Wall, "up") => return Some(NeighborRule::WallPost),
+            (Group::Stairs, "shape") => return Some(NeighborRule::StairShape),
+            _ => {}
+        }
+        let dir = horizontal(name)?;
+        match group {
+            Group::WoodenFence | Group::NetherBrickFence | Group::Wall | Group::Pane => {
+                Some(NeighborRule::Connects(dir))
+            }
+            Group::RedstoneWire => Some(NeighborRule::Redstone(dir)),
+            _ => None,
+        }
+    }
+}
+
+/// What the neighbor rules need to know about blocks.
+pub trait BlockGroups {
+    fn group(&self, block: BlockState) -> Group;
+
+    fn is_opaque(&self, block: BlockState) -> bool;
+
+    /// The properties stored for a block, e.g. the facing of repeaters.
+    fn properties(&self, block: BlockState) -> Option<&Properties>;
+}
+
+// Whether a block of `group` connects to `other`, like vanilla.
+fn connects<B: BlockGroups>(blocks: &B, group: Group, other: BlockState) -> bool {
+    let other_group = blocks.group(other);
+    let in_group = match group {
+        Group::WoodenFence | Group::NetherBrickFence => {
+            other_group == group || other_group == Group::FenceGate
+        }
+        Group::Wall => other_group == Group::Wall || other_group == Group::FenceGate,
+        Group::Pane => other_group == Group::Pane || other_group == Group::Glass,
+        _ => false,
+    };
+    in_group || blocks.is_opaque(other) && (group == Group::Pane || other_group != Group::Gourd)
+}
+
+// Whether redstone wire points at `other`, next to it towards `dir`.
+fn powers<B: BlockGroups>(blocks: &B, other: BlockState, dir: Dir) -> bool {
+    match blocks.group(other) {
+        Group::RedstoneWire | Group::RedstoneSource => true,
+        // Only from the front and the back.
+        Group::Repeater => {
+            let axis: &[&str] = match dir {
+                Dir::North | Dir::South => &["north", "south"],
+                _ => &["west", "east"],
+            };
+            blocks
+                .properties(other)
+                .and_then(|p| p.get("facing"))
+                .is_some_and(|facing| axis.contains(&&facing[..]))
+        }
+        _ => false,
+    }
+}
+
+fn horizontal(name: &str) -> Option<Dir> {
+    match name {
+        "north" => Some(Dir::North),
+        "south" => Some(Dir::South),
+        "west" => Some(Dir::West),
+        "east" => Some(Dir::East),
+        _ => None,
+    }
+}
+
+fn opposite(dir: Dir) -> Dir {
+    match dir {
+        Dir::North => Dir::South,
+        Dir::South => Dir::North,
+        Dir::West => Dir::East,
+        _ => Dir::West,
+    }
+}
+
+// Turned 90° counterclockwise, seen from above.
+fn rotate_ccw(dir: Dir) -> Dir {
+    match dir {
+        Dir::North => Dir::West,
+        Dir::West => Dir::South,
+        Dir::South => Dir::East,
+        _ => Dir::North,
+    }
+}
+
+// The shape of stairs, like vanilla: `facing` is towards their back.
+fn stair_shape<B, A>(blocks: &B, at: &A) -> &'static str
+where
+    B: BlockGroups,
+    A: Fn([i32; 3]) -> BlockState,
+{
+    // The facing of stairs, and whether they're upside down.
+    let stairs = |pos: [i32; 3]| {
+        let block = at(pos);
+        if blocks.group(block) != Group::Stairs {
+            return None;
+        }
+        let properties = blocks.properties(block)?;
+        let facing = horizontal(properties.get("facing")?)?;
+        Some((facing, properties.get("half")? == "top"))
+    };
+    let (facing, top) = match stairs([0, 0, 0]) {
+        Some(stairs) => stairs,
+        None => return "straight",
+    };
+    let same_axis = |a: Dir, b: Dir| (a.xyz()[0] == 0) == (b.xyz()[0] == 0);
+    let different = |dir: Dir| stairs(dir.xyz()) != Some((facing, top));
+    // 1.8 swaps left and right for upside-down stairs.
+    let left = |dir: Dir| (dir == rotate_ccw(facing)) != top;
+
+    if let Some((behind, behind_top)) = stairs(facing.xyz()) {
+        if behind_top == top && !same_axis(behind, facing) && different(opposite(behind)) {
+            return if left(behind) {
+                "outer_left"
+            } else {
+                "outer_right"
+            };
+        }
+    }
+    if let Some((front, front_top)) = stairs(opposite(facing).xyz()) {
+        if front_top == top && !same_axis(front, facing) && different(front) {
+            return if left(front) {
+                "inner_left"
+            } else {
+                "inner_right"
+            };
+        }
+    }
+    "straight"
+}
+
+fn offset(dir: Dir, dy: i32) -> [i32; 3] {
+    let [x, y, z] = dir.xyz();
+    [x, y + dy, z]
+}
+
+/// Sets the properties of a block which follow `rules`,
+/// with `at` returning blocks by offset from it.
+pub fn actual_properties<B, A>(
+    blocks: &B,
+    rules: &[(String, NeighborRule)],
+    at: A,
+    properties: &mut Properties,
+) where
+    B: BlockGroups,
+    A: Fn([i32; 3]) -> BlockState,
+{
+    let group = blocks.group(at([0, 0, 0]));
+    let connects = |dir: Dir| connects(blocks, group, at(dir.xyz()));
+    for &(ref name, rule) in rules.iter() {
+        let value = match rule {
+            NeighborRule::Connects(dir) => connects(dir).to_string(),
+            NeighborRule::WallPost => {
+                let [n, s, w, e] = [Dir::North, Dir::South, Dir::West, Dir::East].map(connects);
+                let straight = n && s && !w && !e || !n && !s && w && e;
+                let air_above = at(Dir::Up.xyz()).value == 0;
+                (!straight || !air_above).to_string()
+            }
+            NeighborRule::Redstone(dir) => {
+                let side = at(dir.xyz());
+                let is_wire = |b| blocks.group(b) == Group::RedstoneWire;
+                if powers(blocks, side, dir)
+                    || !blocks.is_opaque(side) && is_wire(at(offset(dir, -1)))
+                {
+                    "side"
+                } else if blocks.is_opaque(side)
+                    && !blocks.is_opaque(at(Dir::Up.xyz()))
+                    && is_wire(at(offset(dir, 1)))
+                {
+                    "up"
+                } else {
+                    "none"
+                }
+                .to_string()
+            }
+            NeighborRule::StairShape => stair_shape(blocks, &at).to_string(),
+        };
+        properties.insert(name.clone(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::minecraft::multipart::parse_properties;
+
+    const AIR: BlockState = BlockState { value: 0 };
+    const STONE: BlockState = BlockState { value: 1 << 4 };
+    const GLASS: BlockState = BlockState { value: 20 << 4 };
+    const WIRE: BlockState = BlockState { value: 55 << 4 };
+    const TORCH: BlockState = BlockState { value: 76 << 4 | 5 };
+    const FENCE: BlockState = BlockState { value: 85 << 4 };
+    const PUMPKIN: BlockState = BlockState { value: 86 << 4 };
+    const REPEATER_SOUTH: BlockState = BlockState { value: 93 << 4 };
+    const REPEATER_WEST: BlockState = BlockState { value: 93 << 4 | 1 };
+    const IRON_BARS: BlockState = BlockState { value: 101 << 4 };
+    const PANE: BlockState = BlockState { value: 102 << 4 };
+    const GATE: BlockState = BlockState { value: 107 << 4 };
+    const NETHER_BRICK_FENCE: BlockState = BlockState { value: 113 << 4 };
+    const WALL: BlockState = BlockState { value: 139 << 4 };
+
+    // Stairs with their back towards `facing`, upside down if `top`,
+    // with the metadata of 1.8.
+    fn stairs(facing: &str, top: bool) -> BlockState {
+        let meta = ["east", "west", "south", "north"]
+            .iter()
+            .position(|&f| f == facing)
+            .unwrap() as u16;
+        BlockState {
+            value: 53 << 4 | meta | if top { 4 } else { 0 },
+        }
+    }
+
+    struct Blocks(HashMap<u16, Properties>);
+
+    impl Blocks {
+        fn new() -> Blocks {
+            let mut properties = HashMap::new();
+            properties.insert(REPEATER_SOUTH.value, parse_properties("facing=south"));
+            properties.insert(REPEATER_WEST.value, parse_properties("facing=west"));
+            for &facing in ["east", "west", "south", "north"].iter() {
+                for &(top, half) in [(false, "bottom"), (true, "top")].iter() {
+                    // What 1.8 gives for all stairs, which doesn't matter.
+                    let variant = format!("facing={},half={},shape=outer_right", facing, half);
+                    properties.insert(stairs(facing, top).value, parse_properties(&variant));
+                }
+            }
+            Blocks(properties)
+        }
+    }
+
+    impl BlockGroups for Blocks {
+        fn group(&self, block: BlockState) -> Group {
+            Group::of(match block.value >> 4 {
+                1 => "stone",
+                20 => "glass",
+                55 => "redstone_wire",
+                76 => "redstone_torch",
+                85 => "fence",
+                86 => "pumpkin",
+                93 => "unpowered_repeater",
+                101 => "iron_bars",
+                102 => "glass_pane",
+                107 => "fence_gate",
+                113 => "nether_brick_fence",
+                139 => "cobblestone_wall",
+                53 => "oak_stairs",
+                _ => "air",
+            })
+        }
+
+        fn is_opaque(&self, block: BlockState) -> bool {
+            block == STONE || block == PUMPKIN
+        }
+
+        fn properties(&self, block: BlockState) -> Option<&Properties> {
+            self.0.get(&block.value)
+        }
+    }
+
+    // The properties of the block at the origin of a grid of blocks,
+    // which are air where not given.
+    fn actual(grid: &[([i32; 3], BlockState)]) -> Properties {
+        let grid: HashMap<_, _> = grid.iter().cloned().collect();
+        let at = |pos: [i32; 3]| grid.get(&pos).cloned().unwrap_or(AIR);
+        let blocks = Blocks::new();
+        let group = blocks.group(at([0, 0, 0]));
+        let rules: Vec<_> = ["north", "south", "west", "east", "up", "shape"]
+            .iter()
+            .filter_map(|&name| {
+                NeighborRule::for_property(group, name).map(|rule| (name.to_string(), rule))
+            })
+            .collect();
+        let mut properties = Properties::new();
+        actual_properties(&blocks, &rules, at, &mut properties);
+        properties
+    }
+
+    #[test]
+    fn fence_connects_to_fences_gates_and_solid_blocks() {
+        let properties = actual(&[
+            ([0, 0, 0], FENCE),
+            ([0, 0, -1], FENCE),
+            ([0, 0, 1], STONE),
+            ([-1, 0, 0], PUMPKIN),
+            ([1, 0, 0], GATE),
+        ]);
+        assert_eq!(
+            properties,
+            parse_properties("east=true,north=true,south=true,west=false")
+        );
+    }
+
+    #[test]
+    fn nether_brick_fence_only_connects_to_its_own_kind() {
+        let properties = actual(&[
+            ([0, 0, 0], NETHER_BRICK_FENCE),
+            ([0, 0, -1], FENCE),
+            ([0, 0, 1], NETHER_BRICK_FENCE),
+        ]);
+        assert_eq!(
+            properties,
+            parse_properties("east=false,north=false,south=true,west=false")
+        );
+    }
+
+    #[test]
+    fn panes_connect_to_panes_bars_and_glass() {
+        let properties = actual(&[
+            ([0, 0, 0], PANE),
+            ([0, 0, -1], IRON_BARS),
+            ([0, 0, 1], GLASS),
+            ([-1, 0, 0], PUMPKIN),
+            ([1, 0, 0], FENCE),
+        ]);
+        assert_eq!(
+            properties,
+            parse_properties("east=false,north=true,south=true,west=true")
+        );
+    }
+
+    #[test]
+    fn straight_walls_only_have_posts_below_blocks() {
+        let straight = [([0, 0, 0], WALL), ([0, 0, -1], WALL), ([0, 0, 1], GATE)];
+        assert_eq!(actual(&straight)["up"], "false");
+
+        let below_torch = [&straight[..], &[([0, 1, 0], TORCH)]].concat();
+        assert_eq!(actual(&below_torch)["up"], "true");
+
+        let corner = [([0, 0, 0], WALL), ([0, 0, -1], WALL), ([1, 0, 0], STONE)];
+        assert_eq!(actual(&corner)["up"], "true");
+
+        assert_eq!(actual(&[([0, 0, 0], WALL)])["up"], "true");
+    }
+
+    #[test]
+    fn redstone_connects_to_wire_and_sources() {
+        let properties = actual(&[
+            ([0, 0, 0], WIRE),
+            ([0, 0, -1], WIRE),
+            ([0, 0, 1], REPEATER_SOUTH),
+            ([-1, 0, 0], TORCH),
+            ([1, 0, 0], REPEATER_SOUTH),
+        ]);
+        assert_eq!(
+            properties,
+            parse_properties("east=none,north=side,south=side,west=side")
+        );
+        let properties = actual(&[([0, 0, 0], WIRE), ([1, 0, 0], REPEATER_WEST)]);
+        assert_eq!(properties["east"], "side");
+    }
+
+    #[test]
+    fn redstone_goes_up_and_down_blocks() {
+        let grid = [
+            ([0, 0, 0], WIRE),
+            // Up onto a block.
+            ([0, 0, -1], STONE),
+            ([0, 1, -1], WIRE),
+            // Down from the side.
+            ([0, -1, 1], WIRE),
+            // Not through a block.
+            ([1, 0, 0], STONE),
+            ([1, -1, 0], WIRE),
+        ];
+        assert_eq!(
+            actual(&grid),
+            parse_properties("east=none,north=up,south=side,west=none")
+        );
+
+        // A block above cuts the wire going up.
+        let covered = [&grid[..], &[([0, 1, 0], STONE)]].concat();
+        assert_eq!(actual(&covered)["north"], "none");
+    }
+
+    fn shape(grid: &[([i32; 3], BlockState)]) -> String {
+        actual(grid)["shape"].clone()
+    }
+
+    #[test]
+    fn lone_stairs_are_straight() {
+        assert_eq!(shape(&[([0, 0, 0], stairs("east", false))]), "straight");
+        let row = [
+            ([0, 0, -1], stairs("east", false)),
+            ([0, 0, 0], stairs("east", false)),
+            ([0, 0, 1], stairs("east", false)),
+        ];
+        assert_eq!(shape(&row), "straight");
+    }
+
+    #[test]
+    fn stairs_turned_behind_make_outer_corners() {
+        let left = [
+            ([0, 0, 0], stairs("east", false)),
+            ([1, 0, 0], stairs("north", false)),
+        ];
+        assert_eq!(shape(&left), "outer_left");
+        let right = [
+            ([0, 0, 0], stairs("east", false)),
+            ([1, 0, 0], stairs("south", false)),
+        ];
+        assert_eq!(shape(&right), "outer_right");
+        let top = [
+            ([0, 0, 0], stairs("north", true)),
+            ([0, 0, -1], stairs("east", true)),
+        ];
+        // Mirrored like in 1.8.
+        assert_eq!(shape(&top), "outer_left");
+    }
+
+    #[test]
+    fn stairs_turned_in_front_make_inner_corners() {
+        let left = [
+            ([0, 0, 0], stairs("east", false)),
+            ([-1, 0, 0], stairs("north", false)),
+        ];
+        assert_eq!(shape(&left), "inner_left");
+        let right = [
+            ([0, 0, 0], stairs("east", false)),
+            ([-1, 0, 0], stairs("south", false)),
+        ];
+        assert_eq!(shape(&right), "inner_right");
+        let top = [
+            ([0, 0, 0], stairs("west", true)),
+            ([1, 0, 0], stairs("north", true)),
+        ];
+        assert_eq!(shape(&top), "inner_left");
+    }
+
+    #[test]
+    fn stairs_only_turn_with_the_same_half() {
+        let grid = [
+            ([0, 0, 0], stairs("east", false)),
+            ([1, 0, 0], stairs("north", true)),
+            ([-1, 0, 0], stairs("south", true)),
+        ];
+        assert_eq!(shape(&grid), "straight");
+    }
+
+    #[test]
+    fn stairs_stay_straight_next_to_the_same_stairs() {
+        // Continuing the row to the south instead of turning.
+        let grid = [
+            ([0, 0, 0], stairs("east", false)),
+            ([1, 0, 0], stairs("north", false)),
+            ([0, 0, 1], stairs("east", false)),
+        ];
+        assert_eq!(shape(&grid), "straight");
+        let grid = [
+            ([0, 0, 0], stairs("east", false)),
+            ([-1, 0, 0], stairs("north", false)),
+            ([0, 0, -1], stairs("east", false)),
+        ];
+        assert_eq!(shape(&grid), "straight");
+    }
+
+    #[test]
+    fn other_blocks_have_no_rules() {
+        assert!(NeighborRule::for_property(Group::None, "north").is_none());
+        assert!(NeighborRule::for_property(Group::Glass, "north").is_none());
+        assert!(NeighborRule::for_property(Group::WoodenFence, "up").is_none());
+        assert!(NeighborRule::for_property(Group::RedstoneWire, "power").is_none());
+        assert!(NeighborRule::for_property(Group::Stairs, "north").is_none());
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::path::Path;
+
+use crate::minecraft::dimension::Dimension;
+use crate::minecraft::level::LevelData;
+use crate::minecraft::nbt::{Compound, Nbt};
+use flate2::read::GzDecoder;
+use rustc_serialize::json;
+
+#[derive(Clone, Debug)]
+pub struct PlayerData {
+    pub uuid: Option<String>,
+    pub name: Option<String>,
+    pub pos: [f64; 3],
+    // Yaw and pitch, in degrees.
+    pub rotation: [f32; 2],
+    pub dimension: Dimension,
+}
+
+impl PlayerData {
+    pub fn from_compound(player: &Compound) -> Option<PlayerData> {
+        let pos = player.get("Pos").and_then(|p| p.as_double_list())?;
+        let rotation = player.get("Rotation").and_then(|r| r.as_float_list())?;
+        if pos.len() != 3 || rotation.len() != 2 {
+            return None;
+        }
+        Some(PlayerData {
+            uuid: None,
+            name: player
+                .get("bukkit")
+                .and_then(|b| b.as_compound())
+                .and_then(|b| b.get("lastKnownName"))
+                .and_then(|n| n.as_string())
+                .map(|n| n.to_string()),
+            pos: [pos[0], pos[1], pos[2]],
+            rotation: [rotation[0], rotation[1]],
+            dimension: player
+                .get("Dimension")
+                .and_then(Dimension::from_nbt)
+                .unwrap_or(Dimension::Overworld),
+        })
+    }
+
+    /// A player standing at the world spawn.
+    pub fn at_spawn(level: &LevelData) -> PlayerData {
+        let [x, y, z] = level.spawn;
+        PlayerData {
+            uuid: None,
+            name: None,
+            pos: [x as f64 + 0.5, y as f64, z as f64 + 0.5],
+            rotation: [0.0, 0.0],
+            dimension: Dimension::Overworld,
+        }
+    }
+
+    /// Matches either the UUID (with or without dashes) or the name.
+    pub fn matches(&self, query: &str) -> bool {
+        let simplify = |s: &str| s.replace('-', "").to_lowercase();
+        self.uuid
+            .as_ref()
+            .is_some_and(|uuid| simplify(uuid) == simplify(query))
+            || self
+                .name
+                .as_ref()
+                .is_some_and(|name| name.eq_ignore_ascii_case(query))
+    }
+}
+
+impl fmt::Display for PlayerData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.name, &self.uuid) {
+            (Some(name), Some(uuid)) => write!(f, "{} ({})", name, uuid)?,
+            (Some(name), None) => write!(f, "{}", name)?,
+            (None, Some(uuid)) => write!(f, "{}", uuid)?,
+            (None, None) => write!(f, "<unnamed>")?,
+        }
+        write!(
+            f,
+            " at {:.1}, {:.1}, {:.1} in {}",
+            self.pos[0], self.pos[1], self.pos[2], self.dimension
+        )
+    }
+}
+
+fn read_player(path: &Path) -> Option<PlayerData> {
+    let reader = GzDecoder::new(File::open(path).ok()?);
+    let nbt = Nbt::from_reader(reader).ok()?;
+    PlayerData::from_compound(nbt.as_compound()?)
+}
+
+// Servers keep the UUID to name mapping next to the world folder.
+fn user_cache(world: &Path) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    let path = match world.parent() {
+        Some(server) => server.join("usercache.json"),
+        None => return names,
+    };
+    let json = match File::open(&path).map(|mut f| json::Json::from_reader(&mut f)) {
+        Ok(Ok(json)) => json,
+        _ => return names,
+    };
+    for entry in json.as_array().into_iter().flatten() {
+        let uuid = entry.find("uuid").and_then(|x| x.as_string());
+        let name = entry.find("name").and_then(|x| x.as_string());
+        if let (Some(uuid), Some(name)) = (uuid, name) {
+            names.insert(uuid.to_lowercase(), name.to_string());
+        }
+    }
+    names
+}
+
+/// Reads `playerdata/<uuid>.dat` and the pre-1.7.6 `players/<name>.dat`.
+pub fn load_players(world: &Path) -> Vec<PlayerData> {
+    let names = user_cache(world);
+    let mut players = vec![];
+    for &(dir, by_uuid) in [("playerdata", true), ("players", false)].iter() {
+        let entries = match fs::read_dir(world.join(dir)) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "dat"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let stem = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(stem) => stem.to_string(),
+                None => continue,
+            };
+            let mut player = match read_player(&path) {
+                Some(player) => player,
+                None => {
+                    println!("Warning: couldn't read player data {:?}", path);
+                    continue;
+                }
+            };
+            if by_uuid {
+                if player.name.is_none() {
+                    player.name = names.get(&stem.to_lowercase()).cloned();
+                }
+                player.uuid = Some(stem);
+            } else {
+                player.name = Some(stem);
+            }
+            players.push(player);
+        }
+    }
+    players
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::minecraft::nbt::List;
+    use std::{env, process};
+
+    const UUID: &str = "069a79f4-44e9-4726-a5be-fca90e38aaf5";
+
+    fn player(x: f64, dimension: Nbt) -> Nbt {
+        let mut player = Compound::new();
+        player.insert(
+            "Pos".to_string(),
+            Nbt::List(List::Double(vec![x, 64.0, 0.0])),
+        );
+        player.insert(
+            "Rotation".to_string(),
+            Nbt::List(List::Float(vec![90.0, 0.0])),
+        );
+        player.insert("Dimension".to_string(), dimension);
+        Nbt::Compound(player)
+    }
+
+    fn write(path: &Path, nbt: &Nbt) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, nbt.to_gzip("").unwrap()).unwrap();
+    }
+
+    #[test]
+    fn matches() {
+        let mut player =
+            PlayerData::from_compound(player(0.0, Nbt::Int(0)).as_compound().unwrap()).unwrap();
+        player.uuid = Some(UUID.to_string());
+        player.name = Some("Notch".to_string());
+        assert!(player.matches(UUID));
+        assert!(player.matches(&UUID.replace('-', "").to_uppercase()));
+        assert!(player.matches("notch"));
+        assert!(!player.matches("069a79f4"));
+        assert!(!player.matches("jeb_"));
+    }
+
+    #[test]
+    fn from_compound() {
+        let player = player(1.5, Nbt::String("minecraft:the_nether".to_string()));
+        let player = PlayerData::from_compound(player.as_compound().unwrap()).unwrap();
+        assert_eq!(player.pos, [1.5, 64.0, 0.0]);
+        assert_eq!(player.rotation, [90.0, 0.0]);
+        assert_eq!(player.dimension, Dimension::Nether);
+        assert!(PlayerData::from_compound(&Compound::new()).is_none());
+    }
+
+    #[test]
+    fn load() {
+        let server = env::temp_dir().join(format!("hematite_players_{}", process::id()));
+        let world = server.join("world");
+        let _ = fs::remove_dir_all(&server);
+        let other = "61699b2e-d327-4a01-9f1e-0ea8c3f06bc6";
+        write(
+            &world.join("playerdata").join(format!("{}.dat", UUID)),
+            &player(1.0, Nbt::Int(-1)),
+        );
+        write(
+            &world.join("playerdata").join(format!("{}.dat", other)),
+            &player(2.0, Nbt::Int(0)),
+        );
+        write(
+            &world.join("players").join("jeb_.dat"),
+            &player(3.0, Nbt::Int(1)),
+        );
+        fs::write(
+            server.join("usercache.json"),
+            format!(r#"[{{"name": "Notch", "uuid": "{}"}}]"#, UUID),
+        )
+        .unwrap();
+
+        let players = load_players(&world);
+        fs::remove_dir_all(&server).unwrap();
+        assert_eq!(players.len(), 3);
+        let find = |query| players.iter().find(|p| p.matches(query)).unwrap();
+        let notch = find("Notch");
+        assert_eq!(notch.uuid.as_ref().map(|u| &u[..]), Some(UUID));
+        assert_eq!(notch.dimension, Dimension::Nether);
+        // Not in the user cache.
+        assert_eq!(find(other).name, None);
+        assert_eq!(find(other).pos[0], 2.0);
+        let legacy = find("jeb_");
+        assert_eq!(legacy.uuid, None);
+        assert_eq!(legacy.dimension, Dimension::End);
+    }
+}
@@ -1,26 +1,22 @@
-use gfx;
 use memmap::{Mmap, Protection};
-use std::cell::RefCell;
-use std::io;
-use std::path::Path;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::array::*;
-use crate::chunk::{BiomeId, BlockState, Chunk, ChunkColumn, LightLevel, EMPTY_CHUNK, SIZE};
-use crate::minecraft::nbt::Nbt;
+use crate::chunk::{
+    BiomeId, BlockState, Chunk, ChunkColumn, LightLevel, EMPTY_BLOCK, EMPTY_CHUNK, SIZE,
+};
+use crate::lighting::LightProperties;
+use crate::minecraft::nbt::{Compound, List, Nbt};
 
 pub struct Region {
     mmap: Mmap,
 }
 
-fn array_16x16x16<T, F>(mut f: F) -> [[[T; SIZE]; SIZE]; SIZE]
-where
-    F: FnMut(usize, usize, usize) -> T,
-{
-    Array::from_fn(|y| -> [[T; SIZE]; SIZE] {
-        Array::from_fn(|z| -> [T; 16] { Array::from_fn(|x| f(x, y, z)) })
-    })
-}
-
 impl Region {
     pub fn open(filename: &Path) -> io::Result<Region> {
         let mmap = Mmap::open_path(filename, Protection::Read)?;
@@ -31,7 +27,8 @@ impl Region {
         unsafe { self.mmap.as_slice() }
     }
 
-    pub fn get_chunk_column<R: gfx::Resources>(&self, x: u8, z: u8) -> Option<ChunkColumn<R>> {
+    // The length, compression and data of a chunk, without padding.
+    fn chunk_data(&self, x: u8, z: u8) -> Option<&[u8]> {
         let locations = &self.as_slice()[..4096];
         let i = 4 * ((x % 32) as usize + (z % 32) as usize * 32);
         let start = ((locations[i] as usize) << 16)
@@ -46,64 +43,310 @@ impl Region {
             | ((sectors[1] as usize) << 16)
             | ((sectors[2] as usize) << 8)
             | (sectors[3] as usize);
-        let nbt = match sectors[4] {
-            1 => Nbt::from_gzip(&sectors[5..4 + len]),
-            2 => Nbt::from_zlib(&sectors[5..4 + len]),
+        Some(&sectors[..4 + len])
+    }
+
+    fn timestamp(&self, x: u8, z: u8) -> [u8; 4] {
+        let i = 4096 + 4 * ((x % 32) as usize + (z % 32) as usize * 32);
+        let t = &self.as_slice()[i..i + 4];
+        [t[0], t[1], t[2], t[3]]
+    }
+
+    pub fn get_chunk_nbt(&self, x: u8, z: u8) -> Option<Nbt> {
+        let data = self.chunk_data(x, z)?;
+        let nbt = match data[4] {
+            1 => Nbt::from_gzip(&data[5..]),
+            2 => Nbt::from_zlib(&data[5..]),
             c => panic!("unknown region chunk compression method {}", c),
         };
+        Some(nbt.unwrap())
+    }
 
-        let mut c = nbt.unwrap().into_compound().unwrap();
-        let mut level = c.remove("Level").unwrap().into_compound().unwrap();
-        let mut chunks = Vec::new();
-        for chunk in level
-            .remove("Sections")
-            .unwrap()
-            .into_compound_list()
-            .unwrap()
-            .into_iter()
-        {
-            let y = chunk.get("Y").unwrap().as_byte().unwrap();
-            let blocks = chunk.get("Blocks").unwrap().as_bytearray().unwrap();
-            let blocks_top = chunk.get("Add").and_then(|x| x.as_bytearray());
-            let blocks_data = chunk.get("Data").unwrap().as_bytearray().unwrap();
-            let block_light = chunk.get("BlockLight").unwrap().as_bytearray().unwrap();
-            let sky_light = chunk.get("SkyLight").unwrap().as_bytearray().unwrap();
-
-            let chunk = Chunk {
-                blocks: array_16x16x16(|x, y, z| {
-                    let i = (y * SIZE + z) * SIZE + x;
-                    let top = match blocks_top {
-                        Some(blocks_top) => (blocks_top[i >> 1] >> ((i & 1) * 4)) & 0x0f,
-                        None => 0,
-                    };
-                    let data = (blocks_data[i >> 1] >> ((i & 1) * 4)) & 0x0f;
-                    BlockState {
-                        value: ((blocks[i] as u16) << 4) | ((top as u16) << 12) | (data as u16),
-                    }
-                }),
-                light_levels: array_16x16x16(|x, y, z| {
-                    let i = (y * 16 + z) * 16 + x;
-                    let block = (block_light[i >> 1] >> ((i & 1) * 4)) & 0x0f;
-                    let sky = (sky_light[i >> 1] >> ((i & 1) * 4)) & 0x0f;
-                    LightLevel {
-                        value: block | (sky << 4),
-                    }
-                }),
+    pub fn get_chunk_column(&self, x: u8, z: u8) -> Option<ChunkColumn> {
+        let mut c = self.get_chunk_nbt(x, z)?.into_compound().unwrap();
+        let level = c.remove("Level").unwrap().into_compound().unwrap();
+        Some(column_from_level(&level))
+    }
+}
+
+fn nibble(array: &[u8], i: usize) -> u8 {
+    (array[i >> 1] >> ((i & 1) * 4)) & 0x0f
+}
+
+fn set_nibble(array: &mut [u8], i: usize, value: u8) {
+    array[i >> 1] |= (value & 0x0f) << ((i & 1) * 4);
+}
+
+/// Decodes the `Level` compound of a column saved before 1.13.
+pub fn column_from_level(level: &Compound) -> ChunkColumn {
+    let mut chunks = Vec::new();
+    for chunk in level.get("Sections").unwrap().as_compound_list().unwrap() {
+        let y = chunk.get("Y").unwrap().as_byte().unwrap();
+        let blocks = chunk.get("Blocks").unwrap().as_bytearray().unwrap();
+        let blocks_top = chunk.get("Add").and_then(|x| x.as_bytearray());
+        let blocks_data = chunk.get("Data").unwrap().as_bytearray().unwrap();
+        let block_light = chunk.get("BlockLight").unwrap().as_bytearray().unwrap();
+        let sky_light = chunk.get("SkyLight").unwrap().as_bytearray().unwrap();
+
+        let chunk = Chunk::from_fn(|x, y, z| {
+            let i = (y * SIZE + z) * SIZE + x;
+            let top = blocks_top.map_or(0, |blocks_top| nibble(blocks_top, i));
+            let data = nibble(blocks_data, i);
+            let block = nibble(block_light, i);
+            let sky = nibble(sky_light, i);
+            (
+                BlockState {
+                    value: ((blocks[i] as u16) << 4) | ((top as u16) << 12) | (data as u16),
+                },
+                LightLevel {
+                    value: block | (sky << 4),
+                },
+            )
+        });
+        while chunks.len() <= y as usize {
+            chunks.push(Arc::new(EMPTY_CHUNK.clone()));
+        }
+        chunks[y as usize] = Arc::new(chunk);
+    }
+    let biomes = level.get("Biomes").unwrap().as_bytearray().unwrap();
+    let light_populated = level
+        .get("LightPopulated")
+        .and_then(|x| x.as_byte())
+        .is_none_or(|x| x != 0);
+    let tile_entities = level
+        .get("TileEntities")
+        .and_then(|x| x.as_compound_list())
+        .unwrap_or(&[])
+        .iter()
+        .map(|e| {
+            (
+                ["x", "y", "z"].map(|k| e.get(k).and_then(|x| x.as_int()).unwrap_or(0)),
+                e.clone(),
+            )
+        })
+        .collect();
+    ChunkColumn {
+        chunks,
+        biomes: Array::from_fn(|z| -> [BiomeId; SIZE] {
+            Array::from_fn(|x| BiomeId {
+                value: biomes[z * SIZE + x],
+            })
+        }),
+        light_populated,
+        tile_entities,
+    }
+}
+
+fn section_to_nbt(y: usize, chunk: &Chunk) -> Option<Compound> {
+    let (mut blocks, mut top, mut data) = (vec![0; 4096], vec![0; 2048], vec![0; 2048]);
+    let (mut block_light, mut sky_light) = (vec![0; 2048], vec![0; 2048]);
+    let mut empty = true;
+    for y in 0..SIZE {
+        for z in 0..SIZE {
+            for x in 0..SIZE {
+                let i = (y * SIZE + z) * SIZE + x;
+                let (block, light) = (chunk.block(x, y, z), chunk.light_level(x, y, z));
+                empty &= block == EMPTY_BLOCK && light == EMPTY_CHUNK.light_level(x, y, z);
+                blocks[i] = (block.value >> 4) as u8;
+                set_nibble(&mut top, i, (block.value >> 12) as u8);
+                set_nibble(&mut data, i, block.value as u8);
+                set_nibble(&mut block_light, i, light.block_light());
+                set_nibble(&mut sky_light, i, light.sky_light());
+            }
+        }
+    }
+    if empty {
+        return None;
+    }
+    let mut section = Compound::new();
+    section.insert("Y".to_string(), Nbt::Byte(y as i8));
+    section.insert("Blocks".to_string(), Nbt::ByteArray(blocks));
+    if top.iter().any(|&x| x != 0) {
+        section.insert("Add".to_string(), Nbt::ByteArray(top));
+    }
+    section.insert("Data".to_string(), Nbt::ByteArray(data));
+    section.insert("BlockLight".to_string(), Nbt::ByteArray(block_light));
+    section.insert("SkyLight".to_string(), Nbt::ByteArray(sky_light));
+    Some(section)
+}
+
+/// Replaces the blocks, light and tile entities in
+/// the NBT of a column, keeping everything else.
+fn update_level<P: LightProperties>(level: &mut Compound, column: &ChunkColumn, props: &P) {
+    let mut tile_entities: Vec<_> = column.tile_entities.iter().collect();
+    tile_entities.sort_by_key(|&(&[x, y, z], _)| (y, z, x));
+    let tile_entities = tile_entities.into_iter().map(|(_, e)| e.clone()).collect();
+    level.insert(
+        "TileEntities".to_string(),
+        Nbt::List(List::Compound(tile_entities)),
+    );
+
+    let sections = column
+        .chunks
+        .iter()
+        .enumerate()
+        .filter_map(|(y, chunk)| section_to_nbt(y, chunk))
+        .collect();
+    level.insert("Sections".to_string(), Nbt::List(List::Compound(sections)));
+
+    // The height sky light goes down to unchanged, by column.
+    let mut height_map = vec![0; SIZE * SIZE];
+    for z in 0..SIZE {
+        for x in 0..SIZE {
+            let top = (0..column.chunks.len() * SIZE).rev().find(|&y| {
+                let block = column.chunks[y / SIZE].block(x, y % SIZE, z);
+                props.light_opacity(block) != 0
+            });
+            height_map[z * SIZE + x] = top.map_or(0, |y| y as i32 + 1);
+        }
+    }
+    level.insert("HeightMap".to_string(), Nbt::IntArray(height_map));
+    level.insert("LightPopulated".to_string(), Nbt::Byte(1));
+}
+
+/// Writes a region file, with the given chunks (as zlib-compressed
+/// NBT) replacing the ones from the old file, if any.
+fn write_region(
+    path: &Path,
+    old: Option<&Region>,
+    chunks: &HashMap<(u8, u8), Vec<u8>>,
+) -> io::Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as u32);
+    let mut header = vec![0; 8192];
+    let mut body = vec![];
+    for z in 0..32 {
+        for x in 0..32 {
+            let (data, timestamp) = match (chunks.get(&(x, z)), old) {
+                (Some(compressed), _) => {
+                    let mut data = Vec::with_capacity(5 + compressed.len());
+                    data.extend_from_slice(&(compressed.len() as u32 + 1).to_be_bytes());
+                    data.push(2);
+                    data.extend_from_slice(compressed);
+                    (data, now.to_be_bytes())
+                }
+                (None, Some(old)) => match old.chunk_data(x, z) {
+                    Some(data) => (data.to_vec(), old.timestamp(x, z)),
+                    None => continue,
+                },
+                (None, None) => continue,
             };
-            while chunks.len() <= y as usize {
-                chunks.push(*EMPTY_CHUNK);
+            let start = 2 + body.len() / 4096;
+            let num = data.len().div_ceil(4096);
+            if num > 255 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("chunk {}, {} is too large for a region file", x, z),
+                ));
             }
-            chunks[y as usize] = chunk;
+            body.extend_from_slice(&data);
+            body.resize((start - 2 + num) * 4096, 0);
+
+            let i = 4 * (x as usize + z as usize * 32);
+            header[i..i + 3].copy_from_slice(&(start as u32).to_be_bytes()[1..]);
+            header[i + 3] = num as u8;
+            header[4096 + i..4096 + i + 4].copy_from_slice(&timestamp);
         }
-        let biomes = level.get("Biomes").unwrap().as_bytearray().unwrap();
-        Some(ChunkColumn {
-            chunks,
-            buffers: Array::from_fn(|_| RefCell::new(None)),
-            biomes: Array::from_fn(|z| -> [BiomeId; SIZE] {
-                Array::from_fn(|x| BiomeId {
-                    value: biomes[z * SIZE + x],
-                })
-            }),
-        })
+    }
+
+    // Readers might still be using the old file, so replace it as a whole.
+    let tmp = path.with_extension("mca.tmp");
+    {
+        let mut file = File::create(&tmp)?;
+        file.write_all(&header)?;
+        file.write_all(&body)?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp, path)
+}
+
+/// Lazily opened region files of a dimension, addressed by chunk coordinates.
+pub struct RegionStore {
+    path: PathBuf,
+    regions: HashMap<(i32, i32), Option<Arc<Region>>>,
+}
+
+impl RegionStore {
+    pub fn new(path: PathBuf) -> RegionStore {
+        RegionStore {
+            path,
+            regions: HashMap::new(),
+        }
+    }
+
+    pub fn region(&mut self, rx: i32, rz: i32) -> Option<Arc<Region>> {
+        let path = &self.path;
+        self.regions
+            .entry((rx, rz))
+            .or_insert_with(|| {
+                Region::open(&path.join(format!("r.{}.{}.mca", rx, rz)))
+                    .ok()
+                    .map(Arc::new)
+            })
+            .clone()
+    }
+
+    pub fn get_chunk_column(&mut self, x: i32, z: i32) -> Option<ChunkColumn> {
+        self.region(x >> 5, z >> 5)
+            .and_then(|region| region.get_chunk_column((x & 0x1f) as u8, (z & 0x1f) as u8))
+    }
+
+    /// Writes columns back into their region files,
+    /// returning the coordinates of the ones which were saved.
+    pub fn save_columns<P: LightProperties>(
+        &mut self,
+        columns: &[(i32, i32, &ChunkColumn)],
+        props: &P,
+    ) -> io::Result<Vec<(i32, i32)>> {
+        let mut by_region = HashMap::new();
+        for &(x, z, column) in columns {
+            by_region
+                .entry((x >> 5, z >> 5))
+                .or_insert_with(Vec::new)
+                .push(((x & 0x1f) as u8, (z & 0x1f) as u8, column));
+        }
+
+        let mut saved = vec![];
+        for ((rx, rz), columns) in by_region {
+            let region = self.region(rx, rz);
+            let mut chunks = HashMap::new();
+            for (x, z, column) in columns {
+                // Only the blocks are known here, the rest comes from the file.
+                let mut nbt = match region.as_ref().and_then(|r| r.get_chunk_nbt(x, z)) {
+                    Some(nbt) => nbt,
+                    None => {
+                        println!(
+                            "Warning: chunk {}, {} isn't in its region file, not saving it",
+                            rx * 32 + x as i32,
+                            rz * 32 + z as i32
+                        );
+                        continue;
+                    }
+                };
+                if let Some(level) = nbt
+                    .as_compound_mut()
+                    .and_then(|c| c.get_mut("Level"))
+                    .and_then(|l| l.as_compound_mut())
+                {
+                    update_level(level, column, props);
+                }
+                chunks.insert((x, z), nbt.to_zlib("")?);
+            }
+            if chunks.is_empty() {
+                continue;
+            }
+            let path = self.path.join(format!("r.{}.{}.mca", rx, rz));
+            write_region(&path, region.as_deref(), &chunks)?;
+            saved.extend(
+                chunks
+                    .keys()
+                    .map(|&(x, z)| (rx * 32 + x as i32, rz * 32 + z as i32)),
+            );
+
+            // Reopen it with the new contents next time.
+            self.regions.remove(&(rx, rz));
+        }
+        Ok(saved)
     }
 }
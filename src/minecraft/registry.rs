@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::chunk::BlockState;
+use crate::minecraft::multipart::{self, Properties};
+use rustc_serialize::json;
+
+// Registries shipped with hematite, by version prefix:
+// (version, blocks.json, biomes.json).
+static BUNDLED: &[(&str, &str, &str)] = &[(
+    "1.8",
+    include_str!("registries/1.8/blocks.json"),
+    include_str!("registries/1.8/biomes.json"),
+)];
+
+/// What a block state is, e.g. `oak_stairs[facing=east,half=top]`.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct BlockStateInfo {
+    // The blockstate file, without namespace.
+    pub name: String,
+    pub properties: Properties,
+}
+
+impl BlockStateInfo {
+    pub fn get(&self, property: &str) -> Option<&str> {
+        self.properties.get(property).map(|v| &v[..])
+    }
+
+    /// The same block, with a property changed.
+    pub fn with(&self, property: &str, value: &str) -> BlockStateInfo {
+        let mut info = self.clone();
+        info.properties
+            .insert(property.to_string(), value.to_string());
+        info
+    }
+
+    /// The key of the model in the blockstate file.
+    pub fn variant(&self) -> String {
+        if self.properties.is_empty() {
+            "normal".to_string()
+        } else {
+            multipart::format_properties(&self.properties)
+        }
+    }
+}
+
+impl fmt::Display for BlockStateInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.properties.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            let properties = multipart::format_properties(&self.properties);
+            write!(f, "{}[{}]", self.name, properties)
+        }
+    }
+}
+
+/// A block state of a version, and how to draw it.
+#[derive(Clone, Debug)]
+pub struct BlockStateEntry {
+    pub id: u16,
+    pub info: BlockStateInfo,
+    // The variant in the blockstate file, if not made of the properties.
+    pub variant: Option<String>,
+    pub light_emission: u8,
+    // `None` if the registry doesn't say, to be guessed from the model.
+    pub light_opacity: Option<u8>,
+    // Whether entities can move through the block.
+    pub passable: bool,
+}
+
+impl BlockStateEntry {
+    /// The key of the model in the blockstate file.
+    pub fn variant(&self) -> String {
+        match self.variant {
+            Some(ref variant) => variant.clone(),
+            None => self.info.variant(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BiomeEntry {
+    pub id: u8,
+    pub name: String,
+    pub temperature: f32,
+    pub downfall: f32,
+    pub water_color: [u8; 3],
+}
+
+/// The block states and biomes of a version of Minecraft.
+pub struct Registry {
+    pub version: String,
+    // Sorted by id.
+    pub block_states: Vec<BlockStateEntry>,
+    pub biomes: Vec<BiomeEntry>,
+    // The lowest id of each block state.
+    ids: HashMap<BlockStateInfo, u16>,
+}
+
+fn invalid(what: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid {}", what))
+}
+
+impl Registry {
+    /// Loads the registry for `version` from `<dir>/<version>/blocks.json`
+    /// and `biomes.json`, falling back to the bundled ones.
+    pub fn load(version: &str, dir: &Path) -> io::Result<Registry> {
+        let dir = dir.join(version);
+        if dir.join("blocks.json").exists() {
+            let blocks = fs::read_to_string(dir.join("blocks.json"))?;
+            let biomes = fs::read_to_string(dir.join("biomes.json"))?;
+            return Registry::from_json(version, &blocks, &biomes);
+        }
+        let bundled = BUNDLED.iter().find(|&&(prefix, _, _)| {
+            version == prefix || version.starts_with(&format!("{}.", prefix))
+        });
+        match bundled {
+            Some(&(_, blocks, biomes)) => Registry::from_json(version, blocks, biomes),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no block registry for {} in {}", version, dir.display()),
+            )),
+        }
+    }
+
+    /// Reads block states laid out like the `blocks.json` of vanilla's
+    /// data generator, with `light_emission`, `light_opacity` and `passable`
+    /// added to blocks and `variant` to states, and a list of biomes.
+    pub fn from_json(version: &str, blocks: &str, biomes: &str) -> io::Result<Registry> {
+        let blocks = json::Json::from_str(blocks).map_err(|_| invalid("blocks.json"))?;
+        let mut block_states = vec![];
+        for (name, block) in blocks.as_object().ok_or_else(|| invalid("blocks.json"))? {
+            let name = name.trim_start_matches("minecraft:");
+            let byte = |key| {
+                block
+                    .find(key)
+                    .map(|x| x.as_u64().map(|x| x as u8).ok_or_else(|| invalid(key)))
+                    .transpose()
+            };
+            let light_emission = byte("light_emission")?.unwrap_or(0);
+            let light_opacity = byte("light_opacity")?;
+            let passable = block
+                .find("passable")
+                .and_then(|x| x.as_boolean())
+                .unwrap_or(false);
+            let states = block
+                .find("states")
+                .and_then(|x| x.as_array())
+                .ok_or_else(|| invalid("states"))?;
+            for state in states.iter() {
+                let id = state
+                    .find("id")
+                    .and_then(|x| x.as_u64())
+                    .ok_or_else(|| invalid("state id"))?;
+                let properties = match state.find("properties") {
+                    Some(properties) => properties
+                        .as_object()
+                        .ok_or_else(|| invalid("properties"))?
+                        .iter()
+                        .map(|(k, v)| Some((k.clone(), v.as_string()?.to_string())))
+                        .collect::<Option<_>>()
+                        .ok_or_else(|| invalid("properties"))?,
+                    None => Properties::new(),
+                };
+                block_states.push(BlockStateEntry {
+                    id: id as u16,
+                    info: BlockStateInfo {
+                        name: name.to_string(),
+                        properties,
+                    },
+                    variant: state
+                        .find("variant")
+                        .and_then(|x| x.as_string())
+                        .map(|x| x.to_string()),
+                    light_emission,
+                    light_opacity,
+                    passable,
+                });
+            }
+        }
+        block_states.sort_by_key(|state| state.id);
+        let mut ids = HashMap::new();
+        for state in block_states.iter() {
+            ids.entry(state.info.clone()).or_insert(state.id);
+        }
+
+        let biomes = json::Json::from_str(biomes).map_err(|_| invalid("biomes.json"))?;
+        let biomes = biomes
+            .as_array()
+            .ok_or_else(|| invalid("biomes.json"))?
+            .iter()
+            .map(|biome| {
+                let number = |key| biome.find(key).and_then(|x| x.as_f64());
+                let water_color = match biome.find("water_color").and_then(|x| x.as_array()) {
+                    Some(rgb) => match rgb.iter().map(|x| x.as_u64()).collect::<Vec<_>>()[..] {
+                        [Some(r), Some(g), Some(b)] => [r as u8, g as u8, b as u8],
+                        _ => return None,
+                    },
+                    None => [0xff, 0xff, 0xff],
+                };
+                Some(BiomeEntry {
+                    id: number("id")? as u8,
+                    name: biome.find("name")?.as_string()?.to_string(),
+                    temperature: number("temperature")? as f32,
+                    downfall: number("downfall")? as f32,
+                    water_color,
+                })
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(|| invalid("biome"))?;
+
+        Ok(Registry {
+            version: version.to_string(),
+            block_states,
+            biomes,
+            ids,
+        })
+    }
+
+    /// What a block state is, if the version has it.
+    pub fn info(&self, block: BlockState) -> Option<&BlockStateInfo> {
+        let i = self
+            .block_states
+            .binary_search_by_key(&block.value, |state| state.id)
+            .ok()?;
+        Some(&self.block_states[i].info)
+    }
+
+    /// The block state for a block and its properties, e.g. from a palette.
+    /// The 1.8 registry only has the properties models depend on, so states
+    /// only differing in others, like the power of redstone wire or the age
+    /// of cactus, share their info, and the lowest of their ids is returned.
+    pub fn block_state(&self, info: &BlockStateInfo) -> Option<BlockState> {
+        self.ids.get(info).map(|&value| BlockState { value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    fn bundled() -> Registry {
+        Registry::load("1.8.8", Path::new("no_registries")).unwrap()
+    }
+
+    fn entry(registry: &Registry, id: u16) -> &BlockStateEntry {
+        let i = registry
+            .block_states
+            .binary_search_by_key(&id, |state| state.id)
+            .unwrap();
+        &registry.block_states[i]
+    }
+
+    #[test]
+    fn bundled_1_8() {
+        let registry = bundled();
+        assert_eq!(registry.version, "1.8.8");
+        assert_eq!(registry.block_states.len(), 1312);
+        assert!(registry.block_states.windows(2).all(|w| w[0].id < w[1].id));
+        let load = |version| Registry::load(version, Path::new("no_registries"));
+        assert!(load("1.8").is_ok());
+        assert!(load("1.80").is_err());
+        assert_eq!(
+            load("1.12.2").err().unwrap().kind(),
+            io::ErrorKind::NotFound
+        );
+
+        let stone = entry(&registry, 1 << 4);
+        assert_eq!(stone.info.to_string(), "stone");
+        assert_eq!(stone.variant(), "normal");
+        assert_eq!(stone.light_opacity, Some(15));
+        assert!(!stone.passable);
+
+        let torch = entry(&registry, 50 << 4 | 1);
+        assert_eq!(torch.info.to_string(), "torch[facing=east]");
+        assert_eq!(torch.light_emission, 14);
+        assert!(torch.passable);
+
+        let stairs = entry(&registry, 53 << 4 | 4);
+        assert_eq!(stairs.info.get("half"), Some("top"));
+        assert_eq!(stairs.info.get("facing"), Some("east"));
+
+        assert_eq!(entry(&registry, 89 << 4).light_emission, 15);
+        // Upper halves of double plants don't know their type.
+        let upper = entry(&registry, 175 << 4 | 8);
+        assert_eq!(upper.info.to_string(), "paeonia[half=upper]");
+    }
+
+    #[test]
+    fn bundled_biomes() {
+        let biomes = bundled().biomes;
+        let plains = biomes.iter().find(|b| b.id == 1).unwrap();
+        assert_eq!(plains.name, "Plains");
+        assert_eq!((plains.temperature, plains.downfall), (0.8, 0.4));
+        assert_eq!(plains.water_color, [0xff, 0xff, 0xff]);
+        let swamp = biomes.iter().find(|b| b.name == "Swampland").unwrap();
+        assert_ne!(swamp.water_color, [0xff, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn from_json() {
+        let blocks = r#"{
+            "minecraft:lever": {
+                "light_opacity": 0,
+                "passable": true,
+                "properties": {"powered": ["false", "true"]},
+                "states": [
+                    {"id": 1105, "properties": {"powered": "true"}},
+                    {"id": 1104, "properties": {"powered": "false"}, "variant": "off"}
+                ]
+            },
+            "minecraft:stone": {"states": [{"id": 16}]}
+        }"#;
+        let biomes = r#"[{"id": 3, "name": "Hills", "temperature": 0.2,
+                          "downfall": 0.3, "water_color": [1, 2, 3]}]"#;
+        let registry = Registry::from_json("test", blocks, biomes).unwrap();
+        let ids: Vec<_> = registry.block_states.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![16, 1104, 1105]);
+
+        let stone = &registry.block_states[0];
+        assert_eq!(stone.info.name, "stone");
+        assert_eq!((stone.light_emission, stone.light_opacity), (0, None));
+        assert!(!stone.passable);
+
+        let off = &registry.block_states[1];
+        assert_eq!(off.variant(), "off");
+        assert_eq!(off.light_opacity, Some(0));
+        assert!(off.passable);
+        assert_eq!(registry.block_states[2].variant(), "powered=true");
+
+        assert_eq!(registry.biomes[0].water_color, [1, 2, 3]);
+    }
+
+    #[test]
+    fn lookups() {
+        let registry = bundled();
+        for state in registry.block_states.iter() {
+            let block = BlockState { value: state.id };
+            let info = registry.info(block).unwrap();
+            assert_eq!(info, &state.info);
+            let first = registry.block_state(info).unwrap();
+            assert!(first.value <= state.id);
+            assert_eq!(registry.info(first), Some(info));
+        }
+        assert_eq!(registry.info(BlockState { value: 0 }), None);
+        assert_eq!(registry.info(BlockState { value: 0xffff }), None);
+
+        let info = BlockStateInfo {
+            name: "oak_stairs".to_string(),
+            properties: multipart::parse_properties("facing=north,half=top,shape=outer_right"),
+        };
+        assert_eq!(
+            registry.block_state(&info),
+            Some(BlockState { value: 53 << 4 | 7 })
+        );
+        assert_eq!(registry.block_state(&info.with("facing", "up")), None);
+    }
+
+    #[test]
+    fn shared_infos() {
+        // Redstone wire has no `power` property here.
+        let registry = bundled();
+        let wire = |power: u16| BlockState {
+            value: 55 << 4 | power,
+        };
+        let info = registry.info(wire(0)).unwrap();
+        assert_eq!(registry.info(wire(15)), Some(info));
+        assert_eq!(registry.block_state(info), Some(wire(0)));
+    }
+
+    #[test]
+    fn load_from_dir() {
+        let dir = env::temp_dir().join(format!("hematite_registries_{}", process::id()));
+        for &(version, id) in [("1.12.2", 16), ("1.8.8", 32)].iter() {
+            fs::create_dir_all(dir.join(version)).unwrap();
+            let blocks = format!(r#"{{"minecraft:stone": {{"states": [{{"id": {}}}]}}}}"#, id);
+            fs::write(dir.join(version).join("blocks.json"), blocks).unwrap();
+            fs::write(dir.join(version).join("biomes.json"), "[]").unwrap();
+        }
+        let other = Registry::load("1.12.2", &dir);
+        // Replacing the bundled one.
+        let replaced = Registry::load("1.8.8", &dir);
+        let bundled = Registry::load("1.8.9", &dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let other = other.unwrap();
+        assert_eq!(other.version, "1.12.2");
+        assert_eq!(other.block_states[0].id, 16);
+        assert_eq!(replaced.unwrap().block_states[0].id, 32);
+        assert_eq!(bundled.unwrap().block_states.len(), 1312);
+    }
+
+    #[test]
+    fn invalid_json() {
+        let biomes = "[]";
+        for blocks in [
+            "[]",
+            r#"{"minecraft:stone": {}}"#,
+            r#"{"minecraft:stone": {"states": [{}]}}"#,
+            r#"{"minecraft:stone": {"states": [{"id": 1, "properties": {"a": 1}}]}}"#,
+            r#"{"minecraft:stone": {"light_opacity": "a", "states": []}}"#,
+        ]
+        .iter()
+        {
+            let error = Registry::from_json("test", blocks, biomes).err().unwrap();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{}", blocks);
+        }
+        let error = Registry::from_json("test", "{}", r#"[{"id": 1}]"#).err();
+        assert!(error.is_some());
+    }
+}
@@ -0,0 +1,354 @@
+use crate::chunk::{BlockState, ChunkManager, SIZE};
+
+/// The solid parts of blocks, as far as entities are concerned.
+pub trait CollisionShapes {
+    /// The box stopping entities, in block coordinates,
+    /// or `None` if they can move through the block.
+    fn collision_box(&self, block: BlockState) -> Option<([f32; 3], [f32; 3])>;
+}
+
+// Player dimensions and movement, in blocks and seconds, like vanilla.
+pub const WIDTH: f32 = 0.6;
+pub const HEIGHT: f32 = 1.8;
+pub const STEP_HEIGHT: f32 = 0.6;
+pub const EYE_HEIGHT: f32 = 1.62;
+const WALK_SPEED: f32 = 4.317;
+const SPRINT_FACTOR: f32 = 1.3;
+const JUMP_SPEED: f32 = 8.4;
+const GRAVITY: f32 = 32.0;
+const TERMINAL_SPEED: f32 = 78.4;
+
+// Tolerance for boxes touching each other.
+const EPSILON: f32 = 1.0e-3;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Aabb {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl Aabb {
+    pub fn offset(self, d: [f32; 3]) -> Aabb {
+        Aabb {
+            min: [0, 1, 2].map(|i| self.min[i] + d[i]),
+            max: [0, 1, 2].map(|i| self.max[i] + d[i]),
+        }
+    }
+
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        (0..3).all(|i| self.max[i] > other.min[i] && self.min[i] < other.max[i])
+    }
+
+    /// Limits a movement of `d` along `axis`, so that
+    /// this box stops when touching `other`.
+    pub fn clip(&self, other: &Aabb, axis: usize, d: f32) -> f32 {
+        let overlaps = (0..3).all(|i| {
+            i == axis
+                || (self.max[i] - EPSILON > other.min[i] && self.min[i] + EPSILON < other.max[i])
+        });
+        if !overlaps {
+            d
+        } else if d > 0.0 && self.max[axis] <= other.min[axis] + EPSILON {
+            d.min(other.min[axis] - self.max[axis])
+        } else if d < 0.0 && self.min[axis] >= other.max[axis] - EPSILON {
+            d.max(other.max[axis] - self.min[axis])
+        } else {
+            d
+        }
+    }
+}
+
+/// Movement requested by the player for one step.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Input {
+    // Horizontal direction in world space, shortened to 1 if longer.
+    pub direction: [f32; 2],
+    pub jump: bool,
+    pub sprint: bool,
+}
+
+/// A player walking around, rather than flying.
+#[derive(Copy, Clone, Debug)]
+pub struct Player {
+    // Position of the feet, in the middle of the box.
+    pub position: [f32; 3],
+    pub velocity: [f32; 3],
+    pub on_ground: bool,
+}
+
+impl Player {
+    pub fn new(position: [f32; 3]) -> Player {
+        Player {
+            position,
+            velocity: [0.0; 3],
+            on_ground: false,
+        }
+    }
+
+    pub fn bounding_box(&self) -> Aabb {
+        let [x, y, z] = self.position;
+        let r = WIDTH / 2.0;
+        Aabb {
+            min: [x - r, y, z - r],
+            max: [x + r, y + HEIGHT, z + r],
+        }
+    }
+
+    /// Advances the player by `dt` seconds.
+    pub fn step<S: CollisionShapes>(
+        &mut self,
+        world: &ChunkManager,
+        shapes: &S,
+        input: Input,
+        dt: f32,
+    ) {
+        let speed = WALK_SPEED * if input.sprint { SPRINT_FACTOR } else { 1.0 };
+        // Going diagonally isn't faster.
+        let [dx, dz] = input.direction;
+        let speed = speed / dx.hypot(dz).max(1.0);
+        self.velocity[0] = dx * speed;
+        self.velocity[2] = dz * speed;
+        if input.jump && self.on_ground {
+            self.velocity[1] = JUMP_SPEED;
+        }
+        let vy = self.velocity[1];
+        self.velocity[1] = (vy - GRAVITY * dt).max(-TERMINAL_SPEED);
+
+        let mut motion = self.velocity.map(|v| v * dt);
+        // The average speed, so jumps don't depend on the frame rate.
+        motion[1] = (vy + self.velocity[1]) / 2.0 * dt;
+        let bb = self.bounding_box();
+        let boxes = collision_boxes(world, shapes, bb, motion);
+
+        let mut moved = move_box(&boxes, bb, motion);
+        let blocked = moved[0] != motion[0] || moved[2] != motion[2];
+        let landed = motion[1] < 0.0 && moved[1] != motion[1];
+        if blocked && (self.on_ground || landed) {
+            // Try again from higher up, then go back down.
+            let up = move_box(&boxes, bb, [0.0, STEP_HEIGHT, 0.0]);
+            let raised = bb.offset(up);
+            let across = move_box(&boxes, raised, [motion[0], 0.0, motion[2]]);
+            let raised = raised.offset(across);
+            let down = move_box(&boxes, raised, [0.0, -up[1], 0.0]);
+            let stepped = [across[0], up[1] + across[1] + down[1], across[2]];
+            let distance2 = |m: [f32; 3]| m[0] * m[0] + m[2] * m[2];
+            if distance2(stepped) > distance2(moved) {
+                moved = stepped;
+            }
+        }
+
+        self.position = [0, 1, 2].map(|i| self.position[i] + moved[i]);
+        let bb = self.bounding_box();
+        self.on_ground =
+            self.velocity[1] <= 0.0 && move_box(&boxes, bb, [0.0, -EPSILON, 0.0])[1] > -EPSILON;
+        if self.on_ground || (motion[1] > 0.0 && moved[1] < motion[1]) {
+            self.velocity[1] = 0.0;
+        }
+    }
+}
+
+// Moves a box as far as possible, one axis at a time,
+// returning the actual movement.
+fn move_box(boxes: &[Aabb], mut bb: Aabb, motion: [f32; 3]) -> [f32; 3] {
+    let mut moved = [0.0; 3];
+    for &axis in [1, 0, 2].iter() {
+        let mut d = motion[axis];
+        for other in boxes {
+            d = bb.clip(other, axis, d);
+        }
+        moved[axis] = d;
+        let mut offset = [0.0; 3];
+        offset[axis] = d;
+        bb = bb.offset(offset);
+    }
+    moved
+}
+
+// Collects the boxes of the blocks a moving box could touch.
+// Unloaded blocks are solid, so that the player doesn't fall
+// through the world while it's loading.
+fn collision_boxes<S: CollisionShapes>(
+    world: &ChunkManager,
+    shapes: &S,
+    bb: Aabb,
+    motion: [f32; 3],
+) -> Vec<Aabb> {
+    let reach = [motion[0], motion[1].max(STEP_HEIGHT), motion[2]];
+    let min = [0, 1, 2].map(|i| (bb.min[i] + motion[i].min(0.0)).floor() as i32 - 1);
+    let max = [0, 1, 2].map(|i| (bb.max[i] + reach[i].max(0.0)).floor() as i32 + 1);
+    let mut boxes = vec![];
+    for y in min[1]..max[1] + 1 {
+        if y < 0 || y >= (SIZE * SIZE) as i32 {
+            continue;
+        }
+        for z in min[2]..max[2] + 1 {
+            for x in min[0]..max[0] + 1 {
+                let bounds = match world.get_block(x, y, z) {
+                    Some(block) => shapes.collision_box(block),
+                    None => Some(([0.0; 3], [1.0; 3])),
+                };
+                if let Some((lo, hi)) = bounds {
+                    let xyz = [x as f32, y as f32, z as f32];
+                    boxes.push(Aabb {
+                        min: [0, 1, 2].map(|i| xyz[i] + lo[i]),
+                        max: [0, 1, 2].map(|i| xyz[i] + hi[i]),
+                    });
+                }
+            }
+        }
+    }
+    boxes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raycast::tests::{self as raycast, assert_near, Bounds, SLAB, STONE};
+    use crate::raycast::BlockBounds;
+
+    const DT: f32 = 0.05;
+
+    impl CollisionShapes for Bounds {
+        fn collision_box(&self, block: BlockState) -> Option<([f32; 3], [f32; 3])> {
+            self.block_bounds(block)
+        }
+    }
+
+    // A column with a stone floor at y = 10, and the given blocks.
+    fn world(blocks: &[([i32; 3], BlockState)]) -> ChunkManager {
+        let mut world = raycast::world(blocks);
+        for z in 0..SIZE as i32 {
+            for x in 0..SIZE as i32 {
+                world.set_block(x, 10, z, STONE);
+            }
+        }
+        world
+    }
+
+    // Steps with the same input for `seconds`, returning the heights.
+    fn run(world: &ChunkManager, player: &mut Player, input: Input, seconds: f32) -> Vec<f32> {
+        (0..(seconds / DT).round() as usize)
+            .map(|_| {
+                player.step(world, &Bounds, input, DT);
+                player.position[1]
+            })
+            .collect()
+    }
+
+    fn walk(direction: [f32; 2]) -> Input {
+        Input {
+            direction,
+            ..Input::default()
+        }
+    }
+
+    #[test]
+    fn fall_and_land() {
+        let world = world(&[]);
+        let mut player = Player::new([8.5, 15.0, 8.5]);
+        let heights = run(&world, &mut player, Input::default(), 2.0);
+        assert!(heights.windows(2).take(5).all(|h| h[1] < h[0]));
+        assert_near(player.position[1], 11.0);
+        assert!(player.on_ground);
+        assert_eq!(player.velocity[1], 0.0);
+    }
+
+    #[test]
+    fn walls() {
+        let wall: Vec<_> = (6..13)
+            .flat_map(|z| vec![([10, 11, z], STONE), ([10, 12, z], STONE)])
+            .collect();
+        let world = world(&wall);
+        let mut player = Player::new([8.5, 11.0, 8.5]);
+        run(&world, &mut player, walk([1.0, 0.0]), 2.0);
+        assert_near(player.position[0], 10.0 - WIDTH / 2.0);
+        assert_near(player.position[1], 11.0);
+
+        // Sliding along the wall.
+        let mut player = Player::new([9.5, 11.0, 8.5]);
+        run(&world, &mut player, walk([0.6, 0.8]), 0.5);
+        assert_near(player.position[0], 10.0 - WIDTH / 2.0);
+        assert!(player.position[2] > 9.5);
+    }
+
+    #[test]
+    fn step_up() {
+        let world = world(&[([10, 11, 8], SLAB)]);
+        let mut player = Player::new([8.5, 11.0, 8.5]);
+        run(&world, &mut player, walk([1.0, 0.0]), 0.5);
+        assert!(player.position[0] > 10.0);
+        assert_near(player.position[1], 11.5);
+
+        // A full block is too high.
+        let world = self::world(&[([10, 11, 8], STONE)]);
+        let mut player = Player::new([8.5, 11.0, 8.5]);
+        run(&world, &mut player, walk([1.0, 0.0]), 1.0);
+        assert_near(player.position[0], 10.0 - WIDTH / 2.0);
+        assert_near(player.position[1], 11.0);
+    }
+
+    #[test]
+    fn jump() {
+        let world = world(&[]);
+        let mut player = Player::new([8.5, 11.0, 8.5]);
+        run(&world, &mut player, Input::default(), 0.1);
+        assert!(player.on_ground);
+
+        let input = Input {
+            jump: true,
+            ..Input::default()
+        };
+        let heights = run(&world, &mut player, input, DT);
+        assert!(!player.on_ground);
+        assert!(heights[0] > 11.0);
+        let heights = run(&world, &mut player, Input::default(), 1.0);
+        let top = heights.iter().cloned().fold(0.0, f32::max);
+        // v² / 2g, high enough to get onto a block.
+        assert!(top > 12.0 && top < 11.0 + JUMP_SPEED * JUMP_SPEED / (2.0 * GRAVITY) + 1e-3);
+        assert_near(player.position[1], 11.0);
+        assert!(player.on_ground);
+    }
+
+    #[test]
+    fn jump_onto_block() {
+        let platform: Vec<_> = (10..16).map(|x| ([x, 11, 8], STONE)).collect();
+        let world = world(&platform);
+        let mut player = Player::new([8.5, 11.0, 8.5]);
+        run(&world, &mut player, Input::default(), 0.1);
+        let input = Input {
+            direction: [1.0, 0.0],
+            jump: true,
+            sprint: false,
+        };
+        run(&world, &mut player, input, 0.5);
+        run(&world, &mut player, walk([1.0, 0.0]), 0.5);
+        assert!(player.position[0] > 10.0);
+        assert_near(player.position[1], 12.0);
+    }
+
+    #[test]
+    fn diagonal_speed() {
+        let world = world(&[]);
+        let mut player = Player::new([2.5, 11.0, 2.5]);
+        run(&world, &mut player, Input::default(), 0.1);
+        run(&world, &mut player, walk([1.0, 1.0]), 1.0);
+        let [x, _, z] = player.position;
+        assert_near((x - 2.5).hypot(z - 2.5), WALK_SPEED);
+        assert_near(x, z);
+    }
+
+    #[test]
+    fn toggle_walking() {
+        // Walking sets the camera to the feet, and starts from there.
+        let world = world(&[]);
+        let mut player = Player::new([8.5, 13.0, 8.5]);
+        run(&world, &mut player, Input::default(), 1.0);
+        assert!(player.on_ground);
+        let camera = player.position;
+
+        let mut player = Player::new(camera);
+        run(&world, &mut player, Input::default(), DT);
+        assert!(player.on_ground);
+        assert_eq!(player.position, camera);
+    }
+}
@@ -0,0 +1,234 @@
+use vecmath::{vec3_add, vec3_normalized, vec3_scale};
+
+use crate::chunk::{BlockState, ChunkManager};
+use crate::cube;
+
+/// The shape of blocks, as far as rays are concerned.
+pub trait BlockBounds {
+    /// The box enclosing a block, in block coordinates,
+    /// or `None` if rays go through it.
+    fn block_bounds(&self, block: BlockState) -> Option<([f32; 3], [f32; 3])>;
+}
+
+/// A block hit by a ray.
+#[derive(Copy, Clone, Debug)]
+pub struct RayHit {
+    pub pos: [i32; 3],
+    // The side of the block the ray entered through.
+    pub face: cube::Face,
+    pub block: BlockState,
+    pub point: [f32; 3],
+    pub distance: f32,
+}
+
+// Intersects a ray with a box, returning the distance
+// and the axis of the entered side.
+fn intersect_box(
+    origin: [f32; 3],
+    dir: [f32; 3],
+    min: [f32; 3],
+    max: [f32; 3],
+) -> Option<(f32, usize)> {
+    let (mut near, mut far, mut axis) = (f32::NEG_INFINITY, f32::INFINITY, 0);
+    for i in 0..3 {
+        if dir[i] == 0.0 {
+            if origin[i] < min[i] || origin[i] > max[i] {
+                return None;
+            }
+            continue;
+        }
+        let (a, b) = ((min[i] - origin[i]) / dir[i], (max[i] - origin[i]) / dir[i]);
+        let (a, b) = if a < b { (a, b) } else { (b, a) };
+        if a > near {
+            near = a;
+            axis = i;
+        }
+        far = far.min(b);
+    }
+    if near > far || far < 0.0 {
+        None
+    } else {
+        Some((near.max(0.0), axis))
+    }
+}
+
+/// Finds the first block along a ray, stepping through
+/// the grid one block at a time.
+pub fn raycast<B: BlockBounds>(
+    world: &ChunkManager,
+    blocks: &B,
+    origin: [f32; 3],
+    direction: [f32; 3],
+    max_distance: f32,
+) -> Option<RayHit> {
+    let dir = vec3_normalized(direction);
+    if dir.iter().any(|x| x.is_nan()) {
+        return None;
+    }
+    let mut pos = origin.map(|x| x.floor() as i32);
+    let step = dir.map(|x| if x > 0.0 { 1 } else { -1 });
+    // Distance along the ray to the next boundary on each axis,
+    // and between two boundaries.
+    let mut t_max = [0, 1, 2].map(|i| {
+        let boundary = pos[i] as f32 + if dir[i] > 0.0 { 1.0 } else { 0.0 };
+        if dir[i] == 0.0 {
+            f32::INFINITY
+        } else {
+            (boundary - origin[i]) / dir[i]
+        }
+    });
+    let t_delta = dir.map(|x| (1.0 / x).abs());
+
+    loop {
+        if let Some(block) = world.get_block(pos[0], pos[1], pos[2]) {
+            let hit = blocks.block_bounds(block).and_then(|(min, max)| {
+                let xyz = pos.map(|x| x as f32);
+                intersect_box(origin, dir, vec3_add(xyz, min), vec3_add(xyz, max))
+            });
+            if let Some((t, axis)) = hit {
+                if t > max_distance {
+                    return None;
+                }
+                let mut normal = [0; 3];
+                normal[axis] = -step[axis];
+                return Some(RayHit {
+                    pos,
+                    face: cube::Face::from_direction(normal).unwrap(),
+                    block,
+                    point: vec3_add(origin, vec3_scale(dir, t)),
+                    distance: t,
+                });
+            }
+        }
+
+        let axis = if t_max[0] < t_max[1] {
+            if t_max[0] < t_max[2] {
+                0
+            } else {
+                2
+            }
+        } else if t_max[1] < t_max[2] {
+            1
+        } else {
+            2
+        };
+        if t_max[axis] > max_distance {
+            return None;
+        }
+        pos[axis] += step[axis];
+        t_max[axis] += t_delta[axis];
+    }
+}
+
+// Also used by the physics tests.
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use crate::chunk::ChunkColumn;
+
+    pub const STONE: BlockState = BlockState { value: 1 << 4 };
+    pub const SLAB: BlockState = BlockState { value: 44 << 4 };
+
+    pub struct Bounds;
+
+    impl BlockBounds for Bounds {
+        fn block_bounds(&self, block: BlockState) -> Option<([f32; 3], [f32; 3])> {
+            match block {
+                STONE => Some(([0.0, 0.0, 0.0], [1.0, 1.0, 1.0])),
+                SLAB => Some(([0.0, 0.0, 0.0], [1.0, 0.5, 1.0])),
+                _ => None,
+            }
+        }
+    }
+
+    // A column at 0, 0 with the given blocks.
+    pub fn world(blocks: &[([i32; 3], BlockState)]) -> ChunkManager {
+        let mut world = ChunkManager::new();
+        world.add_chunk_column(0, 0, ChunkColumn::empty());
+        for &([x, y, z], block) in blocks {
+            world.set_block(x, y, z, block);
+        }
+        world
+    }
+
+    fn cast(world: &ChunkManager, origin: [f32; 3], dir: [f32; 3], max: f32) -> Option<RayHit> {
+        raycast(world, &Bounds, origin, dir, max)
+    }
+
+    pub fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn axis_aligned() {
+        let world = world(&[([2, 5, 3], STONE)]);
+        let hit = cast(&world, [2.5, 10.5, 3.5], [0.0, -1.0, 0.0], 10.0).unwrap();
+        assert_eq!(hit.pos, [2, 5, 3]);
+        assert_eq!(hit.face, cube::Up);
+        assert_eq!(hit.block, STONE);
+        assert_near(hit.distance, 4.5);
+        assert_near(hit.point[1], 6.0);
+
+        let hit = cast(&world, [0.5, 5.5, 3.5], [2.0, 0.0, 0.0], 10.0).unwrap();
+        assert_eq!(hit.face, cube::West);
+        assert_near(hit.distance, 1.5);
+    }
+
+    #[test]
+    fn negative_directions() {
+        let world = world(&[([2, 5, 3], STONE)]);
+        let hit = cast(&world, [5.5, 5.5, 3.5], [-1.0, 0.0, 0.0], 10.0).unwrap();
+        assert_eq!(hit.face, cube::East);
+        assert_near(hit.distance, 2.5);
+
+        let hit = cast(&world, [2.5, 5.5, 7.25], [0.0, 0.0, -1.0], 10.0).unwrap();
+        assert_eq!(hit.face, cube::South);
+        assert_near(hit.distance, 3.25);
+
+        let hit = cast(&world, [2.5, 1.5, 3.5], [0.0, -1.0, 0.0], 10.0);
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn diagonal() {
+        let world = world(&[([2, 5, 3], STONE)]);
+        let hit = cast(&world, [0.5, 7.2, 3.5], [1.0, -1.0, 0.0], 10.0).unwrap();
+        assert_eq!(hit.pos, [2, 5, 3]);
+        assert_eq!(hit.face, cube::West);
+        assert_near(hit.distance, 1.5 * 2f32.sqrt());
+        assert_near(hit.point[1], 5.7);
+    }
+
+    #[test]
+    fn partial_boxes() {
+        let world = world(&[([4, 5, 3], SLAB), ([4, 5, 6], STONE)]);
+        let hit = cast(&world, [4.5, 8.0, 3.5], [0.0, -1.0, 0.0], 10.0).unwrap();
+        assert_eq!(hit.pos, [4, 5, 3]);
+        assert_eq!(hit.face, cube::Up);
+        assert_near(hit.distance, 2.5);
+
+        // Over the slab, inside its block.
+        let hit = cast(&world, [4.5, 5.75, 1.5], [0.0, 0.0, 1.0], 10.0).unwrap();
+        assert_eq!(hit.pos, [4, 5, 6]);
+        assert_near(hit.distance, 4.5);
+
+        // Into the side of the slab.
+        let hit = cast(&world, [4.5, 5.25, 1.5], [0.0, 0.0, 1.0], 10.0).unwrap();
+        assert_eq!(hit.pos, [4, 5, 3]);
+        assert_eq!(hit.face, cube::North);
+        assert_near(hit.distance, 1.5);
+    }
+
+    #[test]
+    fn max_distance() {
+        let world = world(&[([2, 5, 3], STONE), ([4, 5, 3], SLAB)]);
+        assert!(cast(&world, [2.5, 10.5, 3.5], [0.0, -1.0, 0.0], 4.0).is_none());
+        assert!(cast(&world, [2.5, 10.5, 3.5], [0.0, -1.0, 0.0], 4.5).is_some());
+        // The slab's block is within reach, but not its top.
+        assert!(cast(&world, [4.5, 8.0, 3.5], [0.0, -1.0, 0.0], 2.25).is_none());
+        // Nothing but air.
+        assert!(cast(&world, [8.5, 8.5, 8.5], [0.3, 1.0, -0.2], 100.0).is_none());
+        assert!(cast(&world, [8.5, 8.5, 8.5], [0.0, 0.0, 0.0], 100.0).is_none());
+    }
+}
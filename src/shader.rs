@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use gfx;
 use gfx::traits::FactoryExt;
 use vecmath::{self, Matrix4};
@@ -46,6 +48,65 @@ gfx_pipeline!( pipe {
         gfx::preset::depth::LESS_EQUAL_WRITE,
 });
 
+static TRANSLUCENT_FRAGMENT: &[u8] = b"
+    #version 150 core
+    out vec4 out_color;
+
+    uniform sampler2D s_texture;
+
+    in vec2 v_tex_coord;
+    in vec3 v_color;
+
+    void main() {
+        out_color = texture(s_texture, v_tex_coord) * vec4(v_color, 1.0);
+    }
+";
+
+// Blended over the opaque geometry, without hiding what's behind.
+gfx_pipeline!( translucent_pipe {
+    vbuf: gfx::VertexBuffer<Vertex> = (),
+    transform: gfx::Global<[[f32; 4]; 4]> = "u_projection",
+    view: gfx::Global<[[f32; 4]; 4]> = "u_view",
+    color: gfx::TextureSampler<[f32; 4]> = "s_texture",
+    out_color: gfx::BlendTarget<gfx::format::Srgba8> =
+        ("out_color", gfx::state::ColorMask::all(), gfx::preset::blend::ALPHA),
+    out_depth: gfx::DepthTarget<gfx::format::DepthStencil> =
+        gfx::preset::depth::LESS_EQUAL_TEST,
+});
+
+static OUTLINE_VERTEX: &[u8] = b"
+    #version 150 core
+    uniform mat4 u_projection, u_view;
+
+    in vec3 at_position;
+
+    void main() {
+        gl_Position = u_projection * u_view * vec4(at_position, 1.0);
+    }
+";
+
+static OUTLINE_FRAGMENT: &[u8] = b"
+    #version 150 core
+    out vec4 out_color;
+
+    void main() {
+        out_color = vec4(0.0, 0.0, 0.0, 1.0);
+    }
+";
+
+gfx_pipeline!( outline_pipe {
+    vbuf: gfx::VertexBuffer<OutlineVertex> = (),
+    transform: gfx::Global<[[f32; 4]; 4]> = "u_projection",
+    view: gfx::Global<[[f32; 4]; 4]> = "u_view",
+    out_color: gfx::RenderTarget<gfx::format::Srgba8> = "out_color",
+    out_depth: gfx::DepthTarget<gfx::format::DepthStencil> =
+        gfx::preset::depth::LESS_EQUAL_TEST,
+});
+
+gfx_vertex_struct!(OutlineVertex {
+    xyz: [f32; 3] = "at_position",
+});
+
 gfx_vertex_struct!(Vertex {
     xyz: [f32; 3] = "at_position",
     uv: [f32; 2] = "at_tex_coord",
@@ -56,6 +117,10 @@ pub struct Renderer<R: gfx::Resources, F: gfx::Factory<R>, C: gfx::CommandBuffer
     factory: F,
     pub pipe: gfx::PipelineState<R, pipe::Meta>,
     data: pipe::Data<R>,
+    translucent_pipe: gfx::PipelineState<R, translucent_pipe::Meta>,
+    translucent_data: translucent_pipe::Data<R>,
+    outline_pipe: gfx::PipelineState<R, outline_pipe::Meta>,
+    outline_data: outline_pipe::Data<R>,
     encoder: gfx::Encoder<R, C>,
     clear_color: [f32; 4],
     clear_depth: f32,
@@ -97,9 +162,57 @@ impl<R: gfx::Resources, F: gfx::Factory<R>, C: gfx::CommandBuffer<R>> Renderer<R
             )
             .unwrap();
 
+        let translucent_prog = factory.link_program(VERTEX, TRANSLUCENT_FRAGMENT).unwrap();
+        let translucent_pipe = factory
+            .create_pipeline_from_program(
+                &translucent_prog,
+                gfx::Primitive::TriangleList,
+                rasterizer,
+                translucent_pipe::new(),
+            )
+            .unwrap();
+
+        let outline_prog = factory
+            .link_program(OUTLINE_VERTEX, OUTLINE_FRAGMENT)
+            .unwrap();
+        let outline_pipe = factory
+            .create_pipeline_from_program(
+                &outline_prog,
+                gfx::Primitive::LineList,
+                gfx::state::Rasterizer::new_fill(),
+                outline_pipe::new(),
+            )
+            .unwrap();
+
         let vbuf = factory.create_vertex_buffer(&[]);
         let slice = gfx::Slice::new_match_vertex_buffer(&vbuf);
 
+        // The 12 edges of the outlined box, updated when drawing it.
+        let outline_vbuf = factory
+            .create_buffer(
+                24,
+                gfx::buffer::Role::Vertex,
+                gfx::memory::Usage::Dynamic,
+                gfx::memory::Bind::empty(),
+            )
+            .unwrap();
+        let outline_data = outline_pipe::Data {
+            vbuf: outline_vbuf,
+            transform: vecmath::mat4_id(),
+            view: vecmath::mat4_id(),
+            out_color: target.clone(),
+            out_depth: depth.clone(),
+        };
+
+        let translucent_data = translucent_pipe::Data {
+            vbuf: vbuf.clone(),
+            transform: vecmath::mat4_id(),
+            view: vecmath::mat4_id(),
+            color: (texture_view.clone(), sampler.clone()),
+            out_color: target.clone(),
+            out_depth: depth.clone(),
+        };
+
         let data = pipe::Data {
             vbuf,
             transform: vecmath::mat4_id(),
@@ -113,6 +226,10 @@ impl<R: gfx::Resources, F: gfx::Factory<R>, C: gfx::CommandBuffer<R>> Renderer<R
             factory,
             pipe,
             data,
+            translucent_pipe,
+            translucent_data,
+            outline_pipe,
+            outline_data,
             encoder,
             clear_color: [0.81, 0.8, 1.0, 1.0],
             clear_depth: 1.0,
@@ -123,10 +240,18 @@ impl<R: gfx::Resources, F: gfx::Factory<R>, C: gfx::CommandBuffer<R>> Renderer<R
 
     pub fn set_projection(&mut self, proj_mat: Matrix4<f32>) {
         self.data.transform = proj_mat;
+        self.translucent_data.transform = proj_mat;
+        self.outline_data.transform = proj_mat;
+    }
+
+    pub fn set_clear_color(&mut self, color: [f32; 4]) {
+        self.clear_color = color;
     }
 
     pub fn set_view(&mut self, view_mat: Matrix4<f32>) {
         self.data.view = view_mat;
+        self.translucent_data.view = view_mat;
+        self.outline_data.view = view_mat;
     }
 
     pub fn clear(&mut self) {
@@ -151,9 +276,215 @@ impl<R: gfx::Resources, F: gfx::Factory<R>, C: gfx::CommandBuffer<R>> Renderer<R
         vbuf
     }
 
+    /// Creates the buffers of a section from its vertices, by layer.
+    pub fn create_section_buffers(&mut self, layers: [Vec<Vertex>; 3]) -> SectionBuffers<R> {
+        let [opaque, cutout, translucent] = layers;
+        let mut buffer = |vertices: &[Vertex]| {
+            if vertices.is_empty() {
+                None
+            } else {
+                Some(self.create_buffer(vertices))
+            }
+        };
+        SectionBuffers {
+            opaque: buffer(&opaque),
+            cutout: buffer(&cutout),
+            translucent,
+            sorted: None,
+        }
+    }
+
     pub fn render(&mut self, buffer: &mut gfx::handle::Buffer<R, Vertex>) {
         self.data.vbuf = buffer.clone();
         self.slice.end = buffer.len() as u32;
         self.encoder.draw(&self.slice, &self.pipe, &self.data);
     }
+
+    /// Draws the opaque and cutout layers of a section.
+    pub fn render_section(&mut self, buffers: &mut SectionBuffers<R>) {
+        for buffer in [&mut buffers.opaque, &mut buffers.cutout].iter_mut() {
+            if let Some(ref mut buffer) = **buffer {
+                self.render(buffer);
+            }
+        }
+    }
+
+    /// Draws the translucent layer of a section, from back to front
+    /// as seen from `eye`. Faces are only sorted again when the eye
+    /// moves to another block.
+    pub fn render_translucent(&mut self, buffers: &mut SectionBuffers<R>, eye: [f32; 3]) {
+        if buffers.translucent.is_empty() {
+            return;
+        }
+        let eye_block = eye.map(|x| x.floor() as i32);
+        let sorted_from = buffers.sorted.as_ref().map(|&(_, from)| from);
+        if sorted_from != Some(eye_block) {
+            let distance2 = |quad: &[Vertex]| {
+                let center = [0, 1, 2].map(|i| {
+                    quad.iter().map(|v| v.xyz[i]).sum::<f32>() / quad.len() as f32 - eye[i]
+                });
+                center.iter().map(|x| x * x).sum::<f32>()
+            };
+            let mut quads: Vec<_> = buffers
+                .translucent
+                .chunks(6)
+                .map(|quad| (distance2(quad), quad))
+                .collect();
+            quads.sort_by(|a, b| b.0.total_cmp(&a.0));
+            let vertices: Vec<_> = quads
+                .into_iter()
+                .flat_map(|(_, quad)| quad.iter().cloned())
+                .collect();
+            buffers.sorted = Some((self.create_buffer(&vertices), eye_block));
+        }
+        let buffer = &buffers.sorted.as_ref().unwrap().0;
+        self.translucent_data.vbuf = buffer.clone();
+        self.slice.end = buffer.len() as u32;
+        self.encoder
+            .draw(&self.slice, &self.translucent_pipe, &self.translucent_data);
+    }
+
+    /// Draws the edges of a box, e.g. around the targeted block.
+    pub fn render_outline(&mut self, min: [f32; 3], max: [f32; 3]) {
+        let corner = |i: usize| OutlineVertex {
+            xyz: [0, 1, 2].map(|axis| {
+                if i >> axis & 1 == 0 {
+                    min[axis]
+                } else {
+                    max[axis]
+                }
+            }),
+        };
+        let mut vertices = Vec::with_capacity(24);
+        for i in 0..8 {
+            for axis in 0..3 {
+                // Each edge goes from a corner to the one on its max side.
+                if i >> axis & 1 == 0 {
+                    vertices.push(corner(i));
+                    vertices.push(corner(i | 1 << axis));
+                }
+            }
+        }
+        self.encoder
+            .update_buffer(&self.outline_data.vbuf, &vertices, 0)
+            .unwrap();
+        let slice = gfx::Slice::new_match_vertex_buffer(&self.outline_data.vbuf);
+        self.encoder
+            .draw(&slice, &self.outline_pipe, &self.outline_data);
+    }
+}
+
+/// Orders sections to draw their translucent layers from back to front,
+/// by the distance from `eye` to their centers. Sections are cubes, so
+/// the one containing the eye is always the closest and goes last.
+pub fn sort_back_to_front(sections: &mut [[i32; 3]], eye: [f32; 3]) {
+    let distance2 = |coords: &[i32; 3]| {
+        let d = [0, 1, 2].map(|i| coords[i] as f32 * 16.0 + 8.0 - eye[i]);
+        d[0] * d[0] + d[1] * d[1] + d[2] * d[2]
+    };
+    sections.sort_by(|a, b| distance2(b).total_cmp(&distance2(a)));
+}
+
+/// The vertex buffers of a section, one for each layer.
+pub struct SectionBuffers<R: gfx::Resources> {
+    opaque: Option<gfx::handle::Buffer<R, Vertex>>,
+    cutout: Option<gfx::handle::Buffer<R, Vertex>>,
+    // Kept around to be sorted again when the camera moves,
+    // as triangles in groups of 6 vertices, one per face.
+    translucent: Vec<Vertex>,
+    // The last sorted buffer, with the block it was sorted from.
+    sorted: Option<(gfx::handle::Buffer<R, Vertex>, [i32; 3])>,
+}
+
+impl<R: gfx::Resources> SectionBuffers<R> {
+    pub fn has_translucent(&self) -> bool {
+        !self.translucent.is_empty()
+    }
+}
+
+/// Vertex buffers of chunk sections, keyed by section coordinates.
+pub struct ChunkBuffers<R: gfx::Resources> {
+    buffers: HashMap<[i32; 3], SectionBuffers<R>>,
+}
+
+impl<R: gfx::Resources> Default for ChunkBuffers<R> {
+    fn default() -> ChunkBuffers<R> {
+        ChunkBuffers::new()
+    }
+}
+
+impl<R: gfx::Resources> ChunkBuffers<R> {
+    pub fn new() -> ChunkBuffers<R> {
+        ChunkBuffers {
+            buffers: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, coords: [i32; 3], buffers: SectionBuffers<R>) {
+        self.buffers.insert(coords, buffers);
+    }
+
+    pub fn get_mut(&mut self, coords: [i32; 3]) -> Option<&mut SectionBuffers<R>> {
+        self.buffers.get_mut(&coords)
+    }
+
+    pub fn remove(&mut self, coords: [i32; 3]) {
+        self.buffers.remove(&coords);
+    }
+
+    /// Frees the buffers of all the sections in a column.
+    pub fn remove_column(&mut self, x: i32, z: i32) {
+        self.buffers.retain(|c, _| c[0] != x || c[2] != z);
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    pub fn each_buffer<F>(&mut self, mut f: F)
+    where
+        F: FnMut(/*coords:*/ [i32; 3], /*buffers:*/ &mut SectionBuffers<R>),
+    {
+        for (&coords, buffer) in self.buffers.iter_mut() {
+            f(coords, buffer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut sections: Vec<[i32; 3]>, eye: [f32; 3]) -> Vec<[i32; 3]> {
+        sort_back_to_front(&mut sections, eye);
+        sections
+    }
+
+    #[test]
+    fn eye_inside() {
+        // Near the corner of its section.
+        let sections = vec![[0, 0, 0], [1, 1, 1], [1, 0, 0], [1, 1, 0], [2, 1, 1]];
+        let order = sorted(sections, [31.5, 31.5, 31.5]);
+        assert_eq!(order[0], [0, 0, 0]);
+        assert_eq!(order[4], [1, 1, 1]);
+        assert_eq!(order[3], [2, 1, 1]);
+    }
+
+    #[test]
+    fn eye_above() {
+        let column = (0..4).map(|y| [0, y, 0]).collect();
+        let order = sorted(column, [8.0, 100.0, 8.0]);
+        assert_eq!(order, vec![[0, 0, 0], [0, 1, 0], [0, 2, 0], [0, 3, 0]]);
+    }
+
+    #[test]
+    fn eye_beside() {
+        let row = vec![[3, 0, 0], [-1, 0, 0], [1, 0, 0], [0, 0, 2]];
+        let order = sorted(row, [-40.0, 8.0, 8.0]);
+        assert_eq!(order, vec![[3, 0, 0], [1, 0, 0], [0, 0, 2], [-1, 0, 0]]);
+    }
 }
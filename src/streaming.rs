@@ -0,0 +1,309 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use crate::chunk::{ChunkColumn, ChunkManager};
+use crate::minecraft::region::RegionStore;
+use crate::workers::WorkerPool;
+
+/// Anything chunk columns can be loaded from, possibly in the background.
+pub trait ColumnLoader {
+    /// Starts loading a column, lower priorities first.
+    fn request(&mut self, x: i32, z: i32, priority: i32);
+
+    /// Returns a column which finished loading, if any.
+    fn poll(&mut self) -> Option<(i32, i32, Option<ChunkColumn>)>;
+
+    /// Called when the center of the loaded area moves.
+    fn recenter(&mut self, _center: [i32; 2]) {}
+}
+
+pub type RegionLoader = WorkerPool<(i32, i32), (i32, i32, Option<ChunkColumn>)>;
+
+/// Decodes columns from region files on background threads.
+pub fn region_loader(regions: Arc<Mutex<RegionStore>>, num_threads: usize) -> RegionLoader {
+    WorkerPool::new("region loader", num_threads, move |(x, z)| {
+        // Only hold the lock while opening the region, not while decoding.
+        let region = regions.lock().unwrap().region(x >> 5, z >> 5);
+        let column =
+            region.and_then(|region| region.get_chunk_column((x & 0x1f) as u8, (z & 0x1f) as u8));
+        (x, z, column)
+    })
+}
+
+impl ColumnLoader for RegionLoader {
+    fn request(&mut self, x: i32, z: i32, priority: i32) {
+        self.push(priority, (x, z));
+    }
+
+    fn poll(&mut self) -> Option<(i32, i32, Option<ChunkColumn>)> {
+        self.try_recv()
+    }
+
+    fn recenter(&mut self, center: [i32; 2]) {
+        self.reprioritize(|&coords| distance2(coords, center));
+    }
+}
+
+/// Loads the columns around the camera as it moves, and unloads
+/// the ones which got too far from it.
+pub struct ChunkStreamer {
+    // Radius of the loaded area, in chunks.
+    pub render_distance: i32,
+    // Columns are only unloaded past this radius, so that moving
+    // back and forth over a chunk border doesn't reload them.
+    pub unload_distance: i32,
+    // Maximum number of columns being loaded at once.
+    pub max_requests: usize,
+    center: Option<[i32; 2]>,
+    // Columns waiting to be requested, closest to the center last.
+    queue: Vec<(i32, i32)>,
+    requested: HashSet<(i32, i32)>,
+    // Columns which the loader doesn't have.
+    missing: HashSet<(i32, i32)>,
+}
+
+pub fn distance2((x, z): (i32, i32), center: [i32; 2]) -> i32 {
+    let (dx, dz) = (x - center[0], z - center[1]);
+    dx * dx + dz * dz
+}
+
+impl ChunkStreamer {
+    pub fn new(render_distance: i32, unload_distance: i32) -> ChunkStreamer {
+        ChunkStreamer {
+            render_distance,
+            unload_distance: unload_distance.max(render_distance),
+            max_requests: 64,
+            center: None,
+            queue: vec![],
+            requested: HashSet::new(),
+            missing: HashSet::new(),
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.queue.is_empty() && self.requested.is_empty()
+    }
+
+    /// Updates the loaded area around a camera position, returning
+    /// the columns which were unloaded, so their buffers can be freed.
+    pub fn update<L: ColumnLoader>(
+        &mut self,
+        position: [f32; 3],
+        manager: &mut ChunkManager,
+        loader: &mut L,
+    ) -> Vec<(i32, i32)> {
+        let center = [position[0], position[2]].map(|x| (x / 16.0).floor() as i32);
+        let unload = self.unload_distance * self.unload_distance;
+        let mut unloaded = vec![];
+        if self.center != Some(center) {
+            self.center = Some(center);
+            loader.recenter(center);
+
+            for coords in manager.chunk_column_coords() {
+                // Unsaved changes would be lost.
+                if distance2(coords, center) > unload && !manager.is_modified(coords.0, coords.1) {
+                    manager.remove_chunk_column(coords.0, coords.1);
+                    unloaded.push(coords);
+                }
+            }
+            self.missing
+                .retain(|&coords| distance2(coords, center) <= unload);
+
+            let r = self.render_distance;
+            self.queue.clear();
+            for z in center[1] - r..center[1] + r + 1 {
+                for x in center[0] - r..center[0] + r + 1 {
+                    let coords = (x, z);
+                    if distance2(coords, center) <= r * r
+                        && !manager.has_chunk_column(x, z)
+                        && !self.requested.contains(&coords)
+                        && !self.missing.contains(&coords)
+                    {
+                        self.queue.push(coords);
+                    }
+                }
+            }
+            self.queue.sort_by_key(|&coords| -distance2(coords, center));
+        }
+
+        while self.requested.len() < self.max_requests {
+            let (x, z) = match self.queue.pop() {
+                Some(coords) => coords,
+                None => break,
+            };
+            loader.request(x, z, distance2((x, z), center));
+            self.requested.insert((x, z));
+        }
+
+        while let Some((x, z, column)) = loader.poll() {
+            if !self.requested.remove(&(x, z)) || distance2((x, z), center) > unload {
+                continue;
+            }
+            match column {
+                Some(column) => manager.add_chunk_column(x, z, column),
+                None => {
+                    self.missing.insert((x, z));
+                }
+            }
+        }
+
+        unloaded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    use crate::chunk::BlockState;
+
+    // Serves columns from memory, answering requests in order.
+    struct MockLoader {
+        // Coordinates of the columns which exist.
+        world: HashSet<(i32, i32)>,
+        requests: Vec<(i32, i32)>,
+        pending: VecDeque<(i32, i32)>,
+        // Whether to answer requests right away.
+        instant: bool,
+    }
+
+    impl MockLoader {
+        fn new(world: HashSet<(i32, i32)>) -> MockLoader {
+            MockLoader {
+                world,
+                requests: vec![],
+                pending: VecDeque::new(),
+                instant: true,
+            }
+        }
+    }
+
+    impl ColumnLoader for MockLoader {
+        fn request(&mut self, x: i32, z: i32, _priority: i32) {
+            self.requests.push((x, z));
+            self.pending.push_back((x, z));
+        }
+
+        fn poll(&mut self) -> Option<(i32, i32, Option<ChunkColumn>)> {
+            if !self.instant {
+                return None;
+            }
+            let (x, z) = self.pending.pop_front()?;
+            let column = if self.world.contains(&(x, z)) {
+                Some(ChunkColumn::empty())
+            } else {
+                None
+            };
+            Some((x, z, column))
+        }
+    }
+
+    fn square(r: i32) -> HashSet<(i32, i32)> {
+        (-r..=r)
+            .flat_map(|z| (-r..=r).map(move |x| (x, z)))
+            .collect()
+    }
+
+    fn loaded(manager: &ChunkManager) -> HashSet<(i32, i32)> {
+        manager.chunk_column_coords().into_iter().collect()
+    }
+
+    #[test]
+    fn loads_within_radius() {
+        let mut manager = ChunkManager::new();
+        let mut loader = MockLoader::new(square(10));
+        let mut streamer = ChunkStreamer::new(2, 3);
+        let unloaded = streamer.update([8.0, 64.0, 8.0], &mut manager, &mut loader);
+        assert!(unloaded.is_empty());
+        assert!(streamer.is_idle());
+
+        let expected: HashSet<_> = square(2)
+            .into_iter()
+            .filter(|&c| distance2(c, [0, 0]) <= 4)
+            .collect();
+        assert_eq!(loaded(&manager), expected);
+        // Closest first.
+        assert_eq!(loader.requests[0], (0, 0));
+        let distances: Vec<_> = loader
+            .requests
+            .iter()
+            .map(|&c| distance2(c, [0, 0]))
+            .collect();
+        assert!(distances.windows(2).all(|d| d[0] <= d[1]));
+    }
+
+    #[test]
+    fn unloads_past_unload_distance() {
+        let mut manager = ChunkManager::new();
+        let mut loader = MockLoader::new(square(20));
+        let mut streamer = ChunkStreamer::new(2, 3);
+        streamer.update([8.0, 64.0, 8.0], &mut manager, &mut loader);
+        // Moving along a path, one chunk at a time.
+        for x in 1..=3 {
+            let unloaded = streamer.update(
+                [x as f32 * 16.0 + 8.0, 64.0, 8.0],
+                &mut manager,
+                &mut loader,
+            );
+            if x < 2 {
+                assert!(unloaded.is_empty());
+            }
+            for &c in unloaded.iter() {
+                assert!(distance2(c, [x, 0]) > 9);
+            }
+        }
+        assert!(loaded(&manager).iter().all(|&c| distance2(c, [3, 0]) <= 9));
+        assert!(loaded(&manager).contains(&(1, 0)));
+        assert!(!loaded(&manager).contains(&(-2, 0)));
+    }
+
+    #[test]
+    fn keeps_modified_columns() {
+        let mut manager = ChunkManager::new();
+        let mut loader = MockLoader::new(square(20));
+        let mut streamer = ChunkStreamer::new(1, 1);
+        streamer.update([8.0, 64.0, 8.0], &mut manager, &mut loader);
+        manager.set_block(0, 0, 0, BlockState { value: 1 << 4 });
+        let unloaded = streamer.update([168.0, 64.0, 8.0], &mut manager, &mut loader);
+        assert!(!unloaded.contains(&(0, 0)));
+        assert!(unloaded.contains(&(-1, 0)));
+        assert!(manager.has_chunk_column(0, 0));
+    }
+
+    #[test]
+    fn limits_requests() {
+        let mut manager = ChunkManager::new();
+        let mut loader = MockLoader::new(square(10));
+        loader.instant = false;
+        let mut streamer = ChunkStreamer::new(3, 3);
+        streamer.max_requests = 5;
+        streamer.update([8.0, 64.0, 8.0], &mut manager, &mut loader);
+        assert_eq!(loader.requests.len(), 5);
+        assert!(loader.requests.iter().all(|&c| distance2(c, [0, 0]) <= 1));
+        assert!(!streamer.is_idle());
+
+        loader.instant = true;
+        for _ in 0..10 {
+            streamer.update([8.0, 64.0, 8.0], &mut manager, &mut loader);
+        }
+        assert!(streamer.is_idle());
+        assert_eq!(loaded(&manager).len(), loader.requests.len());
+    }
+
+    #[test]
+    fn skips_missing_columns() {
+        let mut manager = ChunkManager::new();
+        let mut loader = MockLoader::new([(0, 0)].iter().cloned().collect());
+        let mut streamer = ChunkStreamer::new(1, 1);
+        streamer.update([8.0, 64.0, 8.0], &mut manager, &mut loader);
+        assert_eq!(loader.requests.len(), 5);
+        assert_eq!(loaded(&manager).len(), 1);
+
+        // Moving within the area doesn't request them again.
+        streamer.update([8.0, 64.0, 24.0], &mut manager, &mut loader);
+        let again = loader.requests[5..].to_vec();
+        assert!(!again.contains(&(1, 0)));
+        assert!(again.contains(&(0, 2)));
+    }
+}
@@ -1,6 +1,7 @@
 import java.util.Map;
 
 // Updated for 1.8-pre2.
+// Its output was converted to src/minecraft/registries/1.8.
 // Follow the grep commands to update obfuscated class names.
 
 public class DumpData {
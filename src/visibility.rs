@@ -0,0 +1,247 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::chunk::SIZE;
+use crate::cube::{self, Face};
+
+/// Which faces of a section can be seen from each other,
+/// through the blocks which aren't opaque.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct VisibilityGraph {
+    // Bit `a * 6 + b` is set if `a` and `b` are connected.
+    bits: u64,
+}
+
+// Below this many opaque blocks, a section can't separate its faces.
+const MIN_OPAQUE_TO_SEPARATE: usize = SIZE * SIZE;
+
+impl VisibilityGraph {
+    /// All faces see each other, like in an empty section.
+    pub const ALL: VisibilityGraph = VisibilityGraph {
+        bits: (1 << 36) - 1,
+    };
+    pub const NONE: VisibilityGraph = VisibilityGraph { bits: 0 };
+
+    pub fn connected(self, a: Face, b: Face) -> bool {
+        self.bits & (1 << (a as usize * 6 + b as usize)) != 0
+    }
+
+    fn connect(&mut self, a: Face, b: Face) {
+        self.bits |= 1 << (a as usize * 6 + b as usize);
+        self.bits |= 1 << (b as usize * 6 + a as usize);
+    }
+
+    /// Flood-fills the section from each block which isn't opaque,
+    /// connecting the faces reached together.
+    pub fn compute<F>(opaque: F) -> VisibilityGraph
+    where
+        F: Fn(usize, usize, usize) -> bool,
+    {
+        let index = |x: usize, y: usize, z: usize| (y * SIZE + z) * SIZE + x;
+        let mut visited = [false; SIZE * SIZE * SIZE];
+        let mut num_opaque = 0;
+        for y in 0..SIZE {
+            for z in 0..SIZE {
+                for x in 0..SIZE {
+                    if opaque(x, y, z) {
+                        visited[index(x, y, z)] = true;
+                        num_opaque += 1;
+                    }
+                }
+            }
+        }
+        if num_opaque < MIN_OPAQUE_TO_SEPARATE {
+            return VisibilityGraph::ALL;
+        }
+
+        let mut graph = VisibilityGraph::NONE;
+        let mut queue = vec![];
+        for start in 0..visited.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            queue.push(start);
+            let mut faces = vec![];
+            while let Some(i) = queue.pop() {
+                let xyz = [i % SIZE, i / (SIZE * SIZE), i / SIZE % SIZE];
+                for face in cube::FaceIterator::new() {
+                    let d = face.direction();
+                    let next = [0, 1, 2].map(|j| xyz[j].wrapping_add(d[j] as usize));
+                    if next.iter().any(|&v| v >= SIZE) {
+                        if !faces.contains(&face) {
+                            faces.push(face);
+                        }
+                        continue;
+                    }
+                    let j = index(next[0], next[1], next[2]);
+                    if !visited[j] {
+                        visited[j] = true;
+                        queue.push(j);
+                    }
+                }
+            }
+            for &a in faces.iter() {
+                for &b in faces.iter() {
+                    graph.connect(a, b);
+                }
+            }
+        }
+        graph
+    }
+}
+
+fn opposite(face: Face) -> Face {
+    Face::from_direction(face.direction().map(|x| -x)).unwrap()
+}
+
+/// Finds the sections which might be visible from the one at `start`,
+/// walking away from it through connected faces of sections.
+/// `graph` returns `None` for sections which aren't loaded, and
+/// sections are only entered if `in_view` accepts them.
+pub fn visible_sections<G, V>(start: [i32; 3], graph: G, in_view: V) -> HashSet<[i32; 3]>
+where
+    G: Fn([i32; 3]) -> Option<VisibilityGraph>,
+    V: Fn([i32; 3]) -> bool,
+{
+    // The camera can be above or below the world.
+    let start = [start[0], start[1].clamp(0, SIZE as i32 - 1), start[2]];
+    let mut visible = HashSet::new();
+    if graph(start).is_none() {
+        return visible;
+    }
+    visible.insert(start);
+
+    // Each section is entered through a face, and the directions
+    // taken so far are never walked back.
+    let mut queue = VecDeque::new();
+    queue.push_back((start, None, 0_u8));
+    while let Some((coords, entered, directions)) = queue.pop_front() {
+        let section = match graph(coords) {
+            Some(section) => section,
+            None => continue,
+        };
+        for face in cube::FaceIterator::new() {
+            if directions & (1 << opposite(face) as usize) != 0 {
+                continue;
+            }
+            if let Some(entered) = entered {
+                if !section.connected(entered, face) {
+                    continue;
+                }
+            }
+            let d = face.direction();
+            let next = [0, 1, 2].map(|i| coords[i] + d[i]);
+            if next[1] < 0 || next[1] >= SIZE as i32 || visible.contains(&next) {
+                continue;
+            }
+            if !in_view(next) || graph(next).is_none() {
+                continue;
+            }
+            visible.insert(next);
+            queue.push_back((next, Some(opposite(face)), directions | 1 << face as usize));
+        }
+    }
+    visible
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_section_connects_everything() {
+        let graph = VisibilityGraph::compute(|_, _, _| false);
+        assert_eq!(graph, VisibilityGraph::ALL);
+    }
+
+    #[test]
+    fn solid_section_connects_nothing() {
+        let graph = VisibilityGraph::compute(|_, _, _| true);
+        assert_eq!(graph, VisibilityGraph::NONE);
+    }
+
+    #[test]
+    fn floor_separates_up_and_down() {
+        let graph = VisibilityGraph::compute(|_, y, _| y == 8);
+        assert!(!graph.connected(cube::Up, cube::Down));
+        assert!(graph.connected(cube::Up, cube::North));
+        assert!(graph.connected(cube::Down, cube::East));
+        assert!(graph.connected(cube::North, cube::South));
+    }
+
+    #[test]
+    fn floor_with_hole_connects_up_and_down() {
+        let graph = VisibilityGraph::compute(|x, y, z| match y {
+            4 => (x, z) != (3, 4),
+            8 => (x, z) != (12, 9),
+            _ => false,
+        });
+        assert!(graph.connected(cube::Up, cube::Down));
+    }
+
+    #[test]
+    fn tunnel_connects_its_ends() {
+        // Solid, except for a tunnel going from west to east.
+        let graph = VisibilityGraph::compute(|_, y, z| (y, z) != (5, 5));
+        assert!(graph.connected(cube::West, cube::East));
+        assert!(!graph.connected(cube::West, cube::Up));
+        assert!(!graph.connected(cube::North, cube::South));
+        assert!(!graph.connected(cube::Up, cube::Down));
+    }
+
+    #[test]
+    fn few_opaque_blocks_connect_everything() {
+        let graph = VisibilityGraph::compute(|x, y, z| x == 0 && y == 0 && z < 8);
+        assert_eq!(graph, VisibilityGraph::ALL);
+    }
+
+    #[test]
+    fn flood_fill_stops_at_solid_sections() {
+        // A row of sections along x, the one at x = 2 being solid.
+        let graph = |[x, y, z]: [i32; 3]| match (x, y, z) {
+            (2, 0, 0) => Some(VisibilityGraph::NONE),
+            (-5..=5, 0..=1, -1..=1) => Some(VisibilityGraph::ALL),
+            _ => None,
+        };
+        let visible = visible_sections([0, 0, 0], graph, |_| true);
+        assert!(visible.contains(&[1, 0, 0]));
+        assert!(visible.contains(&[2, 0, 0]));
+        assert!(!visible.contains(&[3, 0, 0]));
+        assert!(visible.contains(&[-5, 1, 1]));
+        assert!(!visible.contains(&[6, 0, 0]));
+    }
+
+    #[test]
+    fn flood_fill_follows_connected_faces() {
+        // Sections only seeing through from west and below
+        // to east and above.
+        let stairs = {
+            let mut graph = VisibilityGraph::NONE;
+            graph.connect(cube::Down, cube::East);
+            graph.connect(cube::West, cube::Up);
+            graph.connect(cube::West, cube::East);
+            graph.connect(cube::Down, cube::Up);
+            graph
+        };
+        let graph = |[x, y, z]: [i32; 3]| {
+            if (0..4).contains(&x) && (0..4).contains(&y) && z == 0 {
+                Some(stairs)
+            } else {
+                None
+            }
+        };
+        let visible = visible_sections([0, 0, 0], graph, |_| true);
+        assert!(visible.contains(&[3, 3, 0]));
+        assert_eq!(visible.len(), 16);
+    }
+
+    #[test]
+    fn flood_fill_respects_view() {
+        let visible = visible_sections(
+            [0, 0, 0],
+            |_| Some(VisibilityGraph::ALL),
+            |[x, y, z]| (0..4).contains(&x) && y < 2 && z == 0,
+        );
+        assert_eq!(visible.len(), 8);
+    }
+}
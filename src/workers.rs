@@ -0,0 +1,177 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+struct Prioritized<J> {
+    priority: i32,
+    job: J,
+}
+
+// Ordered so that the lowest priority value is popped first.
+impl<J> Ord for Prioritized<J> {
+    fn cmp(&self, other: &Prioritized<J>) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<J> PartialOrd for Prioritized<J> {
+    fn partial_cmp(&self, other: &Prioritized<J>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<J> PartialEq for Prioritized<J> {
+    fn eq(&self, other: &Prioritized<J>) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<J> Eq for Prioritized<J> {}
+
+struct Queue<J> {
+    jobs: BinaryHeap<Prioritized<J>>,
+    closed: bool,
+}
+
+/// A pool of threads running jobs with the lowest priority value first,
+/// e.g. the squared distance to the camera.
+pub struct WorkerPool<J, T> {
+    queue: Arc<(Mutex<Queue<J>>, Condvar)>,
+    results: Receiver<T>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl<J: Send + 'static, T: Send + 'static> WorkerPool<J, T> {
+    pub fn new<F>(name: &str, num_threads: usize, f: F) -> WorkerPool<J, T>
+    where
+        F: Fn(J) -> T + Send + Sync + 'static,
+    {
+        let queue = Arc::new((
+            Mutex::new(Queue {
+                jobs: BinaryHeap::new(),
+                closed: false,
+            }),
+            Condvar::new(),
+        ));
+        let (sender, results) = channel();
+        let f = Arc::new(f);
+        let threads = (0..num_threads.max(1))
+            .map(|i| {
+                let queue = queue.clone();
+                let sender = sender.clone();
+                let f = f.clone();
+                thread::Builder::new()
+                    .name(format!("{} {}", name, i))
+                    .spawn(move || loop {
+                        let job = {
+                            let (ref lock, ref cvar) = *queue;
+                            let mut queue = lock.lock().unwrap();
+                            loop {
+                                if queue.closed {
+                                    return;
+                                }
+                                if let Some(job) = queue.jobs.pop() {
+                                    break job.job;
+                                }
+                                queue = cvar.wait(queue).unwrap();
+                            }
+                        };
+                        if sender.send(f(job)).is_err() {
+                            return;
+                        }
+                    })
+                    .unwrap()
+            })
+            .collect();
+        WorkerPool {
+            queue,
+            results,
+            threads,
+        }
+    }
+
+    pub fn push(&self, priority: i32, job: J) {
+        let (ref lock, ref cvar) = *self.queue;
+        lock.lock()
+            .unwrap()
+            .jobs
+            .push(Prioritized { priority, job });
+        cvar.notify_one();
+    }
+
+    /// Recomputes the priority of every queued job, e.g. after the camera moved.
+    pub fn reprioritize<F>(&self, mut f: F)
+    where
+        F: FnMut(&J) -> i32,
+    {
+        let (ref lock, _) = *self.queue;
+        let mut queue = lock.lock().unwrap();
+        let jobs = std::mem::take(&mut queue.jobs);
+        queue.jobs = jobs
+            .into_iter()
+            .map(|Prioritized { job, .. }| Prioritized {
+                priority: f(&job),
+                job,
+            })
+            .collect();
+    }
+
+    /// Returns a finished result, without blocking.
+    pub fn try_recv(&self) -> Option<T> {
+        self.results.try_recv().ok()
+    }
+}
+
+impl<J, T> Drop for WorkerPool<J, T> {
+    fn drop(&mut self) {
+        let (ref lock, ref cvar) = *self.queue;
+        lock.lock().unwrap().closed = true;
+        cvar.notify_all();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn reprioritize() {
+        // Job 0 holds the only thread until the others are queued.
+        let (started, wait_started) = channel();
+        let (open, gate) = channel::<()>();
+        let locks = Mutex::new((started, gate));
+        let pool = WorkerPool::new("test", 1, move |x: i32| {
+            if x == 0 {
+                let locks = locks.lock().unwrap();
+                locks.0.send(()).unwrap();
+                locks.1.recv().unwrap();
+            }
+            x
+        });
+        pool.push(0, 0);
+        wait_started.recv().unwrap();
+
+        // Queued by the distance to 0, then the camera moves to 5.
+        for x in 1..6 {
+            pool.push(x * x, x);
+        }
+        pool.reprioritize(|&x| (x - 5) * (x - 5));
+        open.send(()).unwrap();
+
+        let mut done = vec![];
+        let start = Instant::now();
+        while done.len() < 6 && start.elapsed() < Duration::from_secs(10) {
+            match pool.try_recv() {
+                Some(x) => done.push(x),
+                None => thread::yield_now(),
+            }
+        }
+        assert_eq!(done, vec![0, 5, 4, 3, 2, 1]);
+    }
+}